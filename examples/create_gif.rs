@@ -6,7 +6,7 @@
 // 3. Add them to a GIF structure
 // 4. Save to file
 
-use gif_toolkit::core::{Frame, Gif};
+use gif_toolkit::core::{Frame, Gif, Loop};
 
 fn main() -> anyhow::Result<()> {
     let width = 100u16;
@@ -41,8 +41,8 @@ fn main() -> anyhow::Result<()> {
         gif.add_frame(frame);
     }
 
-    // Set loop count to infinite (0)
-    gif.loop_count = 0;
+    // Loop forever
+    gif.loop_count = Loop::Infinite;
 
     println!("Created GIF with {} frames", gif.frame_count());
     println!("Dimensions: {}x{}", gif.width, gif.height);
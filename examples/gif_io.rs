@@ -6,7 +6,7 @@
 // 3. Modify the GIF
 // 4. Save it back to disk
 
-use gif_toolkit::core::{Frame, Gif};
+use gif_toolkit::core::Gif;
 use std::env;
 
 fn main() -> anyhow::Result<()> {
@@ -35,18 +35,11 @@ fn main() -> anyhow::Result<()> {
     println!("  Dimensions: {}x{}", gif.width, gif.height);
     println!("  Frames: {}", gif.frame_count());
     println!("  Total duration: {} ms", gif.total_duration() * 10);
-    println!(
-        "  Loop count: {}",
-        if gif.loop_count == 0 {
-            "Infinite".to_string()
-        } else {
-            gif.loop_count.to_string()
-        }
-    );
+    println!("  Loop count: {}", gif.loop_count);
     println!(
         "  Global palette: {}",
-        if gif.global_palette.is_some() {
-            format!("{} colors", gif.global_palette.as_ref().unwrap().len())
+        if let Some(palette) = &gif.global_palette {
+            format!("{} colors", palette.len())
         } else {
             "None".to_string()
         }
@@ -6,7 +6,38 @@ use std::fs;
 use std::path::PathBuf;
 
 // Import gif-toolkit library
-use gif_toolkit::core::Gif;
+use gif_toolkit::core::{Gif, GifError};
+use gif_toolkit::operations::info::format_hex_color;
+use gif_toolkit::operations::spec::{self, ParamKind};
+
+/// Error returned by every command, with a stable code the frontend can
+/// switch on (`FILE_NOT_FOUND`, `NOT_A_GIF`, `ENCODE_FAILED`,
+/// `OPERATION_FAILED`, `INTERNAL`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppError {
+    code: String,
+    message: String,
+}
+
+impl AppError {
+    fn new(code: &str, message: String) -> Self {
+        Self {
+            code: code.to_string(),
+            message,
+        }
+    }
+
+    /// Classify a library error by its `GifError` category
+    fn from_error(context: &str, error: anyhow::Error) -> Self {
+        let code = match error.downcast_ref::<GifError>() {
+            Some(GifError::FileNotFound(_)) => "FILE_NOT_FOUND",
+            Some(GifError::NotAGif(_)) => "NOT_A_GIF",
+            Some(GifError::EncodeFailed(_)) => "ENCODE_FAILED",
+            None => "OPERATION_FAILED",
+        };
+        Self::new(code, format!("{}: {:#}", context, error))
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GifInfo {
@@ -18,8 +49,9 @@ pub struct GifInfo {
     frame_count: usize,
     duration_sec: f64,
     avg_delay_ms: u32,
-    loop_count: u16,
+    loop_count: String,
     has_palette: bool,
+    dominant_color: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,33 +62,53 @@ pub struct ProcessResult {
     compression_ratio: Option<f64>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompressPreview {
+    estimated_size: u64,
+    sampled_frames: usize,
+    preview_png_base64: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParamSpec {
+    name: String,
+    description: String,
+    kind: String,
+    required: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    default: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperationSpec {
+    name: String,
+    description: String,
+    params: Vec<ParamSpec>,
+}
+
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
-fn get_gif_info(file_path: String) -> Result<GifInfo, String> {
+fn get_gif_info(file_path: String) -> Result<GifInfo, AppError> {
     let path = PathBuf::from(&file_path);
 
-    // Check if file exists
-    if !path.exists() {
-        return Err(format!("File does not exist: {}", file_path));
-    }
+    // Load GIF (a missing file is reported as FILE_NOT_FOUND)
+    let gif =
+        Gif::from_file(&file_path).map_err(|e| AppError::from_error("Failed to load GIF", e))?;
 
     // Get file size
     let metadata = fs::metadata(&path)
-        .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+        .map_err(|e| AppError::new("INTERNAL", format!("Failed to read file metadata: {}", e)))?;
     let file_size = metadata.len();
     let file_size_mb = file_size as f64 / (1024.0 * 1024.0);
 
-    // Load GIF
-    let gif = Gif::from_file(&file_path)
-        .map_err(|e| format!("Failed to load GIF: {}", e))?;
-
     // Calculate duration
-    let total_duration_cs = gif.total_duration();
-    let duration_sec = total_duration_cs as f64 / 100.0;
+    let duration = gif.duration();
+    let duration_sec = duration.as_secs_f64();
 
     // Calculate average frame delay
     let avg_delay_ms = if !gif.frames.is_empty() {
-        (total_duration_cs / gif.frame_count() as u32) * 10
+        (duration / gif.frame_count() as u32).as_millis() as u32
     } else {
         0
     };
@@ -70,8 +122,9 @@ fn get_gif_info(file_path: String) -> Result<GifInfo, String> {
         frame_count: gif.frame_count(),
         duration_sec,
         avg_delay_ms,
-        loop_count: gif.loop_count,
+        loop_count: gif.loop_count.to_string(),
         has_palette: gif.global_palette.is_some(),
+        dominant_color: format_hex_color(gif.dominant_color()),
     })
 }
 
@@ -80,7 +133,7 @@ fn process_speed(
     input_path: String,
     output_path: String,
     factor: f64,
-) -> Result<ProcessResult, String> {
+) -> Result<ProcessResult, AppError> {
     // Get original file size
     let original_size = fs::metadata(&input_path)
         .map(|m| m.len())
@@ -90,7 +143,7 @@ fn process_speed(
     use gif_toolkit::operations::speed;
 
     speed::run(&input_path, &output_path, factor)
-        .map_err(|e| format!("Speed adjustment failed: {}", e))?;
+        .map_err(|e| AppError::from_error("Speed adjustment failed", e))?;
 
     // Get output file size
     let output_size = fs::metadata(&output_path)
@@ -120,7 +173,7 @@ fn process_compress(
     input_path: String,
     output_path: String,
     percent: u8,
-) -> Result<ProcessResult, String> {
+) -> Result<ProcessResult, AppError> {
     // Get original file size
     let original_size = fs::metadata(&input_path)
         .map(|m| m.len())
@@ -130,7 +183,7 @@ fn process_compress(
     use gif_toolkit::operations::compress;
 
     compress::run(&input_path, &output_path, percent)
-        .map_err(|e| format!("Compression failed: {}", e))?;
+        .map_err(|e| AppError::from_error("Compression failed", e))?;
 
     // Get output file size
     let output_size = fs::metadata(&output_path)
@@ -161,7 +214,7 @@ fn process_tune(
     output_path: String,
     width: Option<u32>,
     height: Option<u32>,
-) -> Result<ProcessResult, String> {
+) -> Result<ProcessResult, AppError> {
     // Get original file size
     let original_size = fs::metadata(&input_path)
         .map(|m| m.len())
@@ -171,7 +224,7 @@ fn process_tune(
     use gif_toolkit::operations::tune;
 
     tune::run(&input_path, &output_path, width, height)
-        .map_err(|e| format!("Tune operation failed: {}", e))?;
+        .map_err(|e| AppError::from_error("Tune operation failed", e))?;
 
     // Get output file size
     let output_size = fs::metadata(&output_path)
@@ -203,14 +256,105 @@ fn process_tune(
     })
 }
 
+/// Fast compression estimate for slider feedback; returns `CompressPreview` as JSON
+#[tauri::command]
+fn compress_preview(
+    file_path: String,
+    percent: u8,
+    sample_frames: usize,
+) -> Result<String, AppError> {
+    use gif_toolkit::operations::compress;
+    use gif_toolkit::utils::base64_encode;
+
+    let preview = compress::preview(&file_path, percent, sample_frames)
+        .map_err(|e| AppError::from_error("Compression preview failed", e))?;
+
+    serde_json::to_string(&CompressPreview {
+        estimated_size: preview.estimated_size,
+        sampled_frames: preview.sampled_frames,
+        preview_png_base64: base64_encode(&preview.preview_png),
+    })
+    .map_err(|e| AppError::new("INTERNAL", format!("Failed to serialize preview: {}", e)))
+}
+
+#[tauri::command]
+fn list_operations() -> Vec<OperationSpec> {
+    // The definitions live in the library so the CLI and GUI stay in sync
+    spec::OPERATIONS
+        .iter()
+        .map(|operation| OperationSpec {
+            name: operation.name.to_string(),
+            description: operation.description.to_string(),
+            params: operation
+                .params
+                .iter()
+                .map(|param| ParamSpec {
+                    name: param.name.to_string(),
+                    description: param.description.to_string(),
+                    kind: match param.kind {
+                        ParamKind::Float => "float",
+                        ParamKind::Integer => "integer",
+                    }
+                    .to_string(),
+                    required: param.required,
+                    min: param.min,
+                    max: param.max,
+                    default: param.default,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 fn main() {
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             get_gif_info,
             process_speed,
             process_compress,
-            process_tune
+            process_tune,
+            list_operations,
+            compress_preview
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_operations() {
+        let operations = list_operations();
+
+        for (name, param) in [
+            ("speed", "factor"),
+            ("compress", "percent"),
+            ("tune", "width"),
+        ] {
+            let operation = operations
+                .iter()
+                .find(|operation| operation.name == name)
+                .unwrap_or_else(|| panic!("missing operation {}", name));
+            let spec = operation
+                .params
+                .iter()
+                .find(|spec| spec.name == param)
+                .unwrap_or_else(|| panic!("missing parameter {}", param));
+            assert!(!spec.kind.is_empty());
+            assert!(spec.min.is_some());
+        }
+    }
+
+    #[test]
+    fn test_missing_file_error_code() {
+        let error = get_gif_info("does/not/exist.gif".to_string()).unwrap_err();
+        assert_eq!(error.code, "FILE_NOT_FOUND");
+        assert!(error.message.contains("does/not/exist.gif"));
+
+        let error = process_speed("does/not/exist.gif".to_string(), "out.gif".to_string(), 2.0)
+            .unwrap_err();
+        assert_eq!(error.code, "FILE_NOT_FOUND");
+    }
+}
@@ -1,3 +1,10 @@
+use crate::operations::annotate::Shape;
+use crate::operations::colorblind::CbKind;
+use crate::operations::compress::{ColorSpace, QualityMetric, DEFAULT_EFFORT};
+use crate::operations::filmstrip::Direction;
+use crate::operations::scenes::DEFAULT_SCENE_THRESHOLD;
+use crate::operations::set_disposal::Disposal;
+use crate::utils::{parse_hex_color, parse_hex_rgba, FrameSelector};
 use clap::{Parser, Subcommand};
 
 /// GIF Toolkit - A powerful cross-platform GIF optimization and manipulation toolkit
@@ -9,6 +16,40 @@ use clap::{Parser, Subcommand};
 pub struct Args {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Only print errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print debug-level details for each step
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Print wall-clock time per stage and frame throughput
+    #[arg(long, global = true)]
+    pub timings: bool,
+}
+
+impl Args {
+    /// Log level selected by `--quiet` / `--verbose` (info by default)
+    pub fn log_level(&self) -> log::LevelFilter {
+        if self.quiet {
+            log::LevelFilter::Error
+        } else if self.verbose {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Info
+        }
+    }
+
+    /// Log level for stage timings: shown only with `--timings`
+    pub fn timings_level(&self) -> log::LevelFilter {
+        if self.timings {
+            log::LevelFilter::Info
+        } else {
+            log::LevelFilter::Off
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -26,6 +67,19 @@ pub enum Commands {
         /// Speed factor (e.g., 2.0 for 2x faster, 0.5 for 2x slower)
         #[arg(short, long)]
         factor: f64,
+
+        /// Never drop frames, even for extreme speedups (only scale delays)
+        #[arg(long)]
+        no_drop: bool,
+
+        /// Keep interlaced input frames interlaced in the output
+        #[arg(long)]
+        preserve_interlace: bool,
+
+        /// Write frames with a source delay of 0 ("as fast as possible") with
+        /// delay 0 again instead of raising them to 1
+        #[arg(long)]
+        emit_zero_delay: bool,
     },
 
     /// Compress GIF file size
@@ -41,6 +95,45 @@ pub enum Commands {
         /// Compression percentage (1-99)
         #[arg(short, long)]
         percent: u8,
+
+        /// Merge duplicated frames at the loop boundary
+        #[arg(long)]
+        optimize_loop: bool,
+
+        /// Report quality loss between input and output
+        #[arg(long)]
+        report: bool,
+
+        /// Metric used by --report: psnr or ssim
+        #[arg(long, default_value = "psnr")]
+        metric: QualityMetric,
+
+        /// Print per-frame PSNR and highlight the N worst frames (default 5)
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "5")]
+        report_per_frame: Option<usize>,
+
+        /// Search effort (1-9): lower is faster, higher tries harder for a
+        /// smaller file
+        #[arg(long, default_value_t = DEFAULT_EFFORT)]
+        effort: u8,
+
+        /// Limit the palette to 2^N colors (1-8) for a smaller file
+        #[arg(long)]
+        palette_bits: Option<u8>,
+
+        /// Color space for matching pixels to the reduced palette: rgb, or
+        /// oklab to follow perceived difference
+        #[arg(long, default_value = "rgb")]
+        color_space: ColorSpace,
+
+        /// Keep the exact original colors (inputs with at most 256 colors);
+        /// only lossless reductions are applied
+        #[arg(long, conflicts_with_all = ["palette_bits", "color_space", "effort"])]
+        preserve_palette: bool,
+
+        /// Uniformly drop frames down to at most N, keeping the total duration
+        #[arg(long)]
+        max_frames: Option<usize>,
     },
 
     /// Tune GIF parameters (resize, crop, etc.)
@@ -53,13 +146,18 @@ pub enum Commands {
         #[arg(short, long)]
         output: String,
 
-        /// New width in pixels
+        /// New width in pixels (at most 65535)
         #[arg(short, long)]
         width: Option<u32>,
 
-        /// New height in pixels
+        /// New height in pixels (at most 65535)
         #[arg(short, long)]
         height: Option<u32>,
+
+        /// Scale each stored frame by the same factor, keeping partial frames
+        /// and their offsets instead of compositing to full frames first
+        #[arg(long)]
+        resize_frames_independently: bool,
     },
 
     /// Display GIF information
@@ -67,5 +165,581 @@ pub enum Commands {
         /// Input GIF file path
         #[arg(short, long)]
         input: String,
+
+        /// Report color contrast and color-blindness concerns
+        #[arg(long)]
+        a11y: bool,
+    },
+
+    /// Preview a frame in the terminal using ANSI colors
+    Preview {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Frame index to render (0-based)
+        #[arg(short, long, default_value_t = 0)]
+        frame: usize,
+    },
+
+    /// Clean up alpha values using a threshold
+    Alpha {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Alpha values below this become fully transparent (0-255)
+        #[arg(short, long, default_value_t = 128)]
+        threshold: u8,
+
+        /// Also make alpha values at or above the threshold fully opaque
+        #[arg(short, long)]
+        binarize: bool,
+
+        /// Only adjust these frames, e.g. "1,3,5-9,^2" (0-based, ^ excludes)
+        #[arg(long)]
+        frames: Option<FrameSelector>,
+    },
+
+    /// Resample to a constant frame rate for uniform playback
+    ConstantFps {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Target frames per second (at most 100)
+        #[arg(short, long)]
+        fps: f64,
+
+        /// Use the same rounded delay for every frame, even if the total
+        /// duration drifts from the input
+        #[arg(long)]
+        no_preserve_timing: bool,
+    },
+
+    /// Overlay all frames into a single motion-trail PNG
+    Onionskin {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output PNG file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Opacity multiplier per step back in time (0.0-1.0)
+        #[arg(short, long, default_value_t = 0.7)]
+        falloff: f32,
+    },
+
+    /// Export a heatmap GIF of per-pixel differences between frames
+    Heatmap {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+    },
+
+    /// Export an RGB histogram PNG of the colors across all frames
+    Histogram {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output PNG file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Also write the raw per-channel counts to this JSON file
+        #[arg(long)]
+        json_out: Option<String>,
+    },
+
+    /// Losslessly optimize GIF file size (no color changes)
+    Optimize {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Drop a truncated or corrupt last frame that flashes on loop
+        #[arg(long)]
+        drop_bad_last: bool,
+    },
+
+    /// Save a single composited frame as a static GIF
+    PickFrame {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Frame index (0-based; negative counts from the end, -1 = last frame)
+        #[arg(short = 'n', long, allow_negative_numbers = true)]
+        index: i64,
+    },
+
+    /// Simulate how a GIF looks with a color vision deficiency
+    Colorblind {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Deficiency to simulate: protanopia, deuteranopia or tritanopia
+        #[arg(long = "cb-kind")]
+        kind: CbKind,
+    },
+
+    /// Draw filled or outlined rectangles over every frame
+    Annotate {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Rectangle "x,y,w,h,#rrggbb" (filled) or "x,y,w,h,#rrggbb,thickness"
+        /// (outline); repeat for more shapes
+        #[arg(long = "rect", required = true)]
+        rects: Vec<Shape>,
+    },
+
+    /// Force one disposal method on every frame (e.g. to fix flicker)
+    SetDisposal {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Disposal method for every frame: keep, background (clear to
+        /// transparent) or previous (restore what was on screen)
+        #[arg(long = "disposal")]
+        method: Disposal,
+    },
+
+    /// Repeat the whole animation N times in the file
+    Repeat {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Number of copies of the animation in the output
+        #[arg(short = 'n', long)]
+        times: u32,
+
+        /// Play the result once instead of looping forever
+        #[arg(long)]
+        play_once: bool,
+    },
+
+    /// Compare two GIFs frame by frame (exits non-zero if they differ)
+    Diff {
+        /// First GIF file path
+        a: String,
+
+        /// Second GIF file path
+        b: String,
+
+        /// Largest per-channel pixel difference still counted as equal (0-255)
+        #[arg(short, long, default_value_t = 0)]
+        tolerance: u8,
+    },
+
+    /// List supported output formats, optional features and subcommands
+    Capabilities {
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Shift frame content further on every frame (pan effect)
+    Pan {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Horizontal shift per frame in pixels (negative moves left)
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        dx: i32,
+
+        /// Vertical shift per frame in pixels (negative moves up)
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        dy: i32,
+
+        /// Wrap content around the edges instead of exposing transparency
+        #[arg(long)]
+        wrap: bool,
     },
+
+    /// Export grayscale GIFs of the red, green, blue and alpha channels
+    Channels {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Directory for red.gif, green.gif, blue.gif and alpha.gif
+        #[arg(short, long)]
+        output_dir: String,
+    },
+
+    /// Burn the frame number and timestamp into the corner of every frame
+    Stamp {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Stamp the frame number (#index)
+        #[arg(long)]
+        show_index: bool,
+
+        /// Stamp the time each frame appears (seconds)
+        #[arg(long)]
+        show_time: bool,
+    },
+
+    /// Reduce the GIF to at most N colors using one shared palette
+    Quantize {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Maximum number of colors (1-256)
+        #[arg(short, long)]
+        colors: u16,
+    },
+
+    /// Lay out all composited frames in a single row or column PNG
+    Filmstrip {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output PNG file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Lay frames out in a row or a column: horizontal or vertical
+        #[arg(short, long, default_value = "horizontal")]
+        direction: Direction,
+    },
+
+    /// Split a long GIF into shorter GIFs of consecutive frames
+    SplitChunks {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Path prefix for the chunks (writes <prefix>_001.gif, ...)
+        #[arg(short, long)]
+        output_prefix: String,
+
+        /// Maximum number of frames in each chunk
+        #[arg(short = 'n', long)]
+        frames_per_chunk: usize,
+    },
+
+    /// Uniformly drop frames down to a maximum count, keeping the total duration
+    LimitFrames {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Maximum number of frames in the output
+        #[arg(short = 'n', long)]
+        max_frames: usize,
+    },
+
+    /// Split a GIF into one PNG per composited frame
+    Split {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output directory for the PNG files
+        #[arg(short, long)]
+        output: String,
+
+        /// Write palette-indexed PNGs using the GIF's global palette
+        #[arg(long)]
+        indexed: bool,
+    },
+
+    /// Map brightness onto a two-color gradient (duotone)
+    Duotone {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Color for black, as #rrggbb
+        #[arg(long, value_parser = parse_hex_color)]
+        dark: [u8; 3],
+
+        /// Color for white, as #rrggbb
+        #[arg(long, value_parser = parse_hex_color)]
+        light: [u8; 3],
+    },
+
+    /// Add a solid-color border around every frame
+    Border {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Border width in pixels
+        #[arg(short, long)]
+        width: u32,
+
+        /// Border color, as #rrggbb or #rrggbbaa
+        #[arg(short, long, value_parser = parse_hex_rgba)]
+        color: [u8; 4],
+    },
+
+    /// Write a small single-frame thumbnail (.png or .gif by extension)
+    Thumbnail {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output .png or .gif file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Length of the thumbnail's longer side in pixels
+        #[arg(short, long, default_value_t = 128)]
+        size: u16,
+
+        /// 0-based index of the frame to use
+        #[arg(short, long, default_value_t = 0)]
+        frame: usize,
+    },
+
+    /// Ramp playback speed linearly from a start to an end factor
+    SpeedRamp {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Speed factor for the first frame (e.g., 0.5 for half speed)
+        #[arg(short, long)]
+        start: f64,
+
+        /// Speed factor for the last frame (e.g., 2.0 for double speed)
+        #[arg(short, long)]
+        end: f64,
+    },
+
+    /// Merge consecutive near-duplicate frames, keeping the total duration
+    Dedup {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Average per-pixel difference (0-255) below which frames merge
+        #[arg(short, long, default_value_t = 1)]
+        threshold: u8,
+
+        /// Only merge frames at most this many positions apart (default: no limit)
+        #[arg(long)]
+        dedup_window: Option<usize>,
+    },
+
+    /// List the frames where a new scene starts (big visual cuts)
+    Scenes {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Average per-pixel difference (0-255) above which a frame starts a
+        /// new scene
+        #[arg(short, long, default_value_t = DEFAULT_SCENE_THRESHOLD)]
+        threshold: u8,
+    },
+
+    /// Set every frame to the same delay, ignoring the original timing
+    SetDelay {
+        /// Input GIF file path
+        #[arg(short, long)]
+        input: String,
+
+        /// Output GIF file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Delay for every frame in centiseconds (10ms units, at least 1)
+        #[arg(short, long)]
+        delay: u16,
+    },
+
+    /// Apply a chain of effects, e.g. "grayscale|resize:200|speed:2.0"
+    Pipeline {
+        /// Input GIF file path
+        input: String,
+
+        /// Output GIF file path
+        output: String,
+
+        /// `|`-separated stages: grayscale, duotone:#dark,#light,
+        /// colorblind:KIND, resize:W or resize:WxH, speed:FACTOR,
+        /// set-delay:CENTISECONDS, limit-frames:N
+        spec: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_log_flags() {
+        let args = Args::try_parse_from(["gif-toolkit", "-q", "info", "-i", "a.gif"]).unwrap();
+        assert!(args.quiet);
+        assert_eq!(args.log_level(), log::LevelFilter::Error);
+
+        // Global flags are also accepted after the subcommand
+        let command = "gif-toolkit speed -i a.gif -o b.gif -f 2 --verbose";
+        let args = Args::try_parse_from(command.split(' ')).unwrap();
+        assert!(args.verbose);
+        assert_eq!(args.log_level(), log::LevelFilter::Debug);
+
+        let args = Args::try_parse_from(["gif-toolkit", "info", "-i", "a.gif"]).unwrap();
+        assert_eq!(args.log_level(), log::LevelFilter::Info);
+
+        assert!(Args::try_parse_from(["gif-toolkit", "-q", "-v", "info", "-i", "a.gif"]).is_err());
+
+        let command = "gif-toolkit speed -i a.gif -o b.gif -f 2 --timings";
+        let args = Args::try_parse_from(command.split(' ')).unwrap();
+        assert_eq!(args.timings_level(), log::LevelFilter::Info);
+        assert_eq!(args.log_level(), log::LevelFilter::Info);
+
+        let args =
+            Args::try_parse_from(["gif-toolkit", "--timings", "info", "-i", "a.gif"]).unwrap();
+        assert_eq!(args.timings_level(), log::LevelFilter::Info);
+        let command = "gif-toolkit compress -i a.gif -o b.gif -p 50 --timings";
+        let args = Args::try_parse_from(command.split(' ')).unwrap();
+        assert_eq!(args.timings_level(), log::LevelFilter::Info);
+
+        let args = Args::try_parse_from(["gif-toolkit", "info", "-i", "a.gif"]).unwrap();
+        assert_eq!(args.timings_level(), log::LevelFilter::Off);
+    }
+
+    #[test]
+    fn test_pick_frame_negative_index() {
+        let command = "gif-toolkit pick-frame -i a.gif -o b.gif --index -1";
+        let args = Args::try_parse_from(command.split(' ')).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::PickFrame { index: -1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_report_per_frame_optional_count() {
+        let parse = |command: &str| {
+            let args = Args::try_parse_from(command.split(' ')).unwrap();
+            match args.command {
+                Commands::Compress {
+                    report_per_frame, ..
+                } => report_per_frame,
+                _ => unreachable!(),
+            }
+        };
+
+        let base = "gif-toolkit compress -i a.gif -o b.gif -p 50";
+        assert_eq!(parse(base), None);
+        assert_eq!(parse(&format!("{} --report-per-frame", base)), Some(5));
+        assert_eq!(parse(&format!("{} --report-per-frame 2", base)), Some(2));
+    }
+
+    #[test]
+    fn test_preserve_palette_conflicts() {
+        let base = "gif-toolkit compress -i a.gif -o b.gif -p 50 --preserve-palette";
+        assert!(Args::try_parse_from(base.split(' ')).is_ok());
+        for flag in ["--palette-bits 4", "--color-space oklab", "--effort 9"] {
+            let command = format!("{} {}", base, flag);
+            assert!(
+                Args::try_parse_from(command.split(' ')).is_err(),
+                "{}",
+                flag
+            );
+        }
+    }
+
+    #[test]
+    fn test_enum_options_parse_by_name() {
+        let command = "gif-toolkit compress -i a.gif -o b.gif -p 50 --color-space Oklab";
+        let args = Args::try_parse_from(command.split(' ')).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Compress {
+                color_space: ColorSpace::Oklab,
+                metric: QualityMetric::Psnr,
+                ..
+            }
+        ));
+
+        let command = "gif-toolkit colorblind -i a.gif -o b.gif --cb-kind blue";
+        assert!(Args::try_parse_from(command.split(' ')).is_err());
+    }
 }
@@ -2,8 +2,9 @@
 
 use anyhow::{Context, Result};
 use gif::{Encoder, Frame as GifFrame, Repeat, DisposalMethod};
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 
 /// Represents a single frame in a GIF image
 #[derive(Debug, Clone)]
@@ -16,10 +17,30 @@ pub struct Frame {
     pub height: u16,
     /// Delay time in 10ms units (e.g., 10 = 100ms)
     pub delay: u16,
+    /// Delay as stored in the source file, before the minimum delay was
+    /// applied (see [`DecodeOptions::min_delay_cs`]); 0 means "as fast as
+    /// the renderer allows". Frames not loaded from a file use their initial
+    /// delay.
+    pub original_delay: u16,
     /// Whether this frame has transparency
     pub transparent: bool,
     /// Disposal method for this frame
     pub disposal: DisposalMethod,
+    /// Whether `data` currently stores premultiplied alpha
+    /// (see [`Frame::premultiply`])
+    pub premultiplied: bool,
+    /// Horizontal position of the frame on the GIF canvas
+    pub left: u16,
+    /// Vertical position of the frame on the GIF canvas
+    pub top: u16,
+    /// Whether the source frame was stored interlaced (`data` is always in
+    /// normal row order)
+    pub interlaced: bool,
+    /// Free-form markers (e.g. "keyframe") set by library users
+    ///
+    /// Tags are not stored in the GIF file. Operations that drop frames log
+    /// a warning when a tagged frame is removed.
+    pub tags: Vec<String>,
 }
 
 impl Frame {
@@ -31,8 +52,14 @@ impl Frame {
             width,
             height,
             delay: 10, // Default 100ms delay
+            original_delay: 10,
             transparent: false,
             disposal: DisposalMethod::Keep,
+            premultiplied: false,
+            left: 0,
+            top: 0,
+            interlaced: false,
+            tags: Vec::new(),
         }
     }
 
@@ -46,8 +73,14 @@ impl Frame {
             width,
             height,
             delay: 10,
+            original_delay: 10,
             transparent: false,
             disposal: DisposalMethod::Keep,
+            premultiplied: false,
+            left: 0,
+            top: 0,
+            interlaced: false,
+            tags: Vec::new(),
         }
     }
 
@@ -67,6 +100,277 @@ impl Frame {
         self.height = height as u16;
         self.data = buffer.as_raw().clone();
     }
+
+    /// Convert the frame to an owned `DynamicImage` (RGBA8)
+    pub fn to_dynamic_image(&self) -> image::DynamicImage {
+        image::DynamicImage::ImageRgba8(self.to_image_buffer())
+    }
+
+    /// Create a frame from any `DynamicImage`, converting it to RGBA8
+    ///
+    /// Images larger than 65535 pixels in either dimension cannot be
+    /// represented in a GIF and are cropped to that limit.
+    pub fn from_dynamic_image(img: &image::DynamicImage, delay: u16) -> Frame {
+        let mut rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        if width > u16::MAX as u32 || height > u16::MAX as u32 {
+            rgba = image::imageops::crop_imm(
+                &rgba,
+                0,
+                0,
+                width.min(u16::MAX as u32),
+                height.min(u16::MAX as u32),
+            )
+            .to_image();
+        }
+
+        let (width, height) = (rgba.width() as u16, rgba.height() as u16);
+        let mut frame = Frame::from_rgba(rgba.into_raw(), width, height);
+        frame.delay = delay;
+        frame.original_delay = delay;
+        frame.transparent = frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+        frame
+    }
+
+    /// Convert pixel data to premultiplied alpha (color channels scaled by alpha)
+    ///
+    /// Compositing in premultiplied space avoids repeated un/re-multiplication.
+    /// Does nothing if the frame is already premultiplied.
+    pub fn premultiply(&mut self) {
+        if self.premultiplied {
+            return;
+        }
+
+        for pixel in self.data.chunks_exact_mut(4) {
+            let alpha = pixel[3] as u32;
+            for c in &mut pixel[0..3] {
+                *c = ((*c as u32 * alpha + 127) / 255) as u8;
+            }
+        }
+        self.premultiplied = true;
+    }
+
+    /// Convert pixel data back to straight (non-premultiplied) alpha
+    ///
+    /// Does nothing if the frame is not premultiplied. Color precision lost
+    /// for low alpha values during premultiplication cannot be recovered.
+    pub fn unpremultiply(&mut self) {
+        if !self.premultiplied {
+            return;
+        }
+
+        for pixel in self.data.chunks_exact_mut(4) {
+            unpremultiply_pixel(pixel);
+        }
+        self.premultiplied = false;
+    }
+
+    /// Map every pixel to the index of its nearest palette color
+    ///
+    /// Returns the per-pixel index buffer plus the index used for fully
+    /// transparent pixels, if the frame has any. That index is `palette.len()`
+    /// (one past the end, so callers must append an entry for it), unless the
+    /// palette already holds 256 colors, in which case the last entry is
+    /// given up for transparency.
+    pub fn to_indexed(&self, palette: &[[u8; 3]]) -> (Vec<u8>, Option<u8>) {
+        let has_transparency = self.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+
+        let (usable, transparent_index) = if !has_transparency {
+            (palette.len().min(256), None)
+        } else if palette.len() < 256 {
+            (palette.len(), Some(palette.len() as u8))
+        } else {
+            (255, Some(255))
+        };
+        let usable = &palette[..usable];
+
+        // Frames typically reuse a handful of colors, so cache lookups
+        let mut cache: HashMap<[u8; 3], u8> = HashMap::new();
+        let mut indices = Vec::with_capacity(self.data.len() / 4);
+
+        for pixel in self.data.chunks_exact(4) {
+            if pixel[3] == 0 {
+                indices.push(transparent_index.unwrap_or(0));
+                continue;
+            }
+
+            let rgb = [pixel[0], pixel[1], pixel[2]];
+            let index = *cache
+                .entry(rgb)
+                .or_insert_with(|| nearest_palette_index(usable, rgb));
+            indices.push(index);
+        }
+
+        (indices, transparent_index)
+    }
+
+    /// Copy a sub-rectangle of this frame into a new frame
+    ///
+    /// The new frame keeps this frame's delay, transparency and disposal, and
+    /// is positioned at the canvas origin.
+    pub fn crop(&self, x: u16, y: u16, w: u16, h: u16) -> Result<Frame> {
+        if w == 0 || h == 0 {
+            anyhow::bail!("Crop size must be non-zero, got {}x{}", w, h);
+        }
+
+        if x as u32 + w as u32 > self.width as u32 || y as u32 + h as u32 > self.height as u32 {
+            anyhow::bail!(
+                "Crop region {}x{} at ({}, {}) exceeds frame size {}x{}",
+                w,
+                h,
+                x,
+                y,
+                self.width,
+                self.height
+            );
+        }
+
+        let src_stride = (self.width as usize) * 4;
+        let row_bytes = (w as usize) * 4;
+        let mut data = Vec::with_capacity(row_bytes * h as usize);
+
+        for row in (y as usize)..(y as usize + h as usize) {
+            let start = row * src_stride + (x as usize) * 4;
+            data.extend_from_slice(&self.data[start..start + row_bytes]);
+        }
+
+        Ok(Frame {
+            data,
+            width: w,
+            height: h,
+            delay: self.delay,
+            original_delay: self.original_delay,
+            transparent: self.transparent,
+            disposal: self.disposal,
+            premultiplied: self.premultiplied,
+            left: 0,
+            top: 0,
+            interlaced: self.interlaced,
+            tags: self.tags.clone(),
+        })
+    }
+
+    /// Alpha-blend this frame onto an RGBA canvas at the given position
+    ///
+    /// Uses the standard "source over" operator. The canvas must be in the same
+    /// alpha mode as the frame (premultiplied or straight). Pixels falling
+    /// outside the canvas are clipped.
+    pub fn composite_onto(&self, canvas: &mut [u8], canvas_w: u16, x: u16, y: u16) {
+        let canvas_stride = (canvas_w as usize) * 4;
+        if canvas_stride == 0 {
+            return;
+        }
+        let canvas_h = canvas.len() / canvas_stride;
+
+        let visible_w = (self.width as usize).min((canvas_w as usize).saturating_sub(x as usize));
+        let visible_h = (self.height as usize).min(canvas_h.saturating_sub(y as usize));
+        let frame_stride = (self.width as usize) * 4;
+
+        for row in 0..visible_h {
+            let src_row = row * frame_stride;
+            let dst_row = (y as usize + row) * canvas_stride + (x as usize) * 4;
+
+            for col in 0..visible_w {
+                let src = &self.data[src_row + col * 4..src_row + col * 4 + 4];
+                let dst = &mut canvas[dst_row + col * 4..dst_row + col * 4 + 4];
+                if self.premultiplied {
+                    blend_over_premultiplied(src, dst);
+                } else {
+                    blend_over(src, dst);
+                }
+            }
+        }
+    }
+}
+
+/// Find the palette entry closest to `rgb` (squared RGB distance, lowest index wins ties)
+fn nearest_palette_index(palette: &[[u8; 3]], rgb: [u8; 3]) -> u8 {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, color)| {
+            let dr = color[0] as i32 - rgb[0] as i32;
+            let dg = color[1] as i32 - rgb[1] as i32;
+            let db = color[2] as i32 - rgb[2] as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map_or(0, |(index, _)| index as u8)
+}
+
+/// Replace every opaque pixel of a straight-alpha frame with a color from
+/// `palette`, optionally spreading the error (Floyd-Steinberg)
+///
+/// Alpha is snapped to fully transparent or fully opaque first. A full
+/// palette on a frame with transparency only uses its first 255 entries, as
+/// [`Frame::to_indexed`] reserves the last one.
+fn map_frame_to_palette(frame: &mut Frame, palette: &[[u8; 3]], dither: bool) {
+    for pixel in frame.data.chunks_exact_mut(4) {
+        pixel[3] = if pixel[3] < 128 { 0 } else { 255 };
+    }
+    frame.transparent = has_transparent_pixels(frame);
+    let usable = if frame.transparent {
+        &palette[..palette.len().min(255)]
+    } else {
+        palette
+    };
+
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    // Accumulated dithering error per pixel and channel
+    let mut error = vec![[0.0f32; 3]; if dither { width * height } else { 0 }];
+
+    for y in 0..height {
+        for x in 0..width {
+            let offset = y * width + x;
+            let pixel = &mut frame.data[offset * 4..offset * 4 + 4];
+            if pixel[3] == 0 {
+                continue;
+            }
+
+            let wanted: [f32; 3] = std::array::from_fn(|c| {
+                let carried = if dither { error[offset][c] } else { 0.0 };
+                (pixel[c] as f32 + carried).clamp(0.0, 255.0)
+            });
+            let nearest = nearest_palette_index(usable, wanted.map(|v| v.round() as u8));
+            let color = usable[nearest as usize];
+            pixel[..3].copy_from_slice(&color);
+
+            if dither {
+                let spread = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+                for (dx, dy, weight) in spread {
+                    let (nx, ny) = (x as isize + dx, y + dy);
+                    if nx < 0 || nx as usize >= width || ny >= height {
+                        continue;
+                    }
+                    let target = &mut error[ny * width + nx as usize];
+                    for c in 0..3 {
+                        target[c] += (wanted[c] - color[c] as f32) * weight / 16.0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Blend a straight-alpha RGBA source pixel over a destination pixel in place
+fn blend_over(src: &[u8], dst: &mut [u8]) {
+    let src_a = src[3] as u32;
+    if src_a == 0 {
+        return;
+    }
+    if src_a == 255 {
+        dst.copy_from_slice(src);
+        return;
+    }
+
+    // out_a = src_a + dst_a * (1 - src_a), all scaled by 255
+    let dst_a = dst[3] as u32;
+    let dst_weight = dst_a * (255 - src_a) / 255;
+    let out_a = src_a + dst_weight;
+
+    for c in 0..3 {
+        dst[c] = ((src[c] as u32 * src_a + dst[c] as u32 * dst_weight + out_a / 2) / out_a) as u8;
+    }
+    dst[3] = out_a as u8;
 }
 
 /// Represents a GIF image with all its frames and metadata
@@ -80,8 +384,313 @@ pub struct Gif {
     pub height: u16,
     /// Global color palette (optional, each entry is RGB)
     pub global_palette: Option<Vec<[u8; 3]>>,
-    /// Loop count (0 = infinite loop)
-    pub loop_count: u16,
+    /// Logical screen background color, if the file names one from its
+    /// global palette
+    pub background_color: Option<[u8; 3]>,
+    /// How often the animation plays
+    pub loop_count: Loop,
+    /// Number of frames whose delay was raised to the minimum when loading
+    pub delay_adjustments: usize,
+    /// Format version the GIF was loaded from (GIF89a for new GIFs)
+    pub version: GifVersion,
+}
+
+/// NeuQuant sampling factor (1 = every pixel, 30 = fastest)
+///
+/// `color_quant::NeuQuant` has no random number generator: it walks the input at
+/// a fixed stride derived from this factor and the pixel count. Keeping the
+/// factor constant makes palettes, and therefore encoded output, reproducible.
+pub(crate) const NEUQUANT_SAMPLE_FACTOR: i32 = 10;
+
+/// How often an animation plays, stored in the NETSCAPE2.0 extension
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Loop {
+    /// Loop forever (the default)
+    #[default]
+    Infinite,
+    /// Repeat this many times after the first play; `Finite(0)` plays once
+    ///
+    /// This is the raw NETSCAPE2.0 repeat count, so every value round-trips.
+    Finite(u16),
+}
+
+/// Result of looking for the NETSCAPE2.0 extension in the peeked header
+enum NetscapeBlock {
+    /// The extension was found with this repeat count
+    Found(u16),
+    /// The first image starts without a NETSCAPE2.0 extension before it
+    Absent,
+    /// The blocks run past the peeked bytes, so the count is unknown
+    Truncated,
+}
+
+impl Loop {
+    /// Map a NETSCAPE2.0 repeat count (0 = forever, otherwise extra plays)
+    fn from_netscape(count: u16) -> Self {
+        match count {
+            0 => Loop::Infinite,
+            n => Loop::Finite(n),
+        }
+    }
+
+    /// Repeat setting for the encoder, or `None` to omit the extension so
+    /// the animation plays once
+    fn to_repeat(self) -> Option<Repeat> {
+        match self {
+            Loop::Infinite => Some(Repeat::Infinite),
+            Loop::Finite(0) => None,
+            Loop::Finite(n) => Some(Repeat::Finite(n)),
+        }
+    }
+
+    /// Read the loop count from the start of a GIF stream
+    ///
+    /// Walks the extension blocks between the header and the first image
+    /// looking for a NETSCAPE2.0 application extension. Without one the
+    /// animation plays once. If the blocks continue past `header` the count
+    /// cannot be known, so it falls back to the default of looping forever.
+    fn parse(header: &[u8]) -> Self {
+        match Self::find_netscape_block(header).unwrap_or(NetscapeBlock::Truncated) {
+            NetscapeBlock::Found(count) => Self::from_netscape(count),
+            NetscapeBlock::Absent => Loop::Finite(0),
+            NetscapeBlock::Truncated => {
+                log::debug!("Loop count lies beyond the peeked header, assuming infinite");
+                Loop::Infinite
+            }
+        }
+    }
+
+    /// Scan the blocks before the first image; `None` if `header` ends first
+    fn find_netscape_block(header: &[u8]) -> Option<NetscapeBlock> {
+        // Skip the header, logical screen descriptor and global color table
+        let flags = *header.get(10)?;
+        let mut pos = 13;
+        if flags & 0x80 != 0 {
+            pos += 3 * (2 << (flags & 0x07));
+        }
+
+        // Extension blocks start with 0x21; anything else is image data
+        while *header.get(pos)? == 0x21 {
+            let label = *header.get(pos + 1)?;
+            pos += 2;
+
+            if label == 0xFF && header.get(pos..pos + 12)? == b"\x0bNETSCAPE2.0" {
+                if let [3, 1, lo, hi] = *header.get(pos + 12..pos + 16)? {
+                    return Some(NetscapeBlock::Found(u16::from_le_bytes([lo, hi])));
+                }
+            }
+
+            // Skip the extension's sub-blocks up to the zero-length terminator
+            loop {
+                let size = *header.get(pos)? as usize;
+                pos += 1 + size;
+                if size == 0 {
+                    break;
+                }
+            }
+        }
+
+        Some(NetscapeBlock::Absent)
+    }
+}
+
+impl std::fmt::Display for Loop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Loop::Infinite => write!(f, "Infinite"),
+            Loop::Finite(n) => write!(f, "{} times", *n as u32 + 1),
+        }
+    }
+}
+
+/// GIF format version, from the file's magic bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GifVersion {
+    /// The original 1987 format: no extensions, so no delays, transparency
+    /// or loop count
+    Gif87a,
+    /// The 1989 format with extension blocks (what animated GIFs use)
+    #[default]
+    Gif89a,
+}
+
+impl std::fmt::Display for GifVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifVersion::Gif87a => write!(f, "GIF87a"),
+            GifVersion::Gif89a => write!(f, "GIF89a"),
+        }
+    }
+}
+
+/// Options controlling how a GIF is encoded
+#[derive(Debug, Clone, Copy)]
+pub struct EncodeOptions {
+    /// Quantize each frame to its own local color table.
+    ///
+    /// This gives the best fidelity when frames use very different colors.
+    /// When false, a single palette is computed for the whole animation and
+    /// written as the global color table, which is smaller but may shift colors.
+    pub per_frame_palette: bool,
+    /// Format version to write.
+    ///
+    /// GIF87a output drops all extension blocks, so delays, disposal and the
+    /// loop count are lost (viewers play it once with default timing). Saving
+    /// fails if any frame needs transparency.
+    pub version: GifVersion,
+    /// Write frames that were interlaced in the source interlaced again.
+    ///
+    /// Otherwise every frame is written progressively.
+    pub preserve_interlace: bool,
+    /// Write each frame's own disposal method.
+    ///
+    /// Otherwise every frame uses Background disposal, which is right for the
+    /// full-canvas frames most operations produce.
+    pub preserve_disposal: bool,
+    /// Limit the palette to `2^bits` entries (1-8), including the transparent
+    /// entry.
+    ///
+    /// Forces a single shared palette; fewer bits per pixel give smaller
+    /// files at the cost of colors.
+    pub palette_bits: Option<u8>,
+    /// Write a delay of 0 for frames whose source delay was 0 and whose delay
+    /// is still at most 1.
+    ///
+    /// Otherwise delays are written as at least 1, since many viewers slow
+    /// down 0-delay frames.
+    pub emit_zero_delay: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            per_frame_palette: true,
+            version: GifVersion::Gif89a,
+            preserve_interlace: false,
+            preserve_disposal: false,
+            palette_bits: None,
+            emit_zero_delay: false,
+        }
+    }
+}
+
+/// Error categories that callers may want to tell apart
+///
+/// Attached as context to the `anyhow` errors returned when loading and
+/// saving, so the message is unchanged; use
+/// `error.downcast_ref::<GifError>()` to find the category.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GifError {
+    /// The input file does not exist
+    #[error("Failed to open GIF file: {0}")]
+    FileNotFound(String),
+    /// The input could not be decoded as a GIF
+    #[error("Failed to read GIF header from: {0}")]
+    NotAGif(String),
+    /// Writing the output GIF failed
+    #[error("Failed to encode GIF: {0}")]
+    EncodeFailed(String),
+}
+
+/// Options controlling how a GIF is decoded
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeOptions {
+    /// Minimum frame delay in 10ms units; shorter delays are raised to this.
+    ///
+    /// The GIF spec allows a delay of 0, which browsers interpret
+    /// inconsistently (often as 100ms). The default of 1 keeps such frames
+    /// animating quickly; use 0 to keep delays exactly as stored.
+    pub min_delay_cs: u16,
+    /// Largest download accepted when loading from a URL, in bytes
+    ///
+    /// Only used with the `network` feature; guards against endless or
+    /// maliciously large responses. Defaults to 50 MiB.
+    pub max_download_bytes: u64,
+    /// What to do with malformed frames that extend past the canvas
+    pub oversized_frames: OversizedFrames,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            min_delay_cs: 1,
+            max_download_bytes: 50 * 1024 * 1024,
+            oversized_frames: OversizedFrames::default(),
+        }
+    }
+}
+
+/// How [`Gif::ensure_consistent_dimensions`] fits a frame to the canvas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResizePolicy {
+    /// Keep the pixels at their size and offset; the rest of the canvas is
+    /// transparent and whatever lies outside it is cut off (partial frames
+    /// keep displaying the same)
+    #[default]
+    Pad,
+    /// Scale, keeping the aspect ratio, until the frame covers the canvas and
+    /// cut off the centered excess
+    Crop,
+    /// Stretch the frame to the canvas size
+    Scale,
+}
+
+/// Handling of frames larger than the canvas (logical screen) when decoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizedFrames {
+    /// Cut frames down to the part inside the canvas (the default, and what
+    /// browsers display)
+    #[default]
+    Crop,
+    /// Grow the canvas so every frame fits (up to 65535 pixels per side)
+    GrowCanvas,
+}
+
+/// Reorder the rows of an indexed frame into GIF interlaced order
+///
+/// Rows are written in four passes: every 8th row from 0, every 8th from 4,
+/// every 4th from 2 and every 2nd from 1.
+fn interlace_rows(indices: &[u8], width: u16, height: u16) -> Vec<u8> {
+    let width = width as usize;
+    let mut interlaced = Vec::with_capacity(indices.len());
+
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        for row in (start..height as usize).step_by(step) {
+            interlaced.extend_from_slice(&indices[row * width..(row + 1) * width]);
+        }
+    }
+
+    interlaced
+}
+
+/// Blend a premultiplied RGBA source pixel over a destination pixel in place
+fn blend_over_premultiplied(src: &[u8], dst: &mut [u8]) {
+    // out = src + dst * (1 - src_a), identical for color and alpha channels
+    let inverse_alpha = 255 - src[3] as u32;
+    for c in 0..4 {
+        dst[c] = (src[c] as u32 + (dst[c] as u32 * inverse_alpha + 127) / 255).min(255) as u8;
+    }
+}
+
+/// Convert a single premultiplied RGBA pixel back to straight alpha in place
+fn unpremultiply_pixel(pixel: &mut [u8]) {
+    let alpha = pixel[3] as u32;
+    if alpha == 0 {
+        return;
+    }
+    for c in &mut pixel[0..3] {
+        *c = ((*c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+    }
+}
+
+/// Give every fully transparent pixel the same RGBA value (0, 0, 0, 0)
+///
+/// The encoder keys colors by RGBA and marks only one of them transparent,
+/// so invisible pixels with different colors would otherwise come out opaque.
+fn clear_transparent_pixels(data: &mut [u8]) {
+    for pixel in data.chunks_exact_mut(4).filter(|pixel| pixel[3] == 0) {
+        pixel.copy_from_slice(&[0, 0, 0, 0]);
+    }
 }
 
 impl Gif {
@@ -92,140 +701,447 @@ impl Gif {
             width: 0,
             height: 0,
             global_palette: None,
-            loop_count: 0, // Infinite loop by default
+            background_color: None,
+            loop_count: Loop::Infinite,
+            delay_adjustments: 0,
+            version: GifVersion::Gif89a,
         }
     }
 
+    /// Start building a GIF with a validated fluent API
+    ///
+    /// # Example
+    /// ```
+    /// use gif_toolkit::core::{Frame, Gif};
+    ///
+    /// let gif = Gif::builder()
+    ///     .dimensions(2, 2)
+    ///     .add_frame(Frame::new(2, 2))
+    ///     .add_frame(Frame::new(2, 2))
+    ///     .delay_all(5)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(gif.total_duration(), 10);
+    /// ```
+    pub fn builder() -> GifBuilder {
+        GifBuilder::default()
+    }
+
     /// Load a GIF from a file
     pub fn from_file(path: &str) -> Result<Self> {
-        // Open the file
-        let file =
-            File::open(path).with_context(|| format!("Failed to open GIF file: {}", path))?;
-        let mut reader = BufReader::new(file);
+        Self::from_file_with_options(path, DecodeOptions::default())
+    }
 
-        // Configure decoder to output RGBA format
-        let mut decoder_options = gif::DecodeOptions::new();
-        decoder_options.set_color_output(gif::ColorOutput::RGBA);
+    /// Load a GIF from a file with custom decode options
+    ///
+    /// The number of frames whose delay was raised to `min_delay_cs` is
+    /// recorded in `delay_adjustments`.
+    ///
+    /// With the `network` feature, `http://` and `https://` paths are
+    /// downloaded (up to `max_download_bytes`) and decoded from memory.
+    pub fn from_file_with_options(path: &str, options: DecodeOptions) -> Result<Self> {
+        #[cfg(feature = "network")]
+        if crate::io::is_url(path) {
+            let bytes = crate::io::download(path, options.max_download_bytes)?;
+            return Self::from_reader(GifReader::from_reader(
+                std::io::Cursor::new(bytes),
+                path,
+                options,
+            )?);
+        }
 
-        // Create decoder and read info
-        let mut decoder = decoder_options
-            .read_info(&mut reader)
-            .with_context(|| format!("Failed to read GIF header from: {}", path))?;
+        Self::from_reader(GifReader::open_with_options(path, options)?)
+    }
 
-        // Get dimensions
-        let width = decoder.width();
-        let height = decoder.height();
+    /// Decode a GIF from bytes in memory
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_options(bytes, DecodeOptions::default())
+    }
 
-        // Read global palette if present
-        let global_palette = decoder.global_palette().map(|palette| {
-            palette
-                .chunks_exact(3)
-                .map(|chunk| {
-                    let mut rgb = [0u8; 3];
-                    rgb.copy_from_slice(chunk);
-                    rgb
-                })
-                .collect()
-        });
+    /// Decode a GIF from bytes in memory with custom decode options
+    pub fn from_bytes_with_options(bytes: &[u8], options: DecodeOptions) -> Result<Self> {
+        Self::from_reader(GifReader::from_reader(bytes, "<memory>", options)?)
+    }
 
+    /// Read every remaining frame of a reader into a GIF
+    fn from_reader<R: BufRead>(mut reader: GifReader<R>) -> Result<Self> {
         // Collect all frames
         let mut frames = Vec::new();
+        while let Some(frame) = reader.next_frame()? {
+            frames.push(frame);
+        }
 
-        while let Some(frame_info) = decoder
-            .read_next_frame()
-            .with_context(|| format!("Failed to read frame from: {}", path))?
-        {
-            // Get RGBA data from the frame buffer
-            let data = frame_info.buffer.to_vec();
-
-            // Ensure data is in RGBA format
-            assert_eq!(data.len() % 4, 0, "Frame data should be RGBA");
-
-            // Use frame's actual dimensions (may differ from GIF dimensions)
-            let frame_width = frame_info.width;
-            let frame_height = frame_info.height;
-
-            // Get disposal method (default to Keep if not specified)
-            let disposal = frame_info.dispose;
-
-            let frame = Frame {
-                data,
-                width: frame_width,
-                height: frame_height,
-                delay: frame_info.delay.max(1), // Ensure minimum delay of 1 (10ms)
-                transparent: frame_info.transparent.is_some(),
-                disposal,
-            };
+        Ok(Self::from_decoded(&reader, frames))
+    }
 
-            frames.push(frame);
+    /// Load as many frames as possible from a corrupt or truncated GIF
+    ///
+    /// Decoding stops at the first broken frame instead of failing; the
+    /// frames before it are returned together with warnings describing where
+    /// and why decoding stopped (empty for an intact file). A last frame
+    /// whose pixel data was cut off is dropped rather than returned partly
+    /// decoded. Errors are only returned if the header itself cannot be read.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use gif_toolkit::core::Gif;
+    ///
+    /// let (gif, warnings) = Gif::from_file_lossy("cut_off_download.gif").unwrap();
+    /// for warning in &warnings {
+    ///     eprintln!("warning: {}", warning);
+    /// }
+    /// println!("Recovered {} frames", gif.frames.len());
+    /// ```
+    pub fn from_file_lossy(path: &str) -> Result<(Self, Vec<String>)> {
+        let mut reader = GifReader::open(path)?;
+
+        let mut frames = Vec::new();
+        let mut warnings = Vec::new();
+        loop {
+            match reader.next_frame() {
+                Ok(Some(frame)) => frames.push(frame),
+                Ok(None) => break,
+                Err(error) => {
+                    // A frame whose pixel data ended early would flash
+                    // garbage at the end of every loop, so it is left out
+                    let dropped = if reader.truncated_frame {
+                        format!(", dropped incomplete frame {}", frames.len() + 1)
+                    } else {
+                        String::new()
+                    };
+                    warnings.push(format!(
+                        "Stopped after {} frames{}: {:#}",
+                        frames.len(),
+                        dropped,
+                        error
+                    ));
+                    break;
+                }
+            }
         }
 
-        Ok(Self {
+        let mut gif = Self::from_decoded(&reader, frames);
+        if gif.drop_incomplete_trailing_frame() {
+            warnings.push(format!(
+                "Dropped incomplete frame {}: pixel data does not match its size",
+                gif.frames.len()
+            ));
+        }
+
+        Ok((gif, warnings))
+    }
+
+    /// Assemble a GIF from a reader's metadata and its decoded frames
+    fn from_decoded<R: BufRead>(reader: &GifReader<R>, frames: Vec<Frame>) -> Self {
+        log::debug!(
+            "Decoded {} frames ({}x{}) from {}",
+            frames.len(),
+            reader.width(),
+            reader.height(),
+            reader.path
+        );
+
+        Self {
             frames,
-            width,
-            height,
-            global_palette,
-            loop_count: 0, // Default to infinite loop
-        })
+            width: reader.width(),
+            height: reader.height(),
+            global_palette: reader.global_palette().map(<[[u8; 3]]>::to_vec),
+            background_color: reader.background_color(),
+            loop_count: reader.loop_count(),
+            delay_adjustments: reader.delay_adjustments(),
+            version: reader.version(),
+        }
     }
 
     /// Save the GIF to a file
     pub fn to_file(&self, path: &str) -> Result<()> {
+        self.to_file_with_options(path, EncodeOptions::default())
+    }
+
+    /// Save the GIF to a file with custom encode options
+    pub fn to_file_with_options(&self, path: &str, options: EncodeOptions) -> Result<()> {
+        self.write_frames(path, options)
+    }
+
+    /// Encode the GIF into bytes in memory with custom encode options
+    pub fn to_bytes_with_options(&self, options: EncodeOptions) -> Result<Vec<u8>> {
+        let path = "<memory>";
+        let mut bytes = Vec::new();
+        self.encode_versioned(&mut bytes, path, options)
+            .with_context(|| GifError::EncodeFailed(path.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Save the GIF using `palette` as its global color table
+    ///
+    /// Every frame is mapped onto the palette (nearest color, or
+    /// Floyd-Steinberg dithered with `dither`) instead of being quantized
+    /// automatically, and no local color tables are written. GIF transparency
+    /// is on/off, so alpha is snapped to fully transparent or fully opaque.
+    /// Transparent pixels use an extra entry after the palette, or its last
+    /// entry if it already holds 256 colors.
+    ///
+    /// Returns an error if the palette is empty or has more than 256 colors.
+    pub fn to_file_with_palette(
+        &self,
+        path: &str,
+        palette: &[[u8; 3]],
+        dither: bool,
+    ) -> Result<()> {
+        if palette.is_empty() || palette.len() > 256 {
+            anyhow::bail!("Palette must have 1 to 256 colors, got {}", palette.len());
+        }
+
+        let mut mapped = self.clone();
+        for frame in &mut mapped.frames {
+            frame.unpremultiply();
+            map_frame_to_palette(frame, palette, dither);
+        }
+        mapped.global_palette = Some(palette.to_vec());
+
+        // Every opaque pixel is now a palette color, so the encoder writes the
+        // palette as is
+        mapped.write_frames(path, EncodeOptions::default())
+    }
+
+    /// Save the GIF with each frame reduced to the region that changes
+    ///
+    /// Frames are composited first, then each one is stored as the smallest
+    /// rectangle that differs from what is already on screen, with unchanged
+    /// pixels inside it made transparent. Frames stay on screen (`Keep`
+    /// disposal) unless the next frame needs pixels cleared back to
+    /// transparent. The displayed animation is unchanged.
+    pub fn to_file_optimized(&self, path: &str) -> Result<()> {
+        let mut composited = self.clone();
+        for frame in &mut composited.frames {
+            frame.unpremultiply();
+        }
+        composited.normalize()?;
+
+        let options = EncodeOptions {
+            preserve_disposal: true,
+            ..EncodeOptions::default()
+        };
+        composited.diff_encoded().write_frames(path, options)
+    }
+
+    /// Replace composited full-canvas frames with their changed regions
+    ///
+    /// Returns an unmodified copy if any frame does not cover the full canvas.
+    fn diff_encoded(&self) -> Gif {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let full_frame_size = width * height * 4;
+        if self.frames.iter().any(|f| f.data.len() != full_frame_size) {
+            return self.clone();
+        }
+
+        // A frame must be cleared from the canvas if the next one shows
+        // transparency where it is visible, which Keep disposal cannot do
+        let clear_after: Vec<bool> = (0..self.frames.len())
+            .map(|i| {
+                self.frames.get(i + 1).is_some_and(|next| {
+                    self.frames[i]
+                        .data
+                        .chunks_exact(4)
+                        .zip(next.data.chunks_exact(4))
+                        .any(|(current, next)| current[3] != 0 && next[3] == 0)
+                })
+            })
+            .collect();
+
+        let mut canvas = vec![0u8; full_frame_size];
+        let mut frames = Vec::with_capacity(self.frames.len());
+
+        for (target, &clear) in self.frames.iter().zip(&clear_after) {
+            // Cleared frames cover the whole canvas so disposal clears everything
+            let (x0, y0, x1, y1) = if clear {
+                (0, 0, width, height)
+            } else {
+                changed_bounds(&canvas, &target.data, width, height).unwrap_or((0, 0, 1, 1))
+            };
+
+            let mut data = Vec::with_capacity((x1 - x0) * (y1 - y0) * 4);
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let index = (y * width + x) * 4;
+                    let pixel = &target.data[index..index + 4];
+                    if pixel[3] == 0 || pixel == &canvas[index..index + 4] {
+                        data.extend_from_slice(&[0, 0, 0, 0]);
+                    } else {
+                        data.extend_from_slice(pixel);
+                    }
+                }
+            }
+
+            let mut frame = Frame::from_rgba(data, (x1 - x0) as u16, (y1 - y0) as u16);
+            frame.delay = target.delay;
+            frame.transparent = has_transparent_pixels(&frame);
+            frame.left = x0 as u16;
+            frame.top = y0 as u16;
+            frame.disposal = if clear {
+                DisposalMethod::Background
+            } else {
+                DisposalMethod::Keep
+            };
+            frames.push(frame);
+
+            if clear {
+                canvas.fill(0);
+            } else {
+                canvas.copy_from_slice(&target.data);
+            }
+        }
+
+        Gif {
+            frames,
+            ..self.clone()
+        }
+    }
+
+    /// Encode all frames and write them to `path`, tagging failures as
+    /// [`GifError::EncodeFailed`]
+    fn write_frames(&self, path: &str, options: EncodeOptions) -> Result<()> {
+        self.encode_to_file(path, options)
+            .with_context(|| GifError::EncodeFailed(path.to_string()))
+    }
+
+    /// Encode all frames and write them to `path`
+    fn encode_to_file(&self, path: &str, options: EncodeOptions) -> Result<()> {
         // Create output file
         let file =
             File::create(path).with_context(|| format!("Failed to create GIF file: {}", path))?;
-        let writer = BufWriter::new(file);
+        self.encode_versioned(BufWriter::new(file), path, options)
+    }
 
-        // Prepare global palette (empty if none)
-        let global_palette: Vec<u8> = if let Some(palette) = &self.global_palette {
-            palette.iter().flat_map(|rgb| rgb.iter().copied()).collect()
+    /// Encode all frames into `writer` in the format version from `options`
+    fn encode_versioned<W: Write>(
+        &self,
+        mut writer: W,
+        path: &str,
+        options: EncodeOptions,
+    ) -> Result<()> {
+        if options.version == GifVersion::Gif87a && self.frames.iter().any(has_transparent_pixels) {
+            anyhow::bail!("GIF87a cannot store transparency; save as GIF89a instead");
+        }
+
+        match options.version {
+            GifVersion::Gif89a => self.encode(writer, path, options),
+            GifVersion::Gif87a => {
+                // The encoder always writes GIF89a, so rewrite its output
+                let mut bytes = Vec::new();
+                self.encode(&mut bytes, path, options)?;
+                writer
+                    .write_all(&to_gif87a(&bytes)?)
+                    .with_context(|| format!("Failed to write GIF file: {}", path))
+            }
+        }
+    }
+
+    /// Encode all frames as GIF89a into `writer`
+    fn encode<W: Write>(&self, writer: W, path: &str, options: EncodeOptions) -> Result<()> {
+        let max_colors = match options.palette_bits {
+            Some(bits @ 1..=8) => Some(1usize << bits),
+            Some(bits) => anyhow::bail!("Palette bits must be between 1 and 8, got {}", bits),
+            None => None,
+        };
+
+        // Reuse the loaded global palette when every frame still maps onto it
+        // exactly, so unmodified GIFs round-trip without re-quantizing. With a
+        // palette limit it must leave room for a transparent entry.
+        let remapped = self
+            .global_palette
+            .as_ref()
+            .filter(|palette| max_colors.is_none_or(|max| palette.len() < max))
+            .and_then(|palette| Some((palette.clone(), self.remap_to_palette(palette)?)));
+
+        // Otherwise compute one palette for every frame unless each frame gets its own
+        let shared_palette = if let Some((palette, _)) = &remapped {
+            Some(palette.clone())
+        } else if let Some(max) = max_colors {
+            Some(self.shared_palette(max))
+        } else if options.per_frame_palette {
+            None
         } else {
-            Vec::new()
+            Some(self.shared_palette(256))
         };
 
-        // Extract background color from palette (palette[0] is typically background)
-        let background_color = if !global_palette.is_empty() && global_palette.len() >= 3 {
-            Some([global_palette[0], global_palette[1], global_palette[2], 255])
+        // Prepare global palette (empty if none)
+        let global_palette: Vec<u8> = if let Some(palette) = &shared_palette {
+            let mut flat: Vec<u8> = palette.iter().flat_map(|rgb| rgb.iter().copied()).collect();
+            // Reserve the entry after the palette for transparent pixels
+            if palette.len() < 256 && self.frames.iter().any(has_transparent_pixels) {
+                flat.extend_from_slice(&[0, 0, 0]);
+            }
+            flat
+        } else if let Some(palette) = &self.global_palette {
+            palette.iter().flat_map(|rgb| rgb.iter().copied()).collect()
         } else {
-            None // Default to white if no palette
+            Vec::new()
         };
 
         // Create encoder
         let mut encoder = Encoder::new(writer, self.width, self.height, &global_palette)
             .with_context(|| format!("Failed to create GIF encoder for: {}", path))?;
 
-        // Set loop count (0 = infinite)
-        if self.loop_count == 0 {
-            encoder
-                .set_repeat(Repeat::Infinite)
-                .context("Failed to set loop count")?;
-        } else {
+        // Set loop count (GIFs that play once have no loop extension)
+        if let Some(repeat) = self.loop_count.to_repeat() {
             encoder
-                .set_repeat(Repeat::Finite(self.loop_count))
+                .set_repeat(repeat)
                 .context("Failed to set loop count")?;
         }
 
         // Write each frame
-        for frame in &self.frames {
+        for (index, frame) in self.frames.iter().enumerate() {
+            // The encoder expects straight alpha
+            let mut data = frame.data.clone();
+            if frame.premultiplied {
+                data.chunks_exact_mut(4).for_each(unpremultiply_pixel);
+            }
+
             // Create GIF frame from RGBA data using frame's actual dimensions
-            let mut gif_frame = GifFrame::from_rgba(
-                frame.width,
-                frame.height,
-                &mut frame.data.clone(),
-            );
+            let mut gif_frame = match (&remapped, &shared_palette) {
+                (Some((_, indexed)), _) => {
+                    // Exact indices into the original global palette
+                    let (indices, transparent) = &indexed[index];
+                    GifFrame::from_indexed_pixels(frame.width, frame.height, indices, *transparent)
+                }
+                (None, Some(palette)) => {
+                    // Map onto the global palette; no local color table is written
+                    let straight = Frame::from_rgba(data, frame.width, frame.height);
+                    let (indices, transparent) = straight.to_indexed(palette);
+                    GifFrame::from_indexed_pixels(frame.width, frame.height, &indices, transparent)
+                }
+                // Quantizes each frame to its own local color table
+                (None, None) => {
+                    clear_transparent_pixels(&mut data);
+                    GifFrame::from_rgba(frame.width, frame.height, &mut data)
+                }
+            };
 
-            // Set delay
-            gif_frame.delay = frame.delay.max(1); // Ensure minimum delay
+            // Set delay, keeping a source delay of 0 only when asked to
+            let zero_delay =
+                options.emit_zero_delay && frame.original_delay == 0 && frame.delay <= 1;
+            gif_frame.delay = if zero_delay { 0 } else { frame.delay.max(1) };
 
             // CRITICAL: Use Background disposal for normalized frames
             // When frames are normalized to full size, each frame contains its complete content
             // We need to clear the canvas before each frame to prevent accumulation/ghosting
-            gif_frame.dispose = DisposalMethod::Background;
+            // Region-encoded frames rely on their own disposal instead
+            gif_frame.dispose = if options.preserve_disposal {
+                frame.disposal
+            } else {
+                DisposalMethod::Background
+            };
 
-            // If frame is smaller than GIF, center it
-            if frame.width < self.width || frame.height < self.height {
-                gif_frame.top = (self.height - frame.height) / 2;
-                gif_frame.left = (self.width - frame.width) / 2;
+            // Place the frame at its position on the canvas
+            gif_frame.left = frame.left;
+            gif_frame.top = frame.top;
+
+            // The encoder writes rows as given, so reorder them for interlacing
+            if options.preserve_interlace && frame.interlaced {
+                let rows = interlace_rows(&gif_frame.buffer, frame.width, frame.height);
+                gif_frame.buffer = rows.into();
+                gif_frame.interlaced = true;
             }
 
             // Note: Don't manually set transparent color index
@@ -240,7 +1156,83 @@ impl Gif {
         Ok(())
     }
 
-    /// Add a new frame to the GIF
+    /// Map every frame onto `palette` without any color loss
+    ///
+    /// Returns each frame's index buffer and transparent index (as produced by
+    /// [`Frame::to_indexed`]), or `None` if some opaque pixel's color is not in
+    /// the palette or a pixel is only partially transparent.
+    pub fn remap_to_palette(&self, palette: &[[u8; 3]]) -> Option<Vec<(Vec<u8>, Option<u8>)>> {
+        if palette.is_empty() || palette.len() > 256 {
+            return None;
+        }
+
+        self.frames
+            .iter()
+            .map(|frame| {
+                let mut straight = frame.clone();
+                if straight.premultiplied {
+                    straight.unpremultiply();
+                }
+
+                // A full palette gives up its last entry for transparency
+                let usable = if has_transparent_pixels(&straight) {
+                    palette.len().min(255)
+                } else {
+                    palette.len()
+                };
+                let colors: std::collections::HashSet<[u8; 3]> =
+                    palette[..usable].iter().copied().collect();
+
+                let exact = straight.data.chunks_exact(4).all(|pixel| match pixel[3] {
+                    0 => true,
+                    255 => colors.contains(&[pixel[0], pixel[1], pixel[2]]),
+                    _ => false,
+                });
+
+                exact.then(|| straight.to_indexed(palette))
+            })
+            .collect()
+    }
+
+    /// Build a single palette of at most `limit` entries covering every frame
+    ///
+    /// Uses the exact colors when they fit, otherwise quantizes with NeuQuant.
+    /// One slot is left free when any frame needs a transparent index.
+    fn shared_palette(&self, limit: usize) -> Vec<[u8; 3]> {
+        let max_colors = if self.frames.iter().any(has_transparent_pixels) {
+            limit - 1
+        } else {
+            limit
+        };
+
+        let mut pixels = Vec::new();
+        let mut unique = std::collections::BTreeSet::new();
+        for frame in &self.frames {
+            let mut data = frame.data.clone();
+            if frame.premultiplied {
+                data.chunks_exact_mut(4).for_each(unpremultiply_pixel);
+            }
+            for pixel in data.chunks_exact(4).filter(|pixel| pixel[3] > 0) {
+                pixels.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
+                unique.insert([pixel[0], pixel[1], pixel[2]]);
+            }
+        }
+
+        if unique.is_empty() {
+            return vec![[0, 0, 0]];
+        }
+        if unique.len() <= max_colors {
+            return unique.into_iter().collect();
+        }
+
+        color_quant::NeuQuant::new(NEUQUANT_SAMPLE_FACTOR, max_colors, &pixels)
+            .color_map_rgb()
+            .chunks_exact(3)
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+            .collect()
+    }
+
+    /// Add a new frame to the GIF
     pub fn add_frame(&mut self, frame: Frame) {
         // Update dimensions if this is the first frame
         if self.frames.is_empty() {
@@ -255,72 +1247,2728 @@ impl Gif {
         self.frames.len()
     }
 
+    /// Apply `f` to every frame, in parallel with the `parallel` feature
+    ///
+    /// Without the feature the frames are processed one after another. `f`
+    /// must not depend on the order frames are visited in.
+    pub fn par_map_frames<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Frame) + Sync,
+    {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            self.frames.par_iter_mut().for_each(&f);
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        self.frames.iter_mut().for_each(f);
+    }
+
+    /// Fail with a clear error if the GIF has no frames
+    ///
+    /// Operations call this right after loading, since timing, compositing
+    /// and encoding all assume at least one frame.
+    pub fn ensure_nonempty(&self) -> Result<()> {
+        if self.frames.is_empty() {
+            anyhow::bail!("GIF has no frames");
+        }
+        Ok(())
+    }
+
+    /// Remove the last frame if its pixel data does not match its declared
+    /// size, e.g. after the data of a hand-built or edited GIF was cut short
+    ///
+    /// Such a frame would flash garbage at the end of every loop or fail to
+    /// encode. Only the last frame is checked. Returns whether it was removed.
+    pub fn drop_incomplete_trailing_frame(&mut self) -> bool {
+        let Some(last) = self.frames.last() else {
+            return false;
+        };
+
+        let expected = last.width as usize * last.height as usize * 4;
+        if last.data.len() == expected {
+            return false;
+        }
+
+        self.frames.pop();
+        true
+    }
+
     /// Get total duration (in 10ms units)
     pub fn total_duration(&self) -> u32 {
         self.frames.iter().map(|f| f.delay as u32).sum()
     }
-}
 
-impl Default for Gif {
-    fn default() -> Self {
-        Self::new()
+    /// Get total duration as a [`Duration`](std::time::Duration)
+    pub fn duration(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.total_duration() as u64 * 10)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Count the distinct visible RGB colors across all frames
+    ///
+    /// Fully transparent pixels are ignored; premultiplied frames are counted
+    /// by their straight-alpha colors.
+    pub fn color_count(&self) -> usize {
+        let mut colors = std::collections::HashSet::new();
+        for frame in &self.frames {
+            let mut data = frame.data.clone();
+            if frame.premultiplied {
+                data.chunks_exact_mut(4).for_each(unpremultiply_pixel);
+            }
+            for pixel in data.chunks_exact(4).filter(|pixel| pixel[3] > 0) {
+                colors.insert([pixel[0], pixel[1], pixel[2]]);
+            }
+        }
+        colors.len()
+    }
 
-    #[test]
-    fn test_frame_new() {
-        let frame = Frame::new(10, 10);
-        assert_eq!(frame.width, 10);
-        assert_eq!(frame.height, 10);
-        assert_eq!(frame.data.len(), 10 * 10 * 4);
-        assert_eq!(frame.delay, 10);
-        assert!(!frame.transparent);
+    /// Change playback speed by dividing every frame delay by `factor`
+    ///
+    /// A factor of 2.0 plays twice as fast, 0.5 half as fast. Rounding is
+    /// distributed across frames (see
+    /// [`Gif::quantize_delays_preserving_total`]) and each delay keeps a
+    /// minimum of 1 (10ms). Returns an error if `factor` is not positive.
+    pub fn scale_delays(&mut self, factor: f64) -> Result<()> {
+        if !(factor > 0.0 && factor.is_finite()) {
+            anyhow::bail!("Speed factor must be greater than 0");
+        }
+
+        let intended: Vec<f64> = self
+            .frames
+            .iter()
+            .map(|frame| frame.delay as f64 / factor)
+            .collect();
+        self.quantize_delays_preserving_total(&intended)
     }
 
-    #[test]
-    fn test_frame_from_rgba() {
-        let data = vec![255u8; 100 * 100 * 4];
-        let frame = Frame::from_rgba(data, 100, 100);
-        assert_eq!(frame.width, 100);
-        assert_eq!(frame.height, 100);
-        assert_eq!(frame.data.len(), 100 * 100 * 4);
+    /// Set frame delays from fractional centisecond values without drift
+    ///
+    /// Rounding each delay on its own lets the error pile up over many
+    /// frames. Instead every delay is rounded down and the centiseconds
+    /// missing from the rounded intended total go to the frames with the
+    /// largest remainders (earlier frames win ties), so the total matches
+    /// and no delay is off by a full centisecond. Delays below 1 are then
+    /// raised to 1, which can overshoot the total, and delays saturate at
+    /// `u16::MAX`.
+    ///
+    /// Returns an error if `intended` does not have one value per frame or
+    /// holds a negative or non-finite value.
+    pub fn quantize_delays_preserving_total(&mut self, intended: &[f64]) -> Result<()> {
+        if intended.len() != self.frames.len() {
+            anyhow::bail!(
+                "Expected {} delays, got {}",
+                self.frames.len(),
+                intended.len()
+            );
+        }
+        if let Some(delay) = intended.iter().find(|d| !(d.is_finite() && **d >= 0.0)) {
+            anyhow::bail!("Invalid delay {}", delay);
+        }
+
+        let mut delays: Vec<f64> = intended.iter().map(|d| d.floor()).collect();
+        let target = intended.iter().sum::<f64>().round();
+        let missing = (target - delays.iter().sum::<f64>()).max(0.0) as usize;
+
+        let mut by_remainder: Vec<usize> = (0..intended.len()).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let remainder = |i: usize| intended[i] - delays[i];
+            remainder(b).total_cmp(&remainder(a)).then(a.cmp(&b))
+        });
+        for &index in by_remainder.iter().take(missing) {
+            delays[index] += 1.0;
+        }
+
+        for (frame, delay) in self.frames.iter_mut().zip(delays) {
+            frame.delay = delay.clamp(1.0, u16::MAX as f64) as u16;
+        }
+
+        Ok(())
     }
 
-    #[test]
-    fn test_gif_new() {
-        let gif = Gif::new();
-        assert_eq!(gif.width, 0);
-        assert_eq!(gif.height, 0);
-        assert_eq!(gif.frames.len(), 0);
-        assert_eq!(gif.loop_count, 0);
+    /// Composite every frame to a full-canvas image of what is on screen
+    ///
+    /// Partial frames are drawn at their position over the previous result,
+    /// honoring each frame's disposal method. Afterwards every frame covers
+    /// the whole canvas at (0, 0) with `Keep` disposal, so frames can be
+    /// edited independently. GIFs whose frames already show their full
+    /// content are left untouched.
+    pub fn normalize(&mut self) -> Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        let full_frame_size = (self.width as usize) * (self.height as usize) * 4;
+
+        // Check if any frame needs normalization: partial frames, or frames with
+        // transparent pixels that reveal a previous frame left on screen
+        let needs_normalization = self.frames.iter().any(|f| f.data.len() < full_frame_size)
+            || self
+                .frames
+                .windows(2)
+                .any(|pair| pair[0].disposal != DisposalMethod::Background && pair[1].transparent);
+
+        if !needs_normalization {
+            return Ok(());
+        }
+
+        log::debug!("Normalizing frames with composite disposal handling");
+
+        // Start with transparent black, the GIF background
+        let mut canvas: Vec<u8> = vec![0; full_frame_size];
+
+        for frame in self.frames.iter_mut() {
+            // `Previous` disposal restores the screen as it was before *this*
+            // frame was drawn, so snapshot it before compositing
+            let previous_canvas =
+                (frame.disposal == DisposalMethod::Previous).then(|| canvas.clone());
+
+            // Composite the frame onto the canvas at its position; transparent
+            // pixels let the canvas show through
+            frame.composite_onto(&mut canvas, self.width, frame.left, frame.top);
+
+            // Update frame with composited result
+            frame.data = canvas.clone();
+            frame.width = self.width;
+            frame.height = self.height;
+            frame.left = 0;
+            frame.top = 0;
+            let disposal = std::mem::replace(&mut frame.disposal, DisposalMethod::Keep);
+
+            // Handle disposal for next frame
+            match disposal {
+                DisposalMethod::Background => {
+                    // Restore to background (transparent black)
+                    canvas = vec![0; full_frame_size];
+                }
+                DisposalMethod::Previous => {
+                    // Undo this frame: the next one draws over the earlier state
+                    if let Some(previous_canvas) = previous_canvas {
+                        canvas = previous_canvas;
+                    }
+                }
+                _ => {
+                    // Keep/Any - the canvas stays as drawn
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    #[test]
-    fn test_gif_add_frame() {
-        let mut gif = Gif::new();
-        let frame = Frame::new(50, 50);
-        gif.add_frame(frame);
+    /// Set every frame's disposal method to `disposal`
+    ///
+    /// The new methods are only written when saving with
+    /// [`EncodeOptions::preserve_disposal`].
+    pub fn set_uniform_disposal(&mut self, disposal: DisposalMethod) {
+        for frame in &mut self.frames {
+            frame.disposal = disposal;
+        }
+    }
 
-        assert_eq!(gif.width, 50);
-        assert_eq!(gif.height, 50);
-        assert_eq!(gif.frame_count(), 1);
+    /// Scale all frame delays proportionally so the total duration is close to
+    /// `target_duration` (in 10ms units)
+    ///
+    /// Each delay keeps a minimum of 1, so the result can overshoot the target
+    /// when there are more frames than centiseconds to distribute.
+    pub(crate) fn fit_delays_to_duration(&mut self, target_duration: u32) {
+        let current_duration = self.total_duration();
+        if current_duration == 0 {
+            return;
+        }
+
+        let ratio = target_duration as f64 / current_duration as f64;
+        let intended: Vec<f64> = self
+            .frames
+            .iter()
+            .map(|frame| frame.delay as f64 * ratio)
+            .collect();
+        // One finite, non-negative delay per frame, so this cannot fail
+        let _ = self.quantize_delays_preserving_total(&intended);
     }
 
-    #[test]
-    fn test_gif_total_duration() {
-        let mut gif = Gif::new();
-        let mut frame1 = Frame::new(10, 10);
-        frame1.delay = 20;
-        let mut frame2 = Frame::new(10, 10);
-        frame2.delay = 30;
+    /// Uniformly subsample down to at most `max_frames` frames
+    ///
+    /// Kept frames are evenly spaced and each takes over the delays of the
+    /// frames dropped after it, so the total duration is unchanged (delays
+    /// saturate at `u16::MAX`). Frames are kept as stored, so partial frames
+    /// should be composited first. A `max_frames` of 0 is treated as 1.
+    ///
+    /// Returns the number of frames removed.
+    pub fn limit_frames(&mut self, max_frames: usize) -> usize {
+        let original_count = self.frames.len();
+        let max_frames = max_frames.max(1);
+        if original_count <= max_frames {
+            return 0;
+        }
 
-        gif.add_frame(frame1);
-        gif.add_frame(frame2);
+        let starts: Vec<usize> = (0..max_frames)
+            .map(|i| i * original_count / max_frames)
+            .collect();
+        let frames = std::mem::take(&mut self.frames);
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(original_count);
+            let delay: u32 = frames[start..end].iter().map(|f| f.delay as u32).sum();
+            for (index, dropped) in frames.iter().enumerate().take(end).skip(start + 1) {
+                warn_if_tagged(dropped, index, "limiting frames");
+            }
 
-        assert_eq!(gif.total_duration(), 50);
+            let mut frame = frames[start].clone();
+            frame.delay = delay.min(u16::MAX as u32) as u16;
+            self.frames.push(frame);
+        }
+
+        original_count - max_frames
+    }
+
+    /// Collapse duplicated frames at the loop boundary
+    ///
+    /// Runs of identical frames at the start or end of the animation are merged
+    /// into a single frame with their delays summed. If the last frame is then
+    /// identical to the first, it is removed and its delay folded into the
+    /// first frame, since it would otherwise cause a hitch when the GIF loops.
+    ///
+    /// Returns the number of frames removed.
+    pub fn trim_duplicate_boundary(&mut self) -> usize {
+        let original_count = self.frames.len();
+
+        // Collapse the leading run of identical frames
+        while self.frames.len() > 1 && frames_identical(&self.frames[0], &self.frames[1]) {
+            let removed = self.frames.remove(1);
+            self.frames[0].delay = self.frames[0].delay.saturating_add(removed.delay);
+        }
+
+        // Collapse the trailing run of identical frames
+        while self.frames.len() > 1 {
+            let last = self.frames.len() - 1;
+            if !frames_identical(&self.frames[last - 1], &self.frames[last]) {
+                break;
+            }
+            let removed = self.frames.pop().unwrap();
+            self.frames[last - 1].delay = self.frames[last - 1].delay.saturating_add(removed.delay);
+        }
+
+        // Fold a trailing copy of the first frame into the first frame
+        if self.frames.len() > 1 {
+            let last = self.frames.len() - 1;
+            if frames_identical(&self.frames[0], &self.frames[last]) {
+                let removed = self.frames.pop().unwrap();
+                self.frames[0].delay = self.frames[0].delay.saturating_add(removed.delay);
+            }
+        }
+
+        original_count - self.frames.len()
+    }
+
+    /// Score how cleanly the animation loops (0 = perfect seam)
+    ///
+    /// Returns the average pixel difference (0-255) between the last and first
+    /// frames as displayed, i.e. after compositing partial frames. GIFs with
+    /// fewer than two frames always score 0.
+    pub fn loop_seam_score(&self) -> u8 {
+        if self.frames.len() < 2 {
+            return 0;
+        }
+
+        let mut composited = self.clone();
+        if composited.normalize().is_err() {
+            return 255;
+        }
+
+        let first = &composited.frames[0];
+        let last = &composited.frames[composited.frames.len() - 1];
+        calculate_frame_difference(last, first)
+    }
+
+    /// Guess whether the frames form an animation rather than a collection
+    /// of unrelated still images
+    ///
+    /// Consecutive frames are compared as displayed; a pair is related when
+    /// its similarity (255 minus the average pixel difference, 0-255) is at
+    /// least `min_similarity`. The GIF looks like an animation when at least
+    /// half of the pairs are related, so occasional scene cuts are tolerated.
+    /// GIFs with fewer than two frames always count as animations. Returns an
+    /// error if the frames cannot be composited.
+    pub fn looks_like_animation(&self, min_similarity: u8) -> Result<bool> {
+        if self.frames.len() < 2 {
+            return Ok(true);
+        }
+
+        let mut composited = self.clone();
+        composited.normalize()?;
+
+        let pairs = composited.frames.len() - 1;
+        let related = composited
+            .frames
+            .windows(2)
+            .filter(|pair| {
+                let difference = calculate_frame_difference(&pair[0], &pair[1]);
+                255 - difference >= min_similarity
+            })
+            .count();
+
+        Ok(related * 2 >= pairs)
+    }
+
+    /// Find the frames that start a new scene, e.g. cuts in a screen recording
+    ///
+    /// Frames are compared as displayed; a frame is a cut when its average
+    /// pixel difference from the previous frame (0-255) exceeds `threshold`.
+    /// Returns the indices of those frames in order; the first frame is never
+    /// a cut. Returns an error if the frames cannot be composited.
+    pub fn scene_cuts(&self, threshold: u8) -> Result<Vec<usize>> {
+        let mut composited = self.clone();
+        composited.normalize()?;
+
+        Ok(composited
+            .frames
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| calculate_frame_difference(&pair[0], &pair[1]) > threshold)
+            .map(|(index, _)| index + 1)
+            .collect())
+    }
+
+    /// Make the canvas width and height even, e.g. for video encoders that
+    /// require it
+    ///
+    /// Frames are composited first. With `pad`, an odd dimension grows by one
+    /// pixel of [`background_color`](Self::background_color) (transparent
+    /// without one) on the right or bottom edge; otherwise the last column or
+    /// row is cropped off. Even GIFs are left
+    /// untouched. Returns an error if the GIF has no frames, or if a
+    /// dimension cannot change (padding 65535, cropping 1).
+    pub fn ensure_even_dimensions(&mut self, pad: bool) -> Result<()> {
+        let adjust = |size: u16| -> Result<u16> {
+            if size % 2 == 0 {
+                return Ok(size);
+            }
+            let adjusted = if pad {
+                size.checked_add(1)
+            } else {
+                Some(size - 1).filter(|&s| s > 0)
+            };
+            adjusted.with_context(|| {
+                format!(
+                    "Cannot {} dimension {} to an even size",
+                    if pad { "pad" } else { "crop" },
+                    size
+                )
+            })
+        };
+        let (width, height) = (adjust(self.width)?, adjust(self.height)?);
+        if (width, height) == (self.width, self.height) {
+            return Ok(());
+        }
+        self.ensure_nonempty()?;
+
+        // Every frame must cover the whole canvas before it is resized
+        self.normalize()?;
+
+        let background = match self.background_color {
+            Some([r, g, b]) => [r, g, b, 255],
+            None => [0; 4],
+        };
+        for frame in &mut self.frames {
+            frame.unpremultiply();
+            let mut data = background.repeat(width as usize * height as usize);
+            let copy_width = width.min(frame.width) as usize * 4;
+            for row in 0..height.min(frame.height) as usize {
+                let src = row * frame.width as usize * 4;
+                let dst = row * width as usize * 4;
+                data[dst..dst + copy_width].copy_from_slice(&frame.data[src..src + copy_width]);
+            }
+
+            frame.data = data;
+            frame.width = width;
+            frame.height = height;
+            frame.transparent = has_transparent_pixels(frame);
+        }
+        self.width = width;
+        self.height = height;
+
+        Ok(())
+    }
+
+    /// Make every frame exactly the canvas size, e.g. after frames of other
+    /// sizes were added by hand
+    ///
+    /// Frames that already cover the canvas are left untouched; the others
+    /// are fitted according to `policy` and moved to the canvas origin.
+    /// Frames are not composited first. Returns an error if the canvas has
+    /// a zero dimension.
+    pub fn ensure_consistent_dimensions(&mut self, policy: ResizePolicy) -> Result<()> {
+        let (width, height) = (self.width, self.height);
+        if width == 0 || height == 0 {
+            anyhow::bail!("Canvas size must be non-zero, got {}x{}", width, height);
+        }
+
+        for frame in &mut self.frames {
+            if (frame.width, frame.height, frame.left, frame.top) == (width, height, 0, 0) {
+                continue;
+            }
+
+            match policy {
+                ResizePolicy::Pad => {
+                    let mut data = vec![0u8; width as usize * height as usize * 4];
+                    let (left, top) = (frame.left as usize, frame.top as usize);
+                    let copy_width = (width as usize)
+                        .saturating_sub(left)
+                        .min(frame.width as usize);
+                    let rows = (height as usize)
+                        .saturating_sub(top)
+                        .min(frame.height as usize);
+                    for row in 0..rows {
+                        let src = row * frame.width as usize * 4;
+                        let dst = ((top + row) * width as usize + left) * 4;
+                        data[dst..dst + copy_width * 4]
+                            .copy_from_slice(&frame.data[src..src + copy_width * 4]);
+                    }
+                    frame.data = data;
+                    frame.width = width;
+                    frame.height = height;
+                }
+                ResizePolicy::Crop => {
+                    let scale = (width as f64 / frame.width as f64)
+                        .max(height as f64 / frame.height as f64);
+                    let scaled_width =
+                        ((frame.width as f64 * scale).round() as u32).max(width as u32);
+                    let scaled_height =
+                        ((frame.height as f64 * scale).round() as u32).max(height as u32);
+                    resize_frame(frame, scaled_width, scaled_height, false);
+
+                    let x = (scaled_width - width as u32) / 2;
+                    let y = (scaled_height - height as u32) / 2;
+                    let cropped = image::imageops::crop_imm(
+                        &frame.to_image_buffer(),
+                        x,
+                        y,
+                        width as u32,
+                        height as u32,
+                    )
+                    .to_image();
+                    frame.update_from_image_buffer(&cropped);
+                }
+                ResizePolicy::Scale => resize_frame(frame, width as u32, height as u32, false),
+            }
+
+            frame.left = 0;
+            frame.top = 0;
+            frame.transparent = has_transparent_pixels(frame);
+        }
+
+        Ok(())
+    }
+
+    /// Render the frame at `index` as displayed, scaled so its longer side is
+    /// `max_dimension` pixels
+    ///
+    /// The aspect ratio is kept (the shorter side is at least 1 pixel).
+    /// Filtering happens on premultiplied pixels, so the returned frame can
+    /// have partial alpha along transparent edges. Returns an error if
+    /// `max_dimension` is 0 or `index` is out of range.
+    pub fn thumbnail(&self, max_dimension: u16, index: usize) -> Result<Frame> {
+        if max_dimension == 0 {
+            anyhow::bail!("Thumbnail size must be at least 1 pixel");
+        }
+
+        // Only the frames up to `index` affect what is on screen
+        let indices: Vec<usize> = (0..=index).collect();
+        let mut prefix = self.subsample_frames(&indices)?;
+        prefix.normalize()?;
+        let mut frame = prefix.frames.pop().context("GIF has no frames")?;
+
+        let (width, height) = (self.width as f64, self.height as f64);
+        let scale = max_dimension as f64 / width.max(height);
+        let thumb_width = ((width * scale).round() as u32).max(1);
+        let thumb_height = ((height * scale).round() as u32).max(1);
+
+        resize_frame(&mut frame, thumb_width, thumb_height, false);
+
+        Ok(frame)
+    }
+
+    /// Build a new GIF from the frames at `indices`, in the given order
+    ///
+    /// Indices may repeat. Frames are copied as stored, so partial frames
+    /// should be composited first if they are taken out of sequence.
+    ///
+    /// Returns an error if any index is out of range.
+    pub fn subsample_frames(&self, indices: &[usize]) -> Result<Gif> {
+        let frames = indices
+            .iter()
+            .map(|&index| {
+                self.frames.get(index).cloned().with_context(|| {
+                    format!(
+                        "Frame index {} is out of range (GIF has {} frames)",
+                        index,
+                        self.frames.len()
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Gif {
+            frames,
+            width: self.width,
+            height: self.height,
+            global_palette: self.global_palette.clone(),
+            background_color: self.background_color,
+            loop_count: self.loop_count,
+            delay_adjustments: self.delay_adjustments,
+            version: self.version,
+        })
+    }
+
+    /// Split the animation into GIFs of at most `frames_per_chunk` consecutive
+    /// frames each
+    ///
+    /// Every chunk keeps the canvas size, palette and loop settings; only the
+    /// last one may be shorter. Frames are copied as stored, so partial frames
+    /// should be composited first. A `frames_per_chunk` of 0 is treated as 1.
+    pub fn split_into_chunks(&self, frames_per_chunk: usize) -> Vec<Gif> {
+        self.frames
+            .chunks(frames_per_chunk.max(1))
+            .map(|frames| Gif {
+                frames: frames.to_vec(),
+                width: self.width,
+                height: self.height,
+                global_palette: self.global_palette.clone(),
+                background_color: self.background_color,
+                loop_count: self.loop_count,
+                delay_adjustments: self.delay_adjustments,
+                version: self.version,
+            })
+            .collect()
+    }
+
+    /// Read one pixel of a frame as it appears on screen (straight RGBA)
+    ///
+    /// Frames up to `frame` are composited with their disposal methods, so
+    /// partial frames report the pixel that is actually visible. Returns an
+    /// error if the frame index or coordinate is out of range.
+    pub fn pixel_at(&self, frame: usize, x: u16, y: u16) -> Result<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            anyhow::bail!(
+                "Pixel ({}, {}) is outside the {}x{} canvas",
+                x,
+                y,
+                self.width,
+                self.height
+            );
+        }
+
+        let indices: Vec<usize> = (0..=frame).collect();
+        let mut shown = self.subsample_frames(&indices)?;
+        shown.normalize()?;
+
+        let mut visible = shown.frames.pop().context("GIF has no frames")?;
+        visible.unpremultiply();
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let mut pixel = [0u8; 4];
+        pixel.copy_from_slice(&visible.data[index..index + 4]);
+        Ok(pixel)
+    }
+
+    /// Every frame as it appears on screen, paired with its delay (in 10ms units)
+    ///
+    /// Frames are composited with their disposal methods (see
+    /// [`Gif::normalize`]) into full-canvas straight-alpha images, ready for a
+    /// player to show in order. The GIF itself is left unchanged.
+    pub fn composited_frames(&self) -> Result<Vec<(image::RgbaImage, u16)>> {
+        let mut composited = self.clone();
+        composited.normalize()?;
+
+        Ok(composited
+            .frames
+            .into_iter()
+            .map(|mut frame| {
+                frame.unpremultiply();
+                let delay = frame.delay;
+                (frame.to_image_buffer(), delay)
+            })
+            .collect())
+    }
+
+    /// Merge runs of consecutive identical frames into one frame
+    ///
+    /// The merged frame's delay is the sum of the run's delays; a run is split
+    /// if the sum would exceed `u16::MAX`. Frames should be composited first,
+    /// since identical partial frames can display differently.
+    ///
+    /// Returns the number of frames removed.
+    pub fn merge_identical_frames(&mut self) -> usize {
+        let original_count = self.frames.len();
+        let mut merged: Vec<Frame> = Vec::with_capacity(original_count);
+
+        for (index, frame) in self.frames.drain(..).enumerate() {
+            if let Some(last) = merged.last_mut() {
+                if frames_identical(last, &frame) {
+                    if let Some(delay) = last.delay.checked_add(frame.delay) {
+                        last.delay = delay;
+                        warn_if_tagged(&frame, index, "merging identical frames");
+                        continue;
+                    }
+                }
+            }
+            merged.push(frame);
+        }
+
+        self.frames = merged;
+        original_count - self.frames.len()
+    }
+
+    /// Remove global palette entries that no visible pixel uses
+    ///
+    /// Frames store RGBA, so no pixel data needs remapping. The palette is
+    /// dropped entirely if no entry is used.
+    ///
+    /// Returns the number of colors removed.
+    pub fn trim_palette(&mut self) -> usize {
+        let Some(palette) = self.global_palette.as_mut() else {
+            return 0;
+        };
+
+        let mut used = std::collections::HashSet::new();
+        for frame in &self.frames {
+            for pixel in frame.data.chunks_exact(4).filter(|p| p[3] > 0) {
+                let mut straight = [pixel[0], pixel[1], pixel[2], pixel[3]];
+                if frame.premultiplied {
+                    unpremultiply_pixel(&mut straight);
+                }
+                used.insert([straight[0], straight[1], straight[2]]);
+            }
+        }
+
+        let original_len = palette.len();
+        palette.retain(|rgb| used.contains(rgb));
+        if palette.is_empty() {
+            self.global_palette = None;
+        }
+
+        original_len - self.global_palette.as_ref().map_or(0, |p| p.len())
+    }
+
+    /// Get the most frequent opaque color across all frames
+    ///
+    /// Pixels are subsampled for speed and bucketed at 5 bits per channel; the
+    /// average color of the largest bucket is returned. Near-transparent pixels
+    /// are ignored. Returns black if no pixel is visible.
+    pub fn dominant_color(&self) -> [u8; 3] {
+        self.prominent_colors(1)
+            .first()
+            .copied()
+            .unwrap_or([0, 0, 0])
+    }
+
+    /// Get up to `count` of the most frequent opaque colors, most frequent first
+    ///
+    /// Uses the same sampling and bucketing as [`Gif::dominant_color`].
+    pub fn prominent_colors(&self, count: usize) -> Vec<[u8; 3]> {
+        let buckets = self.color_buckets();
+
+        // Lowest key wins ties so the result is stable
+        let mut sorted: Vec<(u16, ColorBucket)> = buckets.into_iter().collect();
+        sorted.sort_by(|(ka, a), (kb, b)| b.0.cmp(&a.0).then(ka.cmp(kb)));
+
+        sorted
+            .into_iter()
+            .filter_map(|(_, bucket)| bucket_average(bucket))
+            .take(count)
+            .collect()
+    }
+
+    /// Count sampled opaque pixels in 5-bit-per-channel color buckets
+    fn color_buckets(&self) -> HashMap<u16, ColorBucket> {
+        // Upper bound on the number of pixels sampled across the whole GIF
+        const MAX_SAMPLES: usize = 100_000;
+        // Pixels with alpha below this are considered transparent
+        const MIN_ALPHA: u8 = 16;
+
+        let total_pixels: usize = self.frames.iter().map(|f| f.data.len() / 4).sum();
+        let step = (total_pixels / MAX_SAMPLES).max(1);
+
+        let mut buckets: HashMap<u16, ColorBucket> = HashMap::new();
+
+        for frame in &self.frames {
+            for pixel in frame.data.chunks_exact(4).step_by(step) {
+                if pixel[3] < MIN_ALPHA {
+                    continue;
+                }
+
+                let key = ((pixel[0] as u16 >> 3) << 10)
+                    | ((pixel[1] as u16 >> 3) << 5)
+                    | (pixel[2] as u16 >> 3);
+                let bucket = buckets.entry(key).or_insert((0, 0, 0, 0));
+
+                bucket.0 += 1;
+                bucket.1 += pixel[0] as u64;
+                bucket.2 += pixel[1] as u64;
+                bucket.3 += pixel[2] as u64;
+            }
+        }
+
+        buckets
+    }
+}
+
+/// Pixel count and per-channel sums (count, sum R, sum G, sum B)
+type ColorBucket = (u64, u64, u64, u64);
+
+/// Average color of a bucket, or `None` if it is empty
+fn bucket_average((count, r, g, b): ColorBucket) -> Option<[u8; 3]> {
+    if count == 0 {
+        return None;
+    }
+
+    Some([(r / count) as u8, (g / count) as u8, (b / count) as u8])
+}
+
+/// Check whether a frame has any fully transparent pixel
+fn has_transparent_pixels(frame: &Frame) -> bool {
+    frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0)
+}
+
+/// Resize a frame, keeping its transparency
+///
+/// Filtering happens on premultiplied pixels so transparent areas don't bleed
+/// their (invisible) color into the edges; the frame is left with straight
+/// alpha. GIF transparency is on/off, so with `snap_alpha` the filtered alpha
+/// is snapped back to fully transparent or fully opaque.
+pub(crate) fn resize_frame(frame: &mut Frame, width: u32, height: u32, snap_alpha: bool) {
+    frame.premultiply();
+    // Use Triangle filter for smoother edges without ringing artifacts
+    let resized = image::imageops::resize(
+        &frame.to_image_buffer(),
+        width,
+        height,
+        image::imageops::FilterType::Triangle,
+    );
+    frame.update_from_image_buffer(&resized);
+    frame.unpremultiply();
+
+    if snap_alpha {
+        for pixel in frame.data.chunks_exact_mut(4) {
+            pixel[3] = if pixel[3] < 128 { 0 } else { 255 };
+        }
+    }
+    frame.transparent = has_transparent_pixels(frame);
+}
+
+/// Convert an encoded GIF89a stream to GIF87a by dropping extension blocks
+fn to_gif87a(data: &[u8]) -> Result<Vec<u8>> {
+    let truncated = || anyhow::anyhow!("Truncated GIF data");
+
+    // Header and logical screen descriptor, plus the global color table
+    let header = data.get(..13).ok_or_else(truncated)?;
+    let mut pos = 13;
+    if header[10] & 0x80 != 0 {
+        pos += 3 * (2 << (header[10] & 0x07));
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(b"GIF87a");
+    out.extend_from_slice(data.get(6..pos).ok_or_else(truncated)?);
+
+    // Length of a run of data sub-blocks (including the terminator) at `start`
+    let sub_blocks_len = |start: usize| -> Result<usize> {
+        let mut end = start;
+        loop {
+            let size = *data.get(end).ok_or_else(truncated)? as usize;
+            end += 1 + size;
+            if size == 0 {
+                return Ok(end - start);
+            }
+        }
+    };
+
+    loop {
+        match *data.get(pos).ok_or_else(truncated)? {
+            // Extension: introducer, label, sub-blocks
+            0x21 => pos += 2 + sub_blocks_len(pos + 2)?,
+            // Image: descriptor, optional local color table, LZW code size, sub-blocks
+            0x2C => {
+                let flags = *data.get(pos + 9).ok_or_else(truncated)?;
+                let mut end = pos + 10;
+                if flags & 0x80 != 0 {
+                    end += 3 * (2 << (flags & 0x07));
+                }
+                end += 1;
+                end += sub_blocks_len(end)?;
+                out.extend_from_slice(data.get(pos..end).ok_or_else(truncated)?);
+                pos = end;
+            }
+            0x3B => {
+                out.push(0x3B);
+                return Ok(out);
+            }
+            other => anyhow::bail!("Unexpected GIF block 0x{:02X}", other),
+        }
+    }
+}
+
+/// Bounding box `(x0, y0, x1, y1)` (exclusive end) of pixels that differ
+/// between two full-canvas RGBA buffers, or `None` if they are identical
+fn changed_bounds(
+    before: &[u8],
+    after: &[u8],
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize, usize, usize)> {
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = (y * width + x) * 4;
+            if before[index..index + 4] == after[index..index + 4] {
+                continue;
+            }
+
+            bounds = Some(match bounds {
+                None => (x, y, x + 1, y + 1),
+                Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x + 1), y1.max(y + 1)),
+            });
+        }
+    }
+
+    bounds
+}
+
+/// Check whether two frames have the same size and pixel data
+fn frames_identical(a: &Frame, b: &Frame) -> bool {
+    a.width == b.width && a.height == b.height && a.data == b.data
+}
+
+/// Calculate the difference between two frames
+///
+/// Returns a value from 0-255 representing the average pixel difference
+pub(crate) fn calculate_frame_difference(frame1: &Frame, frame2: &Frame) -> u8 {
+    if frame1.width != frame2.width || frame1.height != frame2.height {
+        return 255; // Maximum difference if dimensions don't match
+    }
+
+    if frame1.data.len() != frame2.data.len() {
+        return 255;
+    }
+
+    let mut total_diff = 0u64;
+    let pixel_count = (frame1.width as u64) * (frame1.height as u64);
+
+    // Compare RGBA pixels
+    for (p1, p2) in frame1.data.chunks(4).zip(frame2.data.chunks(4)) {
+        // Calculate per-channel difference
+        let r_diff = (p1[0] as i16 - p2[0] as i16).unsigned_abs() as u64;
+        let g_diff = (p1[1] as i16 - p2[1] as i16).unsigned_abs() as u64;
+        let b_diff = (p1[2] as i16 - p2[2] as i16).unsigned_abs() as u64;
+        let a_diff = (p1[3] as i16 - p2[3] as i16).unsigned_abs() as u64;
+
+        // Average difference across channels
+        total_diff += (r_diff + g_diff + b_diff + a_diff) / 4;
+    }
+
+    if pixel_count == 0 {
+        return 0;
+    }
+
+    (total_diff / pixel_count) as u8
+}
+
+/// Log a warning if a frame being removed by `operation` carries tags
+///
+/// `index` is the frame's position before the operation. Returns the logged
+/// message, if any.
+pub(crate) fn warn_if_tagged(frame: &Frame, index: usize, operation: &str) -> Option<String> {
+    if frame.tags.is_empty() {
+        return None;
+    }
+
+    let message = format!(
+        "{} removed frame {} tagged {}",
+        operation,
+        index,
+        frame.tags.join(", ")
+    );
+    log::warn!("{}", message);
+    Some(message)
+}
+
+impl Default for Gif {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "async")]
+impl Gif {
+    /// Load a GIF from a file without blocking the async runtime
+    ///
+    /// Runs [`Gif::from_file`] on tokio's blocking thread pool.
+    pub async fn from_file_async(path: &str) -> Result<Self> {
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || Self::from_file(&path))
+            .await
+            .context("GIF loading task failed")?
+    }
+
+    /// Save the GIF to a file without blocking the async runtime
+    ///
+    /// Runs [`Gif::to_file`] on tokio's blocking thread pool. The frames are
+    /// copied so the encoder can run on another thread.
+    pub async fn to_file_async(&self, path: &str) -> Result<()> {
+        let gif = self.clone();
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || gif.to_file(&path))
+            .await
+            .context("GIF saving task failed")?
+    }
+}
+
+/// Fluent builder for [`Gif`], created with [`Gif::builder`]
+///
+/// Consistency is checked in [`GifBuilder::build`] rather than on each call.
+#[derive(Debug, Clone, Default)]
+pub struct GifBuilder {
+    dimensions: Option<(u16, u16)>,
+    loop_count: Loop,
+    global_palette: Option<Vec<[u8; 3]>>,
+    background_color: Option<[u8; 3]>,
+    frames: Vec<Frame>,
+    delay_all: Option<u16>,
+}
+
+impl GifBuilder {
+    /// Set the canvas size (defaults to the size of the first frame)
+    pub fn dimensions(mut self, width: u16, height: u16) -> Self {
+        self.dimensions = Some((width, height));
+        self
+    }
+
+    /// Set how often the animation plays ([`Loop::Infinite`] by default)
+    pub fn loop_count(mut self, count: Loop) -> Self {
+        self.loop_count = count;
+        self
+    }
+
+    /// Set the global color palette (at most 256 RGB entries)
+    pub fn global_palette(mut self, palette: Vec<[u8; 3]>) -> Self {
+        self.global_palette = Some(palette);
+        self
+    }
+
+    /// Set the logical screen background color
+    pub fn background_color(mut self, color: [u8; 3]) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Append a frame
+    pub fn add_frame(mut self, frame: Frame) -> Self {
+        self.frames.push(frame);
+        self
+    }
+
+    /// Override every frame's delay (in 10ms units) when building
+    pub fn delay_all(mut self, delay: u16) -> Self {
+        self.delay_all = Some(delay);
+        self
+    }
+
+    /// Validate the configuration and create the GIF
+    ///
+    /// Fails if there are no frames, if any frame's size differs from the
+    /// canvas size, or if the global palette is empty or has over 256 colors.
+    pub fn build(self) -> Result<Gif> {
+        let first = self
+            .frames
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("GIF must contain at least one frame"))?;
+        let (width, height) = self.dimensions.unwrap_or((first.width, first.height));
+
+        if width == 0 || height == 0 {
+            anyhow::bail!("GIF dimensions must be non-zero, got {}x{}", width, height);
+        }
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            if frame.width != width || frame.height != height {
+                anyhow::bail!(
+                    "Frame {} is {}x{} but the GIF is {}x{}",
+                    index,
+                    frame.width,
+                    frame.height,
+                    width,
+                    height
+                );
+            }
+        }
+
+        if let Some(palette) = &self.global_palette {
+            if palette.is_empty() || palette.len() > 256 {
+                anyhow::bail!(
+                    "Global palette must have 1 to 256 colors, got {}",
+                    palette.len()
+                );
+            }
+        }
+
+        let mut frames = self.frames;
+        if let Some(delay) = self.delay_all {
+            for frame in &mut frames {
+                frame.delay = delay;
+                frame.original_delay = delay;
+            }
+        }
+
+        Ok(Gif {
+            frames,
+            width,
+            height,
+            global_palette: self.global_palette,
+            background_color: self.background_color,
+            loop_count: self.loop_count,
+            delay_adjustments: 0,
+            version: GifVersion::Gif89a,
+        })
+    }
+}
+
+/// Writes frames to a GIF file one at a time
+///
+/// Unlike [`Gif::to_file`], frames are not kept in memory, so long recordings
+/// can be streamed to disk. Each frame is quantized to its own local color
+/// table and keeps its position, delay and disposal. Call
+/// [`GifWriter::finish`] to complete the file.
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::core::{Frame, GifWriter, Loop};
+///
+/// let mut writer = GifWriter::create("recording.gif", 320, 240, Loop::Infinite).unwrap();
+/// for _ in 0..100 {
+///     writer.write_frame(&Frame::new(320, 240)).unwrap();
+/// }
+/// writer.finish().unwrap();
+/// ```
+pub struct GifWriter {
+    encoder: Encoder<BufWriter<File>>,
+    path: String,
+    width: u16,
+    height: u16,
+}
+
+impl GifWriter {
+    /// Create the output file and write the GIF header
+    ///
+    /// `loop_count` follows [`Gif::loop_count`].
+    pub fn create(path: &str, width: u16, height: u16, loop_count: Loop) -> Result<Self> {
+        if width == 0 || height == 0 {
+            anyhow::bail!("GIF dimensions must be non-zero, got {}x{}", width, height);
+        }
+
+        let file =
+            File::create(path).with_context(|| format!("Failed to create GIF file: {}", path))?;
+        let mut encoder = Encoder::new(BufWriter::new(file), width, height, &[])
+            .with_context(|| format!("Failed to create GIF encoder for: {}", path))?;
+
+        if let Some(repeat) = loop_count.to_repeat() {
+            encoder
+                .set_repeat(repeat)
+                .context("Failed to set loop count")?;
+        }
+
+        Ok(Self {
+            encoder,
+            path: path.to_string(),
+            width,
+            height,
+        })
+    }
+
+    /// Encode and write one frame
+    ///
+    /// Fails if the frame does not fit on the canvas at its position, or if
+    /// its pixel data does not match its size.
+    pub fn write_frame(&mut self, frame: &Frame) -> Result<()> {
+        let expected = frame.width as usize * frame.height as usize * 4;
+        if frame.data.len() != expected {
+            anyhow::bail!(
+                "Frame {}x{} has {} bytes of pixel data, expected {}",
+                frame.width,
+                frame.height,
+                frame.data.len(),
+                expected
+            );
+        }
+
+        if frame.left as u32 + frame.width as u32 > self.width as u32
+            || frame.top as u32 + frame.height as u32 > self.height as u32
+        {
+            anyhow::bail!(
+                "Frame {}x{} at ({}, {}) does not fit the {}x{} canvas",
+                frame.width,
+                frame.height,
+                frame.left,
+                frame.top,
+                self.width,
+                self.height
+            );
+        }
+
+        // The encoder expects straight alpha
+        let mut data = frame.data.clone();
+        if frame.premultiplied {
+            data.chunks_exact_mut(4).for_each(unpremultiply_pixel);
+        }
+        clear_transparent_pixels(&mut data);
+
+        let mut gif_frame = GifFrame::from_rgba(frame.width, frame.height, &mut data);
+        gif_frame.delay = frame.delay.max(1);
+        gif_frame.dispose = frame.disposal;
+        gif_frame.left = frame.left;
+        gif_frame.top = frame.top;
+
+        self.encoder
+            .write_frame(&gif_frame)
+            .with_context(|| format!("Failed to write frame to: {}", self.path))
+    }
+
+    /// Write the GIF trailer and flush the file
+    pub fn finish(self) -> Result<()> {
+        let mut writer = self
+            .encoder
+            .into_inner()
+            .with_context(|| format!("Failed to finish GIF file: {}", self.path))?;
+        writer
+            .flush()
+            .with_context(|| format!("Failed to write GIF file: {}", self.path))
+    }
+}
+
+/// Reads frames from a GIF file one at a time
+///
+/// The counterpart of [`GifWriter`]: only the current frame is held in
+/// memory, so huge GIFs can be processed without [`Gif::from_file`] loading
+/// every frame. Frames are returned as stored (partial frames are not
+/// composited). Any buffered source can be read with
+/// [`GifReader::from_reader`].
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::core::GifReader;
+///
+/// let mut reader = GifReader::open("input.gif").unwrap();
+/// println!("{}x{}", reader.width(), reader.height());
+/// while let Some(frame) = reader.next_frame().unwrap() {
+///     println!("delay: {}", frame.delay);
+/// }
+/// ```
+pub struct GifReader<R: BufRead = BufReader<File>> {
+    decoder: gif::Decoder<R>,
+    path: String,
+    options: DecodeOptions,
+    width: u16,
+    height: u16,
+    version: GifVersion,
+    loop_count: Loop,
+    global_palette: Option<Vec<[u8; 3]>>,
+    background_color: Option<[u8; 3]>,
+    frames_read: usize,
+    delay_adjustments: usize,
+    /// Whether decoding failed inside a frame's pixel data
+    truncated_frame: bool,
+}
+
+impl GifReader {
+    /// Open a GIF file and read its header
+    pub fn open(path: &str) -> Result<Self> {
+        Self::open_with_options(path, DecodeOptions::default())
+    }
+
+    /// Open a GIF file with custom decode options
+    pub fn open_with_options(path: &str, options: DecodeOptions) -> Result<Self> {
+        // Open the file
+        let file = File::open(path).map_err(|error| {
+            let not_found = error.kind() == std::io::ErrorKind::NotFound;
+            let error = anyhow::Error::new(error);
+            if not_found {
+                error.context(GifError::FileNotFound(path.to_string()))
+            } else {
+                error.context(format!("Failed to open GIF file: {}", path))
+            }
+        })?;
+        Self::from_reader(BufReader::new(file), path, options)
+    }
+}
+
+impl<R: BufRead> GifReader<R> {
+    /// Read the GIF header from a buffered source
+    ///
+    /// `path` only names the source in error messages.
+    pub fn from_reader(mut reader: R, path: &str, options: DecodeOptions) -> Result<Self> {
+        // Peek at the magic bytes to record the format version, and at the
+        // blocks before the first image for the loop count
+        let header = reader.fill_buf()?;
+        let version = match header.get(..6) {
+            Some(b"GIF87a") => GifVersion::Gif87a,
+            _ => GifVersion::Gif89a,
+        };
+        let loop_count = Loop::parse(header);
+
+        // Configure decoder to output RGBA format
+        let mut decoder_options = gif::DecodeOptions::new();
+        decoder_options.set_color_output(gif::ColorOutput::RGBA);
+
+        // Create decoder and read info
+        let decoder = decoder_options
+            .read_info(reader)
+            .with_context(|| GifError::NotAGif(path.to_string()))?;
+
+        // Read global palette if present
+        let global_palette: Option<Vec<[u8; 3]>> = decoder.global_palette().map(|palette| {
+            palette
+                .chunks_exact(3)
+                .map(|chunk| {
+                    let mut rgb = [0u8; 3];
+                    rgb.copy_from_slice(chunk);
+                    rgb
+                })
+                .collect()
+        });
+        let background_color = decoder
+            .bg_color()
+            .and_then(|index| global_palette.as_ref()?.get(index).copied());
+
+        Ok(Self {
+            width: decoder.width(),
+            height: decoder.height(),
+            decoder,
+            path: path.to_string(),
+            options,
+            version,
+            loop_count,
+            global_palette,
+            background_color,
+            frames_read: 0,
+            delay_adjustments: 0,
+            truncated_frame: false,
+        })
+    }
+
+    /// Canvas width
+    ///
+    /// With [`OversizedFrames::GrowCanvas`] this can grow as frames are read.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Canvas height
+    ///
+    /// With [`OversizedFrames::GrowCanvas`] this can grow as frames are read.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Global color palette, if the file has one
+    pub fn global_palette(&self) -> Option<&[[u8; 3]]> {
+        self.global_palette.as_deref()
+    }
+
+    /// Background color from the global palette, if the file names one
+    pub fn background_color(&self) -> Option<[u8; 3]> {
+        self.background_color
+    }
+
+    /// Format version from the file's magic bytes
+    pub fn version(&self) -> GifVersion {
+        self.version
+    }
+
+    /// How often the animation plays
+    pub fn loop_count(&self) -> Loop {
+        self.loop_count
+    }
+
+    /// Number of frames read so far whose delay was raised to `min_delay_cs`
+    pub fn delay_adjustments(&self) -> usize {
+        self.delay_adjustments
+    }
+
+    /// Decode the next frame, or return `None` after the last one
+    pub fn next_frame(&mut self) -> Result<Option<Frame>> {
+        let path = &self.path;
+        let frame_info = match self
+            .decoder
+            .next_frame_info()
+            .with_context(|| format!("Failed to read frame from: {}", path))?
+        {
+            // read_next_frame() clears the interlaced flag, so read it first
+            Some(frame_info) => frame_info.clone(),
+            None => return Ok(None),
+        };
+
+        // Get RGBA data (the decoder deinterlaces rows)
+        let mut data = vec![0u8; self.decoder.buffer_size()];
+        if let Err(error) = self.decoder.read_into_buffer(&mut data) {
+            self.truncated_frame = true;
+            return Err(error).with_context(|| format!("Failed to read frame from: {}", path));
+        }
+
+        // Use frame's actual dimensions (may differ from GIF dimensions)
+        let frame_width = frame_info.width;
+        let frame_height = frame_info.height;
+
+        // Apply the minimum delay, counting frames that had to change
+        let min_delay = self.options.min_delay_cs;
+        if frame_info.delay < min_delay {
+            self.delay_adjustments += 1;
+        }
+        self.frames_read += 1;
+
+        let frame = Frame {
+            data,
+            width: frame_width,
+            height: frame_height,
+            delay: frame_info.delay.max(min_delay),
+            original_delay: frame_info.delay,
+            transparent: frame_info.transparent.is_some(),
+            disposal: frame_info.dispose,
+            premultiplied: false,
+            left: frame_info.left,
+            top: frame_info.top,
+            interlaced: frame_info.interlaced,
+            tags: Vec::new(),
+        };
+
+        Ok(Some(self.fit_to_canvas(frame)?))
+    }
+
+    /// Apply the oversized frame policy to a frame extending past the canvas
+    fn fit_to_canvas(&mut self, frame: Frame) -> Result<Frame> {
+        let right = frame.left as u32 + frame.width as u32;
+        let bottom = frame.top as u32 + frame.height as u32;
+        if right <= self.width as u32 && bottom <= self.height as u32 {
+            return Ok(frame);
+        }
+
+        log::debug!(
+            "Frame {} ({}x{} at {},{}) exceeds the {}x{} canvas of {}",
+            self.frames_read,
+            frame.width,
+            frame.height,
+            frame.left,
+            frame.top,
+            self.width,
+            self.height,
+            self.path
+        );
+
+        if self.options.oversized_frames == OversizedFrames::GrowCanvas {
+            self.width = self.width.max(right.min(u16::MAX as u32) as u16);
+            self.height = self.height.max(bottom.min(u16::MAX as u32) as u16);
+        }
+
+        // Crop whatever still does not fit
+        let visible_width = self.width.saturating_sub(frame.left).min(frame.width);
+        let visible_height = self.height.saturating_sub(frame.top).min(frame.height);
+        if visible_width == 0 || visible_height == 0 {
+            // Entirely off-canvas: keep the timing with an invisible pixel
+            let mut empty = Frame::new(1, 1);
+            empty.delay = frame.delay;
+            empty.transparent = true;
+            empty.disposal = frame.disposal;
+            return Ok(empty);
+        }
+
+        let mut cropped = frame.crop(0, 0, visible_width, visible_height)?;
+        cropped.left = frame.left;
+        cropped.top = frame.top;
+        Ok(cropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_new() {
+        let frame = Frame::new(10, 10);
+        assert_eq!(frame.width, 10);
+        assert_eq!(frame.height, 10);
+        assert_eq!(frame.data.len(), 10 * 10 * 4);
+        assert_eq!(frame.delay, 10);
+        assert!(!frame.transparent);
+    }
+
+    #[test]
+    fn test_frame_from_rgba() {
+        let data = vec![255u8; 100 * 100 * 4];
+        let frame = Frame::from_rgba(data, 100, 100);
+        assert_eq!(frame.width, 100);
+        assert_eq!(frame.height, 100);
+        assert_eq!(frame.data.len(), 100 * 100 * 4);
+    }
+
+    #[test]
+    fn test_frame_dynamic_image_round_trip() {
+        let data: Vec<u8> = (0..6 * 4 * 4).map(|i| (i * 7 % 256) as u8).collect();
+        let frame = Frame::from_rgba(data.clone(), 6, 4);
+
+        let image = frame.to_dynamic_image();
+        assert_eq!(image.width(), 6);
+        assert_eq!(image.height(), 4);
+
+        let restored = Frame::from_dynamic_image(&image, 25);
+        assert_eq!(restored.width, 6);
+        assert_eq!(restored.height, 4);
+        assert_eq!(restored.delay, 25);
+        assert_eq!(restored.data, data);
+    }
+
+    #[test]
+    fn test_frame_premultiply_round_trip() {
+        let original = vec![200, 100, 50, 128];
+        let mut frame = Frame::from_rgba(original.clone(), 1, 1);
+
+        frame.premultiply();
+        assert!(frame.premultiplied);
+        assert_eq!(frame.data[3], 128);
+        assert!((frame.data[0] as i32 - 100).abs() <= 1);
+
+        // Premultiplying twice is a no-op
+        let premultiplied = frame.data.clone();
+        frame.premultiply();
+        assert_eq!(frame.data, premultiplied);
+
+        frame.unpremultiply();
+        assert!(!frame.premultiplied);
+        for (restored, expected) in frame.data.iter().zip(&original) {
+            assert!((*restored as i32 - *expected as i32).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_frame_composite_onto_premultiplied() {
+        // Opaque blue canvas (premultiplied and straight are identical when opaque)
+        let mut canvas = vec![0, 0, 255, 255];
+
+        let mut frame = Frame::from_rgba(vec![255, 0, 0, 128], 1, 1);
+        frame.premultiply();
+        frame.composite_onto(&mut canvas, 1, 0, 0);
+
+        assert!((126..=130).contains(&canvas[0]), "{:?}", canvas);
+        assert!((125..=129).contains(&canvas[2]), "{:?}", canvas);
+        assert_eq!(canvas[3], 255);
+    }
+
+    #[test]
+    fn test_frame_to_indexed() {
+        let data = vec![
+            255, 0, 0, 255, // Red
+            0, 0, 255, 255, // Blue
+            250, 10, 5, 255, // Almost red
+            0, 0, 255, 255, // Blue
+        ];
+        let frame = Frame::from_rgba(data, 2, 2);
+        let palette = [[255, 0, 0], [0, 0, 255]];
+
+        let (indices, transparent) = frame.to_indexed(&palette);
+        assert_eq!(indices, vec![0, 1, 0, 1]);
+        assert_eq!(transparent, None);
+
+        // Transparent pixels get the index one past the palette
+        let frame = Frame::from_rgba(vec![255, 0, 0, 255, 0, 0, 0, 0], 2, 1);
+        let (indices, transparent) = frame.to_indexed(&palette);
+        assert_eq!(indices, vec![0, 2]);
+        assert_eq!(transparent, Some(2));
+    }
+
+    #[test]
+    fn test_frame_crop() {
+        // 4x4 frame where each pixel's red channel encodes its index
+        let data: Vec<u8> = (0..16u8).flat_map(|i| [i, 0, 0, 255]).collect();
+        let frame = Frame::from_rgba(data, 4, 4);
+
+        let cropped = frame.crop(1, 2, 2, 2).unwrap();
+        assert_eq!(cropped.width, 2);
+        assert_eq!(cropped.height, 2);
+        let reds: Vec<u8> = cropped.data.chunks(4).map(|p| p[0]).collect();
+        assert_eq!(reds, vec![9, 10, 13, 14]);
+
+        // Out-of-bounds and empty regions are rejected
+        assert!(frame.crop(3, 0, 2, 1).is_err());
+        assert!(frame.crop(0, 0, 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_frame_composite_onto() {
+        // Opaque blue canvas, 2x2
+        let mut canvas: Vec<u8> = [0, 0, 255, 255].repeat(4);
+
+        // 1x1 red pixel at 50% alpha, placed at (1, 1)
+        let frame = Frame::from_rgba(vec![255, 0, 0, 128], 1, 1);
+        frame.composite_onto(&mut canvas, 2, 1, 1);
+
+        // Untouched pixel stays blue
+        assert_eq!(&canvas[0..4], &[0, 0, 255, 255]);
+        // Blended pixel is roughly half red, half blue and fully opaque
+        let blended = &canvas[12..16];
+        assert!((126..=130).contains(&blended[0]), "{:?}", blended);
+        assert_eq!(blended[1], 0);
+        assert!((125..=129).contains(&blended[2]), "{:?}", blended);
+        assert_eq!(blended[3], 255);
+
+        // Frames hanging off the canvas edge are clipped rather than panicking
+        let big = Frame::from_rgba([255, 255, 255, 255].repeat(9), 3, 3);
+        big.composite_onto(&mut canvas, 2, 1, 1);
+        assert_eq!(&canvas[12..16], &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_gif_new() {
+        let gif = Gif::new();
+        assert_eq!(gif.width, 0);
+        assert_eq!(gif.height, 0);
+        assert_eq!(gif.frames.len(), 0);
+        assert_eq!(gif.loop_count, Loop::Infinite);
+    }
+
+    #[test]
+    fn test_gif_add_frame() {
+        let mut gif = Gif::new();
+        let frame = Frame::new(50, 50);
+        gif.add_frame(frame);
+
+        assert_eq!(gif.width, 50);
+        assert_eq!(gif.height, 50);
+        assert_eq!(gif.frame_count(), 1);
+    }
+
+    #[test]
+    fn test_previous_disposal_restores_earlier_frame() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let green = [0, 255, 0, 255];
+
+        // Full red background, then a blue square that is undone afterwards,
+        // then a green square elsewhere
+        let background = Frame::from_rgba(red.repeat(16), 4, 4);
+        let mut undone = Frame::from_rgba(blue.repeat(4), 2, 2);
+        undone.disposal = DisposalMethod::Previous;
+        let mut last = Frame::from_rgba(green.repeat(4), 2, 2);
+        last.left = 2;
+        last.top = 2;
+
+        let mut gif = Gif::new();
+        gif.width = 4;
+        gif.height = 4;
+        gif.frames = vec![background, undone, last];
+        gif.normalize().unwrap();
+
+        let pixel = |frame: usize, x: usize, y: usize| {
+            let index = (y * 4 + x) * 4;
+            gif.frames[frame].data[index..index + 4].to_vec()
+        };
+
+        // The middle frame is shown with its blue square...
+        assert_eq!(pixel(1, 0, 0), blue);
+        // ...but the third frame composites over the first, not the second
+        assert_eq!(pixel(2, 0, 0), red);
+        assert_eq!(pixel(2, 3, 3), green);
+        assert_eq!(pixel(2, 3, 0), red);
+    }
+
+    #[test]
+    fn test_normalize_partial_frames() {
+        let mut gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let mut partial = Frame::from_rgba([0, 0, 255, 255].repeat(100), 10, 10);
+        partial.left = 50;
+        partial.top = 60;
+        partial.disposal = DisposalMethod::Background;
+        gif.frames.insert(1, partial);
+
+        gif.normalize().unwrap();
+
+        for frame in &gif.frames {
+            assert_eq!((frame.width, frame.height), (gif.width, gif.height));
+            assert_eq!((frame.left, frame.top), (0, 0));
+            assert_eq!(frame.data.len(), 200 * 200 * 4);
+            assert_eq!(frame.disposal, DisposalMethod::Keep);
+        }
+        let index = (65 * 200 + 55) * 4;
+        assert_eq!(&gif.frames[1].data[index..index + 4], &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_pixel_at() {
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+
+        assert_eq!(gif.pixel_at(0, 50, 50).unwrap(), [255, 0, 0, 255]);
+        assert_eq!(gif.pixel_at(1, 50, 50).unwrap(), [0, 0, 255, 255]);
+        assert!(gif.pixel_at(2, 50, 50).is_err());
+        assert!(gif.pixel_at(0, 100, 0).is_err());
+    }
+
+    #[test]
+    fn test_zero_delay_is_kept_as_original_delay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zero_delay.gif");
+        let path = path.to_str().unwrap();
+
+        let mut gif = Gif::builder()
+            .add_frame(Frame::new(4, 4))
+            .add_frame(Frame::new(4, 4))
+            .build()
+            .unwrap();
+        gif.frames[0].delay = 1;
+        gif.frames[0].original_delay = 0;
+        let options = EncodeOptions {
+            emit_zero_delay: true,
+            ..EncodeOptions::default()
+        };
+        gif.to_file_with_options(path, options).unwrap();
+
+        let loaded = Gif::from_file(path).unwrap();
+        assert_eq!(loaded.frames[0].original_delay, 0);
+        assert_eq!(loaded.frames[0].delay, 1);
+        assert_eq!(loaded.frames[1].original_delay, 10);
+
+        // Re-emitting keeps the true 0; the default raises it to 1
+        loaded.to_file_with_options(path, options).unwrap();
+        assert_eq!(Gif::from_file(path).unwrap().frames[0].original_delay, 0);
+        loaded.to_file(path).unwrap();
+        assert_eq!(Gif::from_file(path).unwrap().frames[0].original_delay, 1);
+    }
+
+    #[test]
+    fn test_looks_like_animation() {
+        let solid = |rgb: [u8; 3]| {
+            let data = [rgb[0], rgb[1], rgb[2], 255].repeat(8 * 8);
+            Frame::from_rgba(data, 8, 8)
+        };
+        let unrelated = Gif::builder()
+            .add_frame(solid([255, 0, 0]))
+            .add_frame(solid([0, 255, 0]))
+            .add_frame(solid([0, 0, 255]))
+            .add_frame(solid([255, 255, 0]))
+            .build()
+            .unwrap();
+        assert!(!unrelated.looks_like_animation(230).unwrap());
+        assert!(unrelated.looks_like_animation(0).unwrap());
+
+        let animation = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        assert!(animation.looks_like_animation(230).unwrap());
+    }
+
+    #[test]
+    fn test_quantize_delays_preserving_total() {
+        let mut gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let intended = [3.4, 3.4, 3.4, 3.3, 3.3, 3.3, 3.3, 3.3, 3.3, 3.3];
+        gif.quantize_delays_preserving_total(&intended).unwrap();
+
+        // 33.3 rounds to 33; rounding each delay alone would give 30
+        assert_eq!(gif.total_duration(), 33);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![4, 4, 4, 3, 3, 3, 3, 3, 3, 3]);
+
+        assert!(gif.quantize_delays_preserving_total(&[1.0]).is_err());
+        assert!(gif.quantize_delays_preserving_total(&[-1.0; 10]).is_err());
+    }
+
+    #[test]
+    fn test_ensure_even_dimensions() {
+        let odd = || {
+            let data = [255, 0, 0, 255].repeat(101 * 101);
+            Gif::builder()
+                .add_frame(Frame::from_rgba(data.clone(), 101, 101))
+                .add_frame(Frame::from_rgba(data, 101, 101))
+                .build()
+                .unwrap()
+        };
+
+        let mut padded = odd();
+        padded.ensure_even_dimensions(true).unwrap();
+        assert_eq!((padded.width, padded.height), (102, 102));
+        let frame = &padded.frames[0];
+        assert_eq!((frame.width, frame.height), (102, 102));
+        assert_eq!(&frame.data[..4], &[255, 0, 0, 255]);
+        // Without a background color the new last column is transparent
+        let edge = (101 * 102 - 1) * 4;
+        assert_eq!(&frame.data[edge..edge + 4], &[0, 0, 0, 0]);
+        assert!(frame.transparent);
+
+        // Otherwise it is padded with the background color
+        let mut padded = odd();
+        padded.background_color = Some([0, 0, 255]);
+        padded.ensure_even_dimensions(true).unwrap();
+        let frame = &padded.frames[1];
+        assert_eq!(&frame.data[edge..edge + 4], &[0, 0, 255, 255]);
+        assert!(!frame.transparent);
+
+        // The background color is read from the file's global palette
+        let loaded = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert_eq!(loaded.background_color, Some([0, 0, 0]));
+
+        let mut cropped = odd();
+        cropped.ensure_even_dimensions(false).unwrap();
+        assert_eq!((cropped.width, cropped.height), (100, 100));
+        assert!(cropped.frames.iter().all(|f| f.data.len() == 100 * 100 * 4));
+        assert!(!cropped.frames[1].transparent);
+
+        let mut tiny = Gif::builder().add_frame(Frame::new(1, 2)).build().unwrap();
+        assert!(tiny.ensure_even_dimensions(false).is_err());
+    }
+
+    #[test]
+    fn test_error_categories() {
+        let missing = Gif::from_file("tests/fixtures/does_not_exist.gif").unwrap_err();
+        assert_eq!(
+            missing.downcast_ref::<GifError>(),
+            Some(&GifError::FileNotFound(
+                "tests/fixtures/does_not_exist.gif".to_string()
+            ))
+        );
+
+        let not_a_gif = Gif::from_bytes(b"definitely not a gif").unwrap_err();
+        assert!(matches!(
+            not_a_gif.downcast_ref::<GifError>(),
+            Some(GifError::NotAGif(_))
+        ));
+
+        let unwritable = Gif::from_file("tests/fixtures/simple.gif")
+            .unwrap()
+            .to_file("/nonexistent-dir/out.gif")
+            .unwrap_err();
+        assert!(matches!(
+            unwritable.downcast_ref::<GifError>(),
+            Some(GifError::EncodeFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_thumbnail() {
+        let gif = Gif::from_file("tests/fixtures/large.gif").unwrap();
+
+        let thumb = gif.thumbnail(64, 0).unwrap();
+        assert_eq!((thumb.width, thumb.height), (64, 48));
+
+        let last = gif.thumbnail(64, gif.frames.len() - 1).unwrap();
+        assert_eq!((last.width, last.height), (64, 48));
+
+        assert!(gif.thumbnail(0, 0).is_err());
+        assert!(gif.thumbnail(64, gif.frames.len()).is_err());
+    }
+
+    #[test]
+    fn test_to_file_with_palette() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixed_palette.gif");
+        let path = path.to_str().unwrap();
+        let palette = [
+            [0, 0, 0],
+            [255, 255, 255],
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 0, 255],
+            [255, 255, 0],
+            [0, 255, 255],
+            [255, 0, 255],
+        ];
+
+        let gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        for dither in [false, true] {
+            gif.to_file_with_palette(path, &palette, dither).unwrap();
+            let encoded = Gif::from_file(path).unwrap();
+
+            assert_eq!(encoded.frames.len(), gif.frames.len());
+            assert_eq!(&encoded.global_palette.as_ref().unwrap()[..8], &palette);
+            for frame in &encoded.frames {
+                for pixel in frame.data.chunks_exact(4).filter(|p| p[3] > 0) {
+                    assert!(palette.contains(&[pixel[0], pixel[1], pixel[2]]));
+                }
+            }
+        }
+
+        assert!(gif.to_file_with_palette(path, &[], false).is_err());
+    }
+
+    #[test]
+    fn test_dropping_tagged_frame_warns() {
+        let mut frame = Frame::new(4, 4);
+        assert_eq!(warn_if_tagged(&frame, 1, "merging"), None);
+
+        frame.tags.push("keyframe".to_string());
+        frame.tags.push("intro".to_string());
+        assert_eq!(
+            warn_if_tagged(&frame, 1, "merging identical frames").unwrap(),
+            "merging identical frames removed frame 1 tagged keyframe, intro"
+        );
+    }
+
+    #[test]
+    fn test_limit_frames() {
+        let mut gif = Gif::from_file("tests/fixtures/high_fps.gif").unwrap();
+        let total = gif.total_duration();
+        assert_eq!(gif.frames.len(), 30);
+
+        assert_eq!(gif.limit_frames(10), 20);
+        assert_eq!(gif.frames.len(), 10);
+        assert_eq!(gif.total_duration(), total);
+
+        assert_eq!(gif.limit_frames(20), 0);
+        assert_eq!(gif.frames.len(), 10);
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        let gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let chunks = gif.split_into_chunks(4);
+
+        let sizes: Vec<usize> = chunks.iter().map(|chunk| chunk.frames.len()).collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert!(chunks
+            .iter()
+            .all(|chunk| (chunk.width, chunk.height) == (gif.width, gif.height)));
+        assert_eq!(chunks[2].frames[1].data, gif.frames[9].data);
+    }
+
+    #[test]
+    fn test_composited_frames() {
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        let frames = gif.composited_frames().unwrap();
+
+        assert_eq!(frames.len(), 2);
+        for (image, delay) in &frames {
+            assert_eq!(image.dimensions(), (100, 100));
+            assert_eq!(*delay, 20);
+        }
+        assert_eq!(frames[0].0.get_pixel(50, 50).0, [255, 0, 0, 255]);
+        assert_eq!(frames[1].0.get_pixel(50, 50).0, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_gif_total_duration() {
+        let mut gif = Gif::new();
+        let mut frame1 = Frame::new(10, 10);
+        frame1.delay = 20;
+        let mut frame2 = Frame::new(10, 10);
+        frame2.delay = 30;
+
+        gif.add_frame(frame1);
+        gif.add_frame(frame2);
+
+        assert_eq!(gif.total_duration(), 50);
+    }
+
+    #[test]
+    fn test_gif_duration() {
+        let mut gif = Gif::new();
+        for delay in [10, 20] {
+            let mut frame = Frame::new(10, 10);
+            frame.delay = delay;
+            gif.add_frame(frame);
+        }
+
+        assert_eq!(gif.duration(), std::time::Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_gif_scale_delays() {
+        let mut gif = Gif::new();
+        for delay in [20, 3, 1] {
+            let mut frame = Frame::new(10, 10);
+            frame.delay = delay;
+            gif.add_frame(frame);
+        }
+        let delays = |gif: &Gif| gif.frames.iter().map(|f| f.delay).collect::<Vec<_>>();
+
+        // Faster: delays halved, but never below 1
+        let mut faster = gif.clone();
+        faster.scale_delays(2.0).unwrap();
+        assert_eq!(delays(&faster), vec![10, 2, 1]);
+
+        let mut much_faster = gif.clone();
+        much_faster.scale_delays(100.0).unwrap();
+        assert_eq!(delays(&much_faster), vec![1, 1, 1]);
+
+        // Slower: delays doubled
+        let mut slower = gif.clone();
+        slower.scale_delays(0.5).unwrap();
+        assert_eq!(delays(&slower), vec![40, 6, 2]);
+
+        assert!(gif.scale_delays(0.0).is_err());
+        assert!(gif.scale_delays(-1.0).is_err());
+        assert_eq!(delays(&gif), vec![20, 3, 1]);
+    }
+
+    #[test]
+    fn test_gif_trim_duplicate_boundary() {
+        let solid = |value: u8, delay: u16| {
+            let mut frame = Frame::from_rgba(vec![value; 4 * 4 * 4], 4, 4);
+            frame.delay = delay;
+            frame
+        };
+
+        // Last frame repeats the first
+        let mut gif = Gif::new();
+        gif.add_frame(solid(10, 10));
+        gif.add_frame(solid(20, 10));
+        gif.add_frame(solid(30, 10));
+        gif.add_frame(solid(10, 5));
+
+        assert_eq!(gif.trim_duplicate_boundary(), 1);
+        assert_eq!(gif.frame_count(), 3);
+        assert_eq!(gif.frames[0].delay, 15);
+        assert_eq!(gif.total_duration(), 35);
+
+        // Runs of identical frames at both ends collapse into one
+        let mut gif = Gif::new();
+        gif.add_frame(solid(10, 10));
+        gif.add_frame(solid(10, 10));
+        gif.add_frame(solid(20, 10));
+        gif.add_frame(solid(30, 10));
+        gif.add_frame(solid(30, 10));
+
+        assert_eq!(gif.trim_duplicate_boundary(), 2);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![20, 10, 20]);
+    }
+
+    #[test]
+    fn test_oversized_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        // A 6x6 frame at (2, 0) on a 4x4 canvas, written with the raw encoder
+        let path = dir.path().join("oversized.gif");
+        {
+            let file = File::create(&path).unwrap();
+            let mut encoder =
+                Encoder::new(BufWriter::new(file), 4, 4, &[0, 0, 0, 255, 255, 255]).unwrap();
+            let mut frame = GifFrame::from_indexed_pixels(6, 6, &[1u8; 36], None);
+            frame.left = 2;
+            encoder.write_frame(&frame).unwrap();
+        }
+        let path = path.to_str().unwrap();
+
+        let cropped = Gif::from_file(path).unwrap();
+        let frame = &cropped.frames[0];
+        assert_eq!((cropped.width, cropped.height), (4, 4));
+        assert_eq!((frame.left, frame.width, frame.height), (2, 2, 4));
+        assert_eq!(frame.data.len(), 2 * 4 * 4);
+
+        // Downstream operations can composite it without going out of bounds
+        let mut composited = cropped.clone();
+        composited.normalize().unwrap();
+        assert_eq!(composited.frames[0].data.len(), 4 * 4 * 4);
+
+        let options = DecodeOptions {
+            oversized_frames: OversizedFrames::GrowCanvas,
+            ..DecodeOptions::default()
+        };
+        let grown = Gif::from_file_with_options(path, options).unwrap();
+        assert_eq!((grown.width, grown.height), (8, 6));
+        assert_eq!((grown.frames[0].width, grown.frames[0].height), (6, 6));
+    }
+
+    #[test]
+    fn test_gif_zero_delay_adjustments() {
+        let dir = tempfile::tempdir().unwrap();
+        // Write frames with raw delays, bypassing to_file's minimum delay
+        let path = dir.path().join("zero_delay.gif");
+        {
+            let file = File::create(&path).unwrap();
+            let mut encoder =
+                Encoder::new(BufWriter::new(file), 4, 4, &[0, 0, 0, 255, 255, 255]).unwrap();
+            for delay in [0u16, 5, 0] {
+                let mut frame = GifFrame::from_indexed_pixels(4, 4, &[1u8; 16], None);
+                frame.delay = delay;
+                encoder.write_frame(&frame).unwrap();
+            }
+        }
+        let path = path.to_str().unwrap();
+
+        let gif = Gif::from_file(path).unwrap();
+        assert_eq!(gif.delay_adjustments, 2);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![1, 5, 1]);
+
+        let raw = Gif::from_file_with_options(
+            path,
+            DecodeOptions {
+                min_delay_cs: 0,
+                ..DecodeOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(raw.delay_adjustments, 0);
+        assert_eq!(raw.frames[0].delay, 0);
+    }
+
+    #[test]
+    fn test_gif_per_frame_palette_fidelity() {
+        // Each frame is a 256-color gradient of a different hue, so no single
+        // 256-entry palette can represent the whole animation exactly
+        let mut gif = Gif::new();
+        for hue in 0..6u16 {
+            let mut data = Vec::with_capacity(16 * 16 * 4);
+            for i in 0..256u16 {
+                let level = i as u8;
+                let rgb = match hue % 3 {
+                    0 => [level, (hue * 40) as u8, 0],
+                    1 => [0, level, (hue * 40) as u8],
+                    _ => [(hue * 40) as u8, 0, level],
+                };
+                data.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+            }
+            gif.add_frame(Frame::from_rgba(data, 16, 16));
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let color_error = |options: EncodeOptions, name: &str| {
+            let path = dir.path().join(name);
+            let path = path.to_str().unwrap();
+            gif.to_file_with_options(path, options).unwrap();
+            let reloaded = Gif::from_file(path).unwrap();
+
+            gif.frames
+                .iter()
+                .zip(&reloaded.frames)
+                .flat_map(|(a, b)| a.data.iter().zip(&b.data))
+                .map(|(a, b)| (*a as i64 - *b as i64).abs())
+                .sum::<i64>()
+        };
+
+        let per_frame = color_error(EncodeOptions::default(), "gif_toolkit_local_palettes.gif");
+        let shared = color_error(
+            EncodeOptions {
+                per_frame_palette: false,
+                ..EncodeOptions::default()
+            },
+            "gif_toolkit_global_palette.gif",
+        );
+        assert!(
+            per_frame < shared,
+            "per-frame {} vs shared {}",
+            per_frame,
+            shared
+        );
+    }
+
+    #[test]
+    fn test_gif_dominant_color() {
+        // The black background covers most of both frames
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert_eq!(gif.dominant_color(), [0, 0, 0]);
+
+        // Transparent pixels never count, however many there are
+        let mut data = [0, 0, 0, 0].repeat(60);
+        data.extend([255, 0, 0, 255].repeat(30));
+        data.extend([0, 0, 255, 255].repeat(10));
+        let gif = Gif::builder()
+            .dimensions(10, 10)
+            .add_frame(Frame::from_rgba(data, 10, 10))
+            .build()
+            .unwrap();
+        assert_eq!(gif.dominant_color(), [255, 0, 0]);
+        assert_eq!(gif.prominent_colors(3), vec![[255, 0, 0], [0, 0, 255]]);
+    }
+
+    #[test]
+    fn test_builder() {
+        let gif = Gif::builder()
+            .dimensions(4, 3)
+            .loop_count(Loop::Finite(2))
+            .global_palette(vec![[0, 0, 0], [255, 255, 255]])
+            .add_frame(Frame::new(4, 3))
+            .add_frame(Frame::new(4, 3))
+            .add_frame(Frame::new(4, 3))
+            .delay_all(7)
+            .build()
+            .unwrap();
+
+        assert_eq!(gif.frame_count(), 3);
+        assert_eq!((gif.width, gif.height), (4, 3));
+        assert_eq!(gif.loop_count, Loop::Finite(2));
+        assert_eq!(gif.total_duration(), 21);
+
+        // Mismatched frame sizes are rejected
+        let result = Gif::builder()
+            .add_frame(Frame::new(4, 3))
+            .add_frame(Frame::new(5, 3))
+            .build();
+        assert!(result.is_err());
+
+        // So are empty GIFs
+        assert!(Gif::builder().dimensions(4, 3).build().is_err());
+    }
+
+    #[test]
+    fn test_loop_seam_score() {
+        // Boomerang: play forward, then backward back to the first frame
+        let mut gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let reversed: Vec<Frame> = gif.frames[..gif.frames.len() - 1]
+            .iter()
+            .rev()
+            .cloned()
+            .collect();
+        gif.frames.extend(reversed);
+        assert!(gif.loop_seam_score() <= 1);
+
+        // Red square followed by blue square does not loop cleanly
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        let score = gif.loop_seam_score();
+        assert!(score > 10, "score: {}", score);
+    }
+
+    #[test]
+    fn test_lossless_palette_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("palette_round_trip.gif");
+        let output = output.to_str().unwrap();
+
+        let original = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert!(original
+            .remap_to_palette(original.global_palette.as_ref().unwrap())
+            .is_some());
+
+        original.to_file(output).unwrap();
+        let reloaded = Gif::from_file(output).unwrap();
+
+        assert_eq!(reloaded.frames.len(), original.frames.len());
+        for (a, b) in original.frames.iter().zip(&reloaded.frames) {
+            assert_eq!((a.width, a.height), (b.width, b.height));
+            assert_eq!(a.data, b.data);
+        }
+        assert_eq!(
+            &reloaded.global_palette.unwrap()[..3],
+            &original.global_palette.unwrap()[..3]
+        );
+    }
+
+    #[test]
+    fn test_subsample_frames() {
+        let gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let subset = gif.subsample_frames(&[0, 2, 4]).unwrap();
+
+        assert_eq!(subset.frames.len(), 3);
+        assert_eq!((subset.width, subset.height), (gif.width, gif.height));
+        for (frame, index) in subset.frames.iter().zip([0, 2, 4]) {
+            assert_eq!(frame.data, gif.frames[index].data);
+        }
+
+        // Order follows the indices, not the source
+        let reversed = gif.subsample_frames(&[4, 0]).unwrap();
+        assert_eq!(reversed.frames[0].data, gif.frames[4].data);
+
+        assert!(gif.subsample_frames(&[0, 10]).is_err());
+    }
+
+    #[test]
+    fn test_trim_palette() {
+        let palette: Vec<[u8; 3]> = (0..=255u8).map(|v| [v, v, v]).collect();
+        let mut gif = Gif::builder()
+            .global_palette(palette)
+            .add_frame(Frame::from_rgba([10, 10, 10, 255].repeat(4), 2, 2))
+            .add_frame(Frame::from_rgba(
+                vec![
+                    20, 20, 20, 255, 30, 30, 30, 255, // used colors
+                    40, 40, 40, 0, 20, 20, 20, 255, // transparent pixels don't count
+                ],
+                2,
+                2,
+            ))
+            .build()
+            .unwrap();
+
+        assert_eq!(gif.trim_palette(), 253);
+        assert_eq!(
+            gif.global_palette,
+            Some(vec![[10, 10, 10], [20, 20, 20], [30, 30, 30]])
+        );
+
+        // Nothing left to remove
+        assert_eq!(gif.trim_palette(), 0);
+    }
+
+    #[test]
+    fn test_merge_identical_frames() {
+        let mut gif = Gif::new();
+        for value in [1u8, 1, 1, 2, 1] {
+            gif.add_frame(Frame::from_rgba(vec![value; 4 * 4 * 4], 4, 4));
+        }
+
+        assert_eq!(gif.merge_identical_frames(), 2);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![30, 10, 10]);
+    }
+
+    #[test]
+    fn test_to_file_optimized_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("region_encoded.gif");
+        let output = output.to_str().unwrap();
+
+        // A bar grows to the right, then everything disappears except a corner
+        // dot, which needs the canvas cleared rather than kept
+        let mut gif = Gif::new();
+        for step in 0..4u16 {
+            let mut data = vec![0u8; 16 * 16 * 4];
+            for y in 4..8 {
+                for x in 0..16 {
+                    let visible = if step < 3 {
+                        x < (step + 1) * 4
+                    } else {
+                        x == 15 && y == 4
+                    };
+                    if visible {
+                        let i = (y * 16 + x as usize) * 4;
+                        data[i..i + 4].copy_from_slice(&[255, 0, 0, 255]);
+                    }
+                }
+            }
+            // Each frame is a complete image, cleared before the next one
+            let mut frame = Frame::from_rgba(data, 16, 16);
+            frame.transparent = true;
+            frame.disposal = DisposalMethod::Background;
+            gif.add_frame(frame);
+        }
+
+        gif.to_file_optimized(output).unwrap();
+        let mut reloaded = Gif::from_file(output).unwrap();
+
+        // Only the growing region is stored until the canvas must be cleared
+        let sizes: Vec<(u16, u16)> = reloaded
+            .frames
+            .iter()
+            .map(|f| (f.width, f.height))
+            .collect();
+        assert_eq!(sizes, vec![(4, 4), (4, 4), (16, 16), (1, 1)]);
+
+        reloaded.normalize().unwrap();
+        for (original, decoded) in gif.frames.iter().zip(&reloaded.frames) {
+            assert_eq!(original.data, decoded.data);
+        }
+    }
+
+    #[test]
+    fn test_gif87a_version() {
+        // Minimal 1x1 GIF87a image: no extension blocks at all
+        let gif87a: &[u8] = &[
+            b'G', b'I', b'F', b'8', b'7', b'a', 1, 0, 1, 0, 0x80, 0, 0, // header
+            0, 0, 0, 255, 255, 255, // global color table
+            0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0, // image descriptor
+            2, 2, 0x44, 0x01, 0, // image data
+            0x3B,
+        ];
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("version_87a.gif");
+        let output = dir.path().join("version_87a_out.gif");
+        let input = input.to_str().unwrap();
+        let output = output.to_str().unwrap();
+        std::fs::write(input, gif87a).unwrap();
+
+        let gif = Gif::from_file(input).unwrap();
+        assert_eq!(gif.version, GifVersion::Gif87a);
+        assert_eq!(gif.frames.len(), 1);
+        let simple = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert_eq!(simple.version, GifVersion::Gif89a);
+
+        // Writing GIF87a strips extensions and reloads as GIF87a
+        let options = EncodeOptions {
+            version: GifVersion::Gif87a,
+            ..EncodeOptions::default()
+        };
+        let colorful = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        colorful.to_file_with_options(output, options).unwrap();
+        let bytes = std::fs::read(output).unwrap();
+        let reloaded = Gif::from_file(output).unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF87a");
+        assert_eq!(reloaded.version, GifVersion::Gif87a);
+        assert_eq!(reloaded.frames.len(), colorful.frames.len());
+        assert!(reloaded.frames.iter().all(|f| f.delay == 1));
+
+        // Transparency requires GIF89a
+        let mut transparent = Gif::new();
+        transparent.add_frame(Frame::new(2, 2));
+        assert!(transparent.to_file_with_options(output, options).is_err());
+    }
+
+    #[test]
+    fn test_interlaced_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("interlaced.gif");
+        let output = dir.path().join("interlaced_out.gif");
+        let input = input.to_str().unwrap();
+        let output = output.to_str().unwrap();
+
+        // 2x10 frame where row y uses palette index y, stored interlaced
+        let (width, height) = (2u16, 10u16);
+        let palette: Vec<u8> = (0..16u8).flat_map(|i| [i * 16, 0, 0]).collect();
+        let rows: Vec<u8> = (0..height as u8).flat_map(|y| [y, y]).collect();
+        {
+            let file = File::create(input).unwrap();
+            let mut encoder = Encoder::new(file, width, height, &palette).unwrap();
+            let mut frame = GifFrame::from_indexed_pixels(
+                width,
+                height,
+                &interlace_rows(&rows, width, height),
+                None,
+            );
+            frame.interlaced = true;
+            encoder.write_frame(&frame).unwrap();
+        }
+
+        let gif = Gif::from_file(input).unwrap();
+        assert!(gif.frames[0].interlaced);
+        for y in 0..height as usize {
+            assert_eq!(gif.frames[0].data[y * 8], y as u8 * 16);
+        }
+
+        // Re-encoded progressively by default, interlaced on request
+        gif.to_file(output).unwrap();
+        assert!(!Gif::from_file(output).unwrap().frames[0].interlaced);
+
+        let options = EncodeOptions {
+            preserve_interlace: true,
+            ..EncodeOptions::default()
+        };
+        gif.to_file_with_options(output, options).unwrap();
+        let reloaded = Gif::from_file(output).unwrap();
+
+        assert!(reloaded.frames[0].interlaced);
+        assert_eq!(reloaded.frames[0].data, gif.frames[0].data);
+    }
+
+    #[test]
+    fn test_gif_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("writer.gif");
+        let output = output.to_str().unwrap();
+
+        let mut writer = GifWriter::create(output, 8, 8, Loop::Finite(3)).unwrap();
+        for i in 0..5u8 {
+            let mut frame = Frame::from_rgba([i * 50, 0, 0, 255].repeat(64), 8, 8);
+            frame.delay = (i as u16 + 1) * 10;
+            writer.write_frame(&frame).unwrap();
+        }
+
+        // Frames must fit on the canvas
+        let mut misplaced = Frame::new(8, 8);
+        misplaced.left = 1;
+        assert!(writer.write_frame(&misplaced).is_err());
+
+        writer.finish().unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        assert_eq!((gif.width, gif.height), (8, 8));
+        assert_eq!(gif.loop_count, Loop::Finite(3));
+        assert_eq!(gif.frames.len(), 5);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn test_gif_writer_transparency() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("writer_transparent.gif");
+        let output = output.to_str().unwrap();
+
+        // Opaque green left half; the right half is invisible in three colors
+        let mut data = Vec::new();
+        for i in 0..16 {
+            let pixel = match i % 4 {
+                0 | 1 => [0, 200, 0, 255],
+                2 => [255, 0, 0, 0],
+                _ => [0, 0, 255, 0],
+            };
+            data.extend_from_slice(&pixel);
+        }
+        data[60..64].copy_from_slice(&[9, 9, 9, 0]);
+        let mut frame = Frame::from_rgba(data, 4, 4);
+        frame.transparent = true;
+
+        let mut writer = GifWriter::create(output, 4, 4, Loop::Infinite).unwrap();
+        writer.write_frame(&frame).unwrap();
+
+        // Pixel data must match the frame size
+        let mut short = Frame::new(4, 4);
+        short.data.truncate(8);
+        assert!(writer.write_frame(&short).is_err());
+
+        writer.finish().unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        for (i, pixel) in gif.frames[0].data.chunks_exact(4).enumerate() {
+            if i % 4 < 2 {
+                assert_eq!(pixel, [0, 200, 0, 255], "pixel {}", i);
+            } else {
+                assert_eq!(pixel[3], 0, "pixel {} should be transparent", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_loop_count_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("loop_count.gif");
+        let output = output.to_str().unwrap();
+
+        for loop_count in [
+            Loop::Infinite,
+            Loop::Finite(0),
+            Loop::Finite(5),
+            Loop::Finite(u16::MAX),
+        ] {
+            let mut gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+            gif.loop_count = loop_count;
+            gif.to_file(output).unwrap();
+
+            let reloaded = Gif::from_file(output).unwrap();
+            assert_eq!(reloaded.loop_count, loop_count);
+        }
+    }
+
+    #[test]
+    fn test_loop_to_repeat() {
+        assert!(matches!(Loop::Infinite.to_repeat(), Some(Repeat::Infinite)));
+        assert!(Loop::Finite(0).to_repeat().is_none());
+        assert!(matches!(
+            Loop::Finite(3).to_repeat(),
+            Some(Repeat::Finite(3))
+        ));
+        assert_eq!(Loop::from_netscape(0), Loop::Infinite);
+        assert_eq!(Loop::from_netscape(2), Loop::Finite(2));
+        assert_eq!(Loop::from_netscape(u16::MAX), Loop::Finite(u16::MAX));
+    }
+
+    #[test]
+    fn test_loop_parse_truncated_header() {
+        // Header, logical screen descriptor without a global color table, then
+        // a comment extension whose sub-blocks run past the peeked bytes
+        let mut header = b"GIF89a\x01\x00\x01\x00\x00\x00\x00".to_vec();
+        header.extend_from_slice(b"\x21\xfe\x10comment");
+        assert_eq!(Loop::parse(&header), Loop::Infinite);
+
+        // The same comment, complete and followed by the first image
+        let mut header = b"GIF89a\x01\x00\x01\x00\x00\x00\x00".to_vec();
+        header.extend_from_slice(b"\x21\xfe\x07comment\x00\x2c");
+        assert_eq!(Loop::parse(&header), Loop::Finite(0));
+
+        // A NETSCAPE2.0 extension after the comment is still found
+        header.pop();
+        header.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x04\x00\x00\x2c");
+        assert_eq!(Loop::parse(&header), Loop::Finite(4));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("async.gif");
+        let output = output.to_str().unwrap();
+
+        let gif = Gif::from_file_async("tests/fixtures/simple.gif")
+            .await
+            .unwrap();
+        gif.to_file_async(output).await.unwrap();
+        let reloaded = Gif::from_file_async(output).await.unwrap();
+
+        assert_eq!(reloaded.frames.len(), gif.frames.len());
+        for (original, decoded) in gif.frames.iter().zip(&reloaded.frames) {
+            assert_eq!(original.data, decoded.data);
+        }
+        assert!(Gif::from_file_async("tests/fixtures/missing.gif")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_gif_reader() {
+        let mut reader = GifReader::open("tests/fixtures/large.gif").unwrap();
+        assert_eq!((reader.width(), reader.height()), (800, 600));
+        assert_eq!(reader.global_palette().map(|p| p.len()), Some(4));
+
+        let mut count = 0;
+        while let Some(frame) = reader.next_frame().unwrap() {
+            assert_eq!(
+                frame.data.len(),
+                frame.width as usize * frame.height as usize * 4
+            );
+            assert_eq!(frame.delay, 30);
+            count += 1;
+        }
+
+        assert_eq!(count, 5);
+        assert!(reader.next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_from_file_lossy_truncated() {
+        let bytes = std::fs::read("tests/fixtures/colorful.gif").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("truncated.gif");
+        std::fs::write(&path, &bytes[..bytes.len() * 6 / 10]).unwrap();
+        let path = path.to_str().unwrap();
+
+        assert!(Gif::from_file(path).is_err());
+        let (gif, warnings) = Gif::from_file_lossy(path).unwrap();
+
+        assert!(!gif.frames.is_empty() && gif.frames.len() < 10);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with(&format!("Stopped after {} frames", gif.frames.len())));
+
+        let (intact, warnings) = Gif::from_file_lossy("tests/fixtures/colorful.gif").unwrap();
+        assert_eq!(intact.frames.len(), 10);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_from_bytes_matches_from_file() {
+        let path = "tests/fixtures/simple.gif";
+        let from_file = Gif::from_file(path).unwrap();
+        let from_bytes = Gif::from_bytes(&std::fs::read(path).unwrap()).unwrap();
+
+        assert_eq!(from_bytes.frames.len(), from_file.frames.len());
+        assert_eq!(from_bytes.frames[1].data, from_file.frames[1].data);
+        assert!(Gif::from_bytes(b"not a gif").is_err());
+    }
+
+    #[test]
+    fn test_calculate_frame_difference() {
+        // Create two identical frames
+        let data = vec![255u8; 10 * 10 * 4];
+        let frame1 = Frame::from_rgba(data.clone(), 10, 10);
+        let frame2 = Frame::from_rgba(data, 10, 10);
+
+        let diff = calculate_frame_difference(&frame1, &frame2);
+        assert_eq!(diff, 0);
+
+        // Create completely different frames
+        let data1 = vec![0u8; 10 * 10 * 4];
+        let data2 = vec![255u8; 10 * 10 * 4];
+        let frame3 = Frame::from_rgba(data1, 10, 10);
+        let frame4 = Frame::from_rgba(data2, 10, 10);
+
+        let diff2 = calculate_frame_difference(&frame3, &frame4);
+        assert!(diff2 > 200);
+    }
+
+    #[test]
+    fn test_scene_cuts() {
+        let solid = |rgba: [u8; 4]| Frame::from_rgba(rgba.repeat(8 * 8), 8, 8);
+        let gif = Gif::builder()
+            .dimensions(8, 8)
+            .add_frame(solid([255, 0, 0, 255]))
+            .add_frame(solid([250, 0, 0, 255]))
+            .add_frame(solid([0, 255, 0, 255]))
+            .add_frame(solid([0, 250, 0, 255]))
+            .build()
+            .unwrap();
+
+        assert_eq!(gif.scene_cuts(60).unwrap(), vec![2]);
+        assert_eq!(gif.scene_cuts(255).unwrap(), Vec::<usize>::new());
+        assert_eq!(gif.scene_cuts(0).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_ensure_consistent_dimensions() {
+        let mut base = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        base.frames[0].delay = 7;
+        base.add_frame(Frame::from_rgba(vec![255; 40 * 20 * 4], 40, 20));
+        base.add_frame(Frame::from_rgba(vec![128; 160 * 120 * 4], 160, 120));
+        let mut offset = Frame::from_rgba(vec![200; 30 * 30 * 4], 30, 30);
+        (offset.left, offset.top) = (90, 80);
+        base.add_frame(offset);
+
+        for policy in [ResizePolicy::Pad, ResizePolicy::Crop, ResizePolicy::Scale] {
+            let mut gif = base.clone();
+            gif.ensure_consistent_dimensions(policy).unwrap();
+
+            assert_eq!(gif.frames.len(), 5);
+            assert_eq!(gif.frames[0].delay, 7);
+            for frame in &gif.frames {
+                assert_eq!((frame.width, frame.height), (100, 100), "{:?}", policy);
+                assert_eq!((frame.left, frame.top), (0, 0));
+                assert_eq!(frame.data.len(), 100 * 100 * 4);
+            }
+            assert_eq!(gif.frames[1].data, base.frames[1].data);
+        }
+
+        // Padding keeps pixels at their offset and clips the overflow
+        let mut padded = base.clone();
+        padded
+            .ensure_consistent_dimensions(ResizePolicy::Pad)
+            .unwrap();
+        let pixel = |frame: &Frame, x: usize, y: usize| {
+            let index = (y * 100 + x) * 4;
+            frame.data[index..index + 4].to_vec()
+        };
+        assert_eq!(pixel(&padded.frames[2], 39, 19), vec![255; 4]);
+        assert_eq!(pixel(&padded.frames[2], 40, 19), vec![0; 4]);
+        assert_eq!(pixel(&padded.frames[4], 95, 95), vec![200; 4]);
+        assert_eq!(pixel(&padded.frames[4], 89, 95), vec![0; 4]);
+
+        // Cropping covers the whole canvas
+        let mut cropped = base.clone();
+        cropped
+            .ensure_consistent_dimensions(ResizePolicy::Crop)
+            .unwrap();
+        assert!(!cropped.frames[2].transparent);
+        assert!(!cropped.frames[4].transparent);
+    }
+
+    #[test]
+    fn test_drop_incomplete_trailing_frame() {
+        let mut gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert!(!gif.drop_incomplete_trailing_frame());
+        assert_eq!(gif.frames.len(), 2);
+
+        // A last frame cut off halfway through its pixel data
+        let mut truncated = gif.frames[1].clone();
+        truncated.data.truncate(truncated.data.len() / 2);
+        gif.add_frame(truncated);
+
+        assert!(gif.drop_incomplete_trailing_frame());
+        assert_eq!(gif.frames.len(), 2);
+        assert!(!gif.drop_incomplete_trailing_frame());
+        assert!(!Gif::new().drop_incomplete_trailing_frame());
+    }
+
+    #[test]
+    fn test_from_file_lossy_drops_incomplete_last_frame() {
+        let path = "tests/fixtures/truncated.gif";
+        assert!(Gif::from_file(path).is_err());
+
+        let (gif, warnings) = Gif::from_file_lossy(path).unwrap();
+        assert_eq!(gif.frames.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Stopped after 2 frames, dropped incomplete frame 3"));
+    }
+
+    #[test]
+    fn test_par_map_frames_matches_serial() {
+        let grayscale = |frame: &mut Frame| {
+            for pixel in frame.data.chunks_exact_mut(4) {
+                let luma =
+                    (pixel[0] as u32 * 299 + pixel[1] as u32 * 587 + pixel[2] as u32 * 114) / 1000;
+                pixel[..3].fill(luma as u8);
+            }
+        };
+
+        let mut parallel = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let mut serial = parallel.clone();
+
+        parallel.par_map_frames(grayscale);
+        for frame in &mut serial.frames {
+            grayscale(frame);
+        }
+
+        assert_eq!(parallel.frames.len(), serial.frames.len());
+        for (a, b) in parallel.frames.iter().zip(&serial.frames) {
+            assert_eq!(a.data, b.data);
+        }
+        assert!(parallel.frames[0]
+            .data
+            .chunks_exact(4)
+            .all(|p| p[0] == p[1] && p[1] == p[2]));
     }
 }
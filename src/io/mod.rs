@@ -1,6 +1,6 @@
 // File I/O operations and platform abstraction
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::Path;
 
 /// Validate that a file exists and is readable
@@ -38,6 +38,97 @@ pub fn validate_output_path(path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Write a file atomically
+///
+/// `write_fn` receives the path of a temporary file in the same directory as
+/// `path` and should write the complete output there. On success the temporary
+/// file is renamed over `path` in one step, so readers never see a partial
+/// file; on error it is removed and `path` is left untouched. New files get
+/// the default mode (0666 minus the umask on Unix); a replaced file keeps
+/// its permissions.
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::io::atomic_write;
+///
+/// atomic_write("output.txt", |temp_path| {
+///     std::fs::write(temp_path, b"done")?;
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn atomic_write<F>(path: &str, write_fn: F) -> Result<()>
+where
+    F: FnOnce(&str) -> Result<()>,
+{
+    let target = Path::new(path);
+    // Same directory as the output so the final rename never crosses filesystems
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = target
+        .file_name()
+        .with_context(|| format!("Output path has no file name: {}", path))?;
+
+    let prefix = format!(".{}.", file_name.to_string_lossy());
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    // Temporary files are owner-only by default; outputs get the usual mode
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o666));
+    let temp_file = builder
+        .tempfile_in(dir)
+        .with_context(|| format!("Failed to create temporary file in {}", dir.display()))?;
+
+    // A replaced file keeps its permissions
+    if let Ok(metadata) = std::fs::metadata(target) {
+        std::fs::set_permissions(temp_file.path(), metadata.permissions())
+            .with_context(|| format!("Failed to copy the permissions of {}", path))?;
+    }
+
+    let temp_path = temp_file
+        .path()
+        .to_str()
+        .context("Temporary file path is not valid UTF-8")?
+        .to_string();
+
+    log::debug!("Writing {} via temporary file {}", path, temp_path);
+
+    // Dropping `temp_file` on error deletes the temporary file
+    write_fn(&temp_path)?;
+
+    temp_file
+        .persist(target)
+        .with_context(|| format!("Failed to move temporary file to {}", path))?;
+
+    Ok(())
+}
+
+/// Check whether an input path is an `http://` or `https://` URL
+///
+/// Such paths are downloaded instead of opened when the `network` feature is
+/// enabled; everything else is treated as a local file path.
+pub fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Download a URL into memory, failing if the body exceeds `max_bytes`
+#[cfg(feature = "network")]
+pub fn download(url: &str, max_bytes: u64) -> Result<Vec<u8>> {
+    log::debug!("Downloading {} (limit {} bytes)", url, max_bytes);
+
+    let mut response = ureq::get(url)
+        .call()
+        .with_context(|| format!("Failed to download: {}", url))?;
+    response
+        .body_mut()
+        .with_config()
+        .limit(max_bytes)
+        .read_to_vec()
+        .with_context(|| format!("Failed to download {} (limit {} bytes)", url, max_bytes))
+}
+
 /// Get file size in bytes
 pub fn get_file_size(path: &str) -> Result<u64> {
     let metadata = std::fs::metadata(path)?;
@@ -64,4 +155,74 @@ mod tests {
         assert_eq!(calculate_compression_ratio(1000, 900), 10.0);
         assert_eq!(calculate_compression_ratio(1000, 100), 90.0);
     }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("http://example.com/a.gif"));
+        assert!(is_url("https://example.com/a.gif"));
+
+        // Local paths are left to the file system, even odd-looking ones
+        assert!(!is_url("tests/fixtures/simple.gif"));
+        assert!(!is_url("http_cache/a.gif"));
+        assert!(!is_url("C:\\gifs\\a.gif"));
+    }
+
+    #[test]
+    fn test_atomic_write_error_leaves_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.gif");
+        let output = output.to_str().unwrap();
+
+        let result = atomic_write(output, |temp_path| {
+            std::fs::write(temp_path, b"partial")?;
+            anyhow::bail!("encoder failed");
+        });
+
+        let leftovers = std::fs::read_dir(dir.path()).unwrap().count();
+
+        assert!(result.is_err());
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn test_atomic_write_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("out.gif");
+        let output = output.to_str().unwrap();
+
+        atomic_write(output, |temp_path| {
+            std::fs::write(temp_path, b"complete")?;
+            Ok(())
+        })
+        .unwrap();
+
+        let contents = std::fs::read(output).unwrap();
+        let entries = std::fs::read_dir(dir.path()).unwrap().count();
+
+        assert_eq!(contents, b"complete");
+        assert_eq!(entries, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_output_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mode =
+            |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        let write = |path: &str| atomic_write(path, |temp| Ok(std::fs::write(temp, b"gif")?));
+
+        // A new output gets the same mode as a plainly created file
+        let plain = dir.path().join("plain.gif");
+        std::fs::write(&plain, b"gif").unwrap();
+        let output = dir.path().join("out.gif");
+        write(output.to_str().unwrap()).unwrap();
+        assert_eq!(mode(&output), mode(&plain));
+
+        // A replaced output keeps its mode
+        std::fs::set_permissions(&output, std::fs::Permissions::from_mode(0o640)).unwrap();
+        write(output.to_str().unwrap()).unwrap();
+        assert_eq!(mode(&output), 0o640);
+    }
 }
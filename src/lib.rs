@@ -5,8 +5,12 @@ pub mod cli;
 pub mod core;
 pub mod io;
 pub mod operations;
+pub mod processor;
+#[cfg(feature = "testgen")]
+pub mod testgen;
 pub mod utils;
 
 // Re-exports
 pub use cli::{Args, Commands};
-pub use core::Gif;
+pub use core::{Gif, GifError};
+pub use processor::GifProcessor;
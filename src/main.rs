@@ -1,50 +1,382 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use gif_toolkit::cli::{Args, Commands};
-use gif_toolkit::operations::{compress, info, speed, tune};
+use gif_toolkit::operations::compress::CompressOptions;
+use gif_toolkit::operations::speed::SpeedOptions;
+use gif_toolkit::operations::tune::TuneOptions;
+use gif_toolkit::operations::{
+    alpha, annotate, border, capabilities, channels, colorblind, compress, constant_fps, dedup,
+    diff, duotone, filmstrip, heatmap, histogram, info, limit_frames, onionskin, optimize, pan,
+    pick_frame, pipeline, preview, quantize, repeat, scenes, set_disposal, speed, split,
+    split_chunks, stamp, thumbnail, tune,
+};
+use gif_toolkit::utils::TIMINGS_TARGET;
+use std::io::Write;
 
 fn main() -> Result<()> {
-    // Initialize logger
-    env_logger::init();
-
     // Parse command-line arguments
     let args = Args::parse();
 
+    // Initialize logger; progress messages are logged at info level
+    env_logger::Builder::new()
+        .filter_level(args.log_level())
+        .filter_module(TIMINGS_TARGET, args.timings_level())
+        .format(|buf, record| writeln!(buf, "{}", record.args()))
+        .init();
+    log::debug!("Parsed arguments: {:?}", args);
+
     // Execute the appropriate command
     match args.command {
         Commands::Speed {
             input,
             output,
             factor,
+            no_drop,
+            preserve_interlace,
+            emit_zero_delay,
         } => {
-            println!("Adjusting GIF speed...");
-            speed::run(&input, &output, factor)?;
-            println!("Speed adjustment complete!");
-            println!("Output: {}", output);
+            log::info!("Adjusting GIF speed...");
+            let options = SpeedOptions {
+                drop_frames: !no_drop,
+                preserve_interlace,
+                emit_zero_delay,
+            };
+            speed::run_with_options(&input, &output, factor, &options)?;
+            log::info!("Speed adjustment complete!");
+            log::info!("Output: {}", output);
         }
         Commands::Compress {
             input,
             output,
             percent,
+            optimize_loop,
+            report,
+            metric,
+            report_per_frame,
+            effort,
+            palette_bits,
+            color_space,
+            preserve_palette,
+            max_frames,
         } => {
-            println!("Compressing GIF...");
-            compress::run(&input, &output, percent)?;
-            println!("Compression complete!");
-            println!("Output: {}", output);
+            log::info!("Compressing GIF...");
+            let options = CompressOptions {
+                optimize_loop,
+                report,
+                metric,
+                report_per_frame,
+                effort,
+                palette_bits,
+                color_space,
+                preserve_palette,
+                max_frames,
+            };
+            compress::run_with_options(&input, &output, percent, &options)?;
+            log::info!("Compression complete!");
+            log::info!("Output: {}", output);
         }
         Commands::Tune {
             input,
             output,
             width,
             height,
+            resize_frames_independently,
+        } => {
+            log::info!("Tuning GIF parameters...");
+            let options = TuneOptions {
+                independent: resize_frames_independently,
+            };
+            tune::run_with_options(&input, &output, width, height, &options)?;
+            log::info!("Parameter tuning complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Info { input, a11y } => {
+            info::run(&input, args.verbose, a11y)?;
+        }
+        Commands::Preview { input, frame } => {
+            preview::run(&input, frame)?;
+        }
+        Commands::Alpha {
+            input,
+            output,
+            threshold,
+            binarize,
+            frames,
+        } => {
+            log::info!("Adjusting GIF transparency...");
+            alpha::run(&input, &output, threshold, binarize, frames.as_ref())?;
+            log::info!("Transparency adjustment complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::ConstantFps {
+            input,
+            output,
+            fps,
+            no_preserve_timing,
+        } => {
+            log::info!("Resampling GIF to constant frame rate...");
+            constant_fps::run(&input, &output, fps, !no_preserve_timing)?;
+            log::info!("Frame rate conversion complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Onionskin {
+            input,
+            output,
+            falloff,
+        } => {
+            log::info!("Creating onion skin...");
+            onionskin::run(&input, &output, falloff)?;
+            log::info!("Onion skin complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Heatmap { input, output } => {
+            log::info!("Creating frame difference heatmap...");
+            heatmap::run(&input, &output)?;
+            log::info!("Heatmap complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Histogram {
+            input,
+            output,
+            json_out,
+        } => {
+            log::info!("Creating color histogram...");
+            histogram::run(&input, &output, json_out.as_deref())?;
+            log::info!("Histogram complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Optimize {
+            input,
+            output,
+            drop_bad_last,
+        } => {
+            log::info!("Optimizing GIF...");
+            optimize::run(&input, &output, drop_bad_last)?;
+            log::info!("Optimization complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::PickFrame {
+            input,
+            output,
+            index,
+        } => {
+            log::info!("Picking frame...");
+            pick_frame::run(&input, &output, index)?;
+            log::info!("Frame saved!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Colorblind {
+            input,
+            output,
+            kind,
+        } => {
+            log::info!("Simulating color vision deficiency...");
+            colorblind::run(&input, &output, kind)?;
+            log::info!("Simulation complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Annotate {
+            input,
+            output,
+            rects,
+        } => {
+            log::info!("Annotating GIF...");
+            annotate::run(&input, &output, &rects)?;
+            log::info!("Annotation complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::SetDisposal {
+            input,
+            output,
+            method,
+        } => {
+            log::info!("Setting frame disposal...");
+            set_disposal::run(&input, &output, method)?;
+            log::info!("Disposal updated!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Repeat {
+            input,
+            output,
+            times,
+            play_once,
+        } => {
+            log::info!("Repeating animation...");
+            repeat::run(&input, &output, times, play_once)?;
+            log::info!("Repeat complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Diff { a, b, tolerance } => {
+            diff::run(&a, &b, tolerance)?;
+        }
+        Commands::Capabilities { json } => {
+            let subcommands = Args::command()
+                .get_subcommands()
+                .map(|command| command.get_name().to_string())
+                .collect();
+            capabilities::run(json, subcommands)?;
+        }
+        Commands::Pan {
+            input,
+            output,
+            dx,
+            dy,
+            wrap,
+        } => {
+            log::info!("Panning frames...");
+            pan::run(&input, &output, dx, dy, wrap)?;
+            log::info!("Pan complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Channels { input, output_dir } => {
+            log::info!("Exporting channels...");
+            channels::run(&input, &output_dir)?;
+            log::info!("Channel export complete!");
+            log::info!("Output: {}", output_dir);
+        }
+        Commands::Stamp {
+            input,
+            output,
+            show_index,
+            show_time,
+        } => {
+            log::info!("Stamping frames...");
+            stamp::run(&input, &output, show_index, show_time)?;
+            log::info!("Stamp complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Quantize {
+            input,
+            output,
+            colors,
+        } => {
+            log::info!("Quantizing GIF colors...");
+            quantize::run(&input, &output, colors)?;
+            log::info!("Quantization complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Filmstrip {
+            input,
+            output,
+            direction,
+        } => {
+            log::info!("Creating filmstrip...");
+            filmstrip::run(&input, &output, direction)?;
+            log::info!("Filmstrip complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::SplitChunks {
+            input,
+            output_prefix,
+            frames_per_chunk,
+        } => {
+            log::info!("Splitting GIF into chunks...");
+            split_chunks::run(&input, &output_prefix, frames_per_chunk)?;
+            log::info!("Split complete!");
+            log::info!("Output: {}_*.gif", output_prefix);
+        }
+        Commands::LimitFrames {
+            input,
+            output,
+            max_frames,
+        } => {
+            log::info!("Limiting frame count...");
+            limit_frames::run(&input, &output, max_frames)?;
+            log::info!("Frame limit complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Split {
+            input,
+            output,
+            indexed,
+        } => {
+            log::info!("Splitting GIF into PNG frames...");
+            split::run(&input, &output, indexed)?;
+            log::info!("Split complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Duotone {
+            input,
+            output,
+            dark,
+            light,
+        } => {
+            log::info!("Applying duotone...");
+            duotone::run(&input, &output, dark, light)?;
+            log::info!("Duotone complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Border {
+            input,
+            output,
+            width,
+            color,
+        } => {
+            log::info!("Adding border...");
+            border::run(&input, &output, width, color)?;
+            log::info!("Border complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Thumbnail {
+            input,
+            output,
+            size,
+            frame,
+        } => {
+            log::info!("Creating thumbnail...");
+            thumbnail::run(&input, &output, size, frame)?;
+            log::info!("Thumbnail complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::SpeedRamp {
+            input,
+            output,
+            start,
+            end,
         } => {
-            println!("Tuning GIF parameters...");
-            tune::run(&input, &output, width, height)?;
-            println!("Parameter tuning complete!");
-            println!("Output: {}", output);
+            log::info!("Ramping GIF speed...");
+            speed::run_ramp(&input, &output, start, end)?;
+            log::info!("Speed ramp complete!");
+            log::info!("Output: {}", output);
         }
-        Commands::Info { input } => {
-            info::run(&input)?;
+        Commands::Dedup {
+            input,
+            output,
+            threshold,
+            dedup_window,
+        } => {
+            log::info!("Deduplicating frames...");
+            dedup::run(
+                &input,
+                &output,
+                threshold,
+                dedup_window.unwrap_or(usize::MAX),
+            )?;
+            log::info!("Deduplication complete!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Scenes { input, threshold } => {
+            scenes::run(&input, threshold)?;
+        }
+        Commands::SetDelay {
+            input,
+            output,
+            delay,
+        } => {
+            log::info!("Setting frame delays...");
+            speed::set_uniform_delay(&input, &output, delay)?;
+            log::info!("Frame delays set!");
+            log::info!("Output: {}", output);
+        }
+        Commands::Pipeline {
+            input,
+            output,
+            spec,
+        } => {
+            log::info!("Running pipeline...");
+            pipeline::run(&input, &output, &spec)?;
+            log::info!("Pipeline complete!");
+            log::info!("Output: {}", output);
         }
     }
 
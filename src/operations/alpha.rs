@@ -0,0 +1,102 @@
+use crate::core::{Frame, Gif};
+use crate::utils::FrameSelector;
+use anyhow::{Context, Result};
+
+/// Clean up alpha values using a threshold
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `threshold` - Alpha values below this become fully transparent
+/// * `binarize` - Also make alpha values at or above the threshold fully opaque
+/// * `frames` - Only adjust these frames (all frames if `None`)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::alpha;
+///
+/// // Make every pixel either fully transparent or fully opaque
+/// alpha::run("input.gif", "output.gif", 128, true, None).unwrap();
+/// ```
+pub fn run(
+    input: &str,
+    output: &str,
+    threshold: u8,
+    binarize: bool,
+    frames: Option<&FrameSelector>,
+) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Alpha threshold: {}", threshold);
+    log::info!("   Binarize: {}", binarize);
+
+    if let Some(selector) = frames {
+        if selector.exceeds(gif.frames.len()) {
+            log::info!(
+                "   Warning: frame selection goes beyond the last frame ({}), ignoring those indices",
+                gif.frames.len().saturating_sub(1)
+            );
+        }
+    }
+
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        if frames.is_none_or(|selector| selector.contains(index)) {
+            apply_alpha_threshold(frame, threshold, binarize);
+        }
+    }
+
+    let transparent_frames = gif.frames.iter().filter(|f| f.transparent).count();
+    log::info!("   Frames with transparency: {}", transparent_frames);
+
+    // Save the modified GIF
+    gif.to_file(output).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Apply the alpha threshold to a single frame and refresh its transparency flag
+fn apply_alpha_threshold(frame: &mut Frame, threshold: u8, binarize: bool) {
+    for pixel in frame.data.chunks_exact_mut(4) {
+        if pixel[3] < threshold {
+            pixel[3] = 0;
+        } else if binarize {
+            pixel[3] = 255;
+        }
+    }
+
+    frame.transparent = frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binarize_alpha() {
+        let data = vec![
+            255, 0, 0, 10, // Nearly transparent
+            0, 255, 0, 200, // Mostly opaque
+        ];
+        let mut frame = Frame::from_rgba(data, 2, 1);
+
+        apply_alpha_threshold(&mut frame, 128, true);
+
+        assert_eq!(frame.data[3], 0);
+        assert_eq!(frame.data[7], 255);
+        assert!(frame.transparent);
+    }
+
+    #[test]
+    fn test_clamp_alpha_without_binarize() {
+        let data = vec![255, 0, 0, 10, 0, 255, 0, 200];
+        let mut frame = Frame::from_rgba(data, 2, 1);
+
+        apply_alpha_threshold(&mut frame, 128, false);
+
+        assert_eq!(frame.data[3], 0);
+        assert_eq!(frame.data[7], 200);
+    }
+}
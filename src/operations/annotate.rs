@@ -0,0 +1,217 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use crate::utils::parse_hex_color;
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// A rectangle to draw on every frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape {
+    /// Left edge in pixels
+    pub x: u16,
+    /// Top edge in pixels
+    pub y: u16,
+    /// Width in pixels
+    pub width: u16,
+    /// Height in pixels
+    pub height: u16,
+    /// Opaque RGB color
+    pub color: [u8; 3],
+    /// `None` fills the rectangle, `Some(n)` draws an outline `n` pixels thick
+    pub outline: Option<u16>,
+}
+
+impl Shape {
+    /// Parse a rectangle spec `x,y,w,h,#rrggbb[,thickness]`
+    ///
+    /// Without a thickness the rectangle is filled; with one only its outline
+    /// is drawn.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(',').map(str::trim).collect();
+        if !(5..=6).contains(&parts.len()) {
+            anyhow::bail!(
+                "Invalid rectangle '{}', expected x,y,w,h,#rrggbb[,thickness]",
+                spec
+            );
+        }
+
+        let number = |index: usize, name: &str| -> Result<u16> {
+            parts[index].parse().with_context(|| {
+                format!(
+                    "Invalid {} '{}' in rectangle '{}'",
+                    name, parts[index], spec
+                )
+            })
+        };
+
+        let shape = Self {
+            x: number(0, "x")?,
+            y: number(1, "y")?,
+            width: number(2, "width")?,
+            height: number(3, "height")?,
+            color: parse_hex_color(parts[4])?,
+            outline: if parts.len() == 6 {
+                Some(number(5, "thickness")?)
+            } else {
+                None
+            },
+        };
+
+        if shape.width == 0 || shape.height == 0 || shape.outline == Some(0) {
+            anyhow::bail!(
+                "Rectangle '{}' must have a non-zero size and thickness",
+                spec
+            );
+        }
+
+        Ok(shape)
+    }
+
+    /// Check whether the shape covers a pixel
+    fn covers(&self, px: u32, py: u32) -> bool {
+        let (x0, y0) = (self.x as u32, self.y as u32);
+        let (x1, y1) = (x0 + self.width as u32, y0 + self.height as u32);
+        if px < x0 || px >= x1 || py < y0 || py >= y1 {
+            return false;
+        }
+
+        match self.outline {
+            None => true,
+            Some(thickness) => {
+                let t = thickness as u32;
+                px < x0 + t || px + t >= x1 || py < y0 + t || py + t >= y1
+            }
+        }
+    }
+}
+
+impl FromStr for Shape {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Self::parse(spec)
+    }
+}
+
+/// Draw rectangles over every frame, e.g. to redact or highlight areas
+///
+/// Frames are composited first so every frame is a full canvas, then the
+/// shapes are drawn in order (later shapes on top). Parts of a shape outside
+/// the canvas are clipped.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `shapes` - Rectangles to draw
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::annotate::{self, Shape};
+///
+/// // Black out a region and outline another in red
+/// let shapes = [
+///     Shape::parse("10,10,80,20,#000000").unwrap(),
+///     Shape::parse("120,40,50,50,#ff0000,3").unwrap(),
+/// ];
+/// annotate::run("input.gif", "output.gif", &shapes).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, shapes: &[Shape]) -> Result<()> {
+    if shapes.is_empty() {
+        anyhow::bail!("At least one shape must be specified");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Shapes: {}", shapes.len());
+
+    // Shapes are drawn in canvas coordinates
+    gif.normalize()?;
+
+    gif.par_map_frames(|frame| draw_shapes(frame, shapes));
+
+    // Save the annotated GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Draw every shape onto a full-canvas frame
+fn draw_shapes(frame: &mut Frame, shapes: &[Shape]) {
+    frame.unpremultiply();
+    let width = frame.width as u32;
+
+    for shape in shapes {
+        let x_end = (shape.x as u32 + shape.width as u32).min(width);
+        let y_end = (shape.y as u32 + shape.height as u32).min(frame.height as u32);
+
+        for y in shape.y as u32..y_end {
+            for x in shape.x as u32..x_end {
+                if shape.covers(x, y) {
+                    let index = ((y * width + x) * 4) as usize;
+                    frame.data[index..index + 3].copy_from_slice(&shape.color);
+                    frame.data[index + 3] = 255;
+                }
+            }
+        }
+    }
+
+    frame.transparent = frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shape() {
+        let filled: Shape = "1, 2, 30, 40, #ff0000".parse().unwrap();
+        assert_eq!(
+            (filled.x, filled.y, filled.width, filled.height),
+            (1, 2, 30, 40)
+        );
+        assert_eq!(filled.color, [255, 0, 0]);
+        assert_eq!(filled.outline, None);
+
+        let outlined = Shape::parse("0,0,10,10,#00ff00,2").unwrap();
+        assert_eq!(outlined.outline, Some(2));
+
+        assert!(Shape::parse("0,0,10,#ff0000").is_err());
+        assert!(Shape::parse("0,0,0,10,#ff0000").is_err());
+        assert!(Shape::parse("a,0,10,10,#ff0000").is_err());
+    }
+
+    #[test]
+    fn test_outline_covers_border_only() {
+        let shape = Shape::parse("0,0,5,5,#ffffff,1").unwrap();
+        assert!(shape.covers(0, 0));
+        assert!(shape.covers(4, 2));
+        assert!(!shape.covers(2, 2));
+        assert!(!shape.covers(5, 0));
+    }
+
+    #[test]
+    fn test_annotate_filled_box() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("annotate.gif");
+        let output = output.to_str().unwrap();
+
+        let shapes = [Shape::parse("10,20,30,15,#ff0000").unwrap()];
+        run("tests/fixtures/colorful.gif", output, &shapes).unwrap();
+        let mut gif = Gif::from_file(output).unwrap();
+        gif.normalize().unwrap();
+
+        assert_eq!(gif.frames.len(), 10);
+        for frame in &gif.frames {
+            for y in 20..35 {
+                for x in 10..40 {
+                    let index = (y * frame.width as usize + x) * 4;
+                    assert_eq!(&frame.data[index..index + 4], &[255, 0, 0, 255]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Surround every frame with a solid-color border
+///
+/// The canvas grows by `width` pixels on every side and the margin is filled
+/// with `color`. Frames are composited first so each one covers the whole
+/// original canvas. GIF transparency is on/off, so an alpha below 128 gives a
+/// transparent margin and anything else an opaque one.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `width` - Border width in pixels
+/// * `color` - RGBA border color
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::border;
+///
+/// // 8px white frame
+/// border::run("input.gif", "framed.gif", 8, [255, 255, 255, 255]).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, width: u32, color: [u8; 4]) -> Result<()> {
+    if width == 0 {
+        anyhow::bail!("Border width must be at least 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let new_width = bordered_dimension(gif.width, width, "width")?;
+    let new_height = bordered_dimension(gif.height, width, "height")?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Original size: {}x{}", gif.width, gif.height);
+    log::info!("   Bordered size: {}x{}", new_width, new_height);
+
+    // Every frame must cover the whole original canvas
+    gif.normalize()?;
+
+    let color = [
+        color[0],
+        color[1],
+        color[2],
+        if color[3] < 128 { 0 } else { 255 },
+    ];
+    let border = width as u16;
+    gif.par_map_frames(|frame| add_border(frame, border, new_width, new_height, color));
+    gif.width = new_width;
+    gif.height = new_height;
+
+    // Save the bordered GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Canvas dimension after adding a border on both sides
+fn bordered_dimension(size: u16, border: u32, name: &str) -> Result<u16> {
+    let bordered = size as u64 + 2 * border as u64;
+    u16::try_from(bordered).with_context(|| {
+        format!(
+            "Bordered {} {} exceeds the GIF limit of {} pixels",
+            name,
+            bordered,
+            u16::MAX
+        )
+    })
+}
+
+/// Place a full-canvas frame inside a new canvas filled with `color`
+fn add_border(frame: &mut Frame, border: u16, width: u16, height: u16, color: [u8; 4]) {
+    frame.unpremultiply();
+
+    let mut data = color.repeat(width as usize * height as usize);
+    let src_stride = frame.width as usize * 4;
+    for (row, src) in frame.data.chunks_exact(src_stride).enumerate() {
+        let start = ((row + border as usize) * width as usize + border as usize) * 4;
+        data[start..start + src_stride].copy_from_slice(src);
+    }
+
+    frame.data = data;
+    frame.width = width;
+    frame.height = height;
+    frame.left = 0;
+    frame.top = 0;
+    frame.transparent = frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_red_border() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("border.gif");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/simple.gif", output, 5, [255, 0, 0, 255]).unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        assert_eq!((gif.width, gif.height), (110, 110));
+        assert_eq!(gif.frames.len(), 2);
+        for frame in &gif.frames {
+            let pixel = |x: usize, y: usize| {
+                let index = (y * frame.width as usize + x) * 4;
+                &frame.data[index..index + 4]
+            };
+            for (x, y) in [(0, 0), (4, 50), (109, 109), (50, 105)] {
+                assert_eq!(pixel(x, y), &[255, 0, 0, 255]);
+            }
+        }
+
+        // The original content sits inside the border
+        let center = (55 * 110 + 55) * 4;
+        assert_eq!(&gif.frames[1].data[center..center + 4], &[0, 0, 255, 255]);
+        assert!(run("tests/fixtures/simple.gif", output, 40_000, [0, 0, 0, 255]).is_err());
+    }
+}
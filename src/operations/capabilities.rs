@@ -0,0 +1,130 @@
+use anyhow::Result;
+
+/// What this build of the toolkit supports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// File formats operations can write
+    pub output_formats: Vec<&'static str>,
+    /// Optional cargo features compiled in
+    pub features: Vec<&'static str>,
+    /// Largest canvas width and height a GIF can have
+    pub max_dimension: u16,
+    /// Available CLI subcommands, as given by the caller
+    pub subcommands: Vec<String>,
+}
+
+impl Capabilities {
+    /// Detect the capabilities of the running build
+    ///
+    /// The library does not know the CLI, so the binary passes in its
+    /// subcommand names.
+    pub fn detect(subcommands: Vec<String>) -> Self {
+        let optional_features = [
+            ("async", cfg!(feature = "async")),
+            ("network", cfg!(feature = "network")),
+            ("testgen", cfg!(feature = "testgen")),
+        ];
+
+        Self {
+            output_formats: vec!["gif", "png"],
+            features: optional_features
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| *name)
+                .collect(),
+            max_dimension: u16::MAX,
+            subcommands,
+        }
+    }
+
+    /// Serialize as a single JSON object
+    pub fn to_json(&self) -> String {
+        let list = |items: &mut dyn Iterator<Item = &str>| {
+            let quoted: Vec<String> = items.map(|item| format!("\"{}\"", item)).collect();
+            format!("[{}]", quoted.join(", "))
+        };
+
+        format!(
+            "{{\"output_formats\": {}, \"features\": {}, \"max_dimension\": {}, \"subcommands\": {}}}",
+            list(&mut self.output_formats.iter().copied()),
+            list(&mut self.features.iter().copied()),
+            self.max_dimension,
+            list(&mut self.subcommands.iter().map(String::as_str))
+        )
+    }
+}
+
+/// Print which formats, features and subcommands this build supports
+///
+/// Useful for scripts that need to check whether an optional feature (such
+/// as URL input) was compiled in.
+///
+/// # Arguments
+/// * `json` - Print a single JSON object instead of text
+/// * `subcommands` - Names of the CLI subcommands to list
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::capabilities;
+///
+/// capabilities::run(true, vec!["info".to_string()]).unwrap();
+/// ```
+pub fn run(json: bool, subcommands: Vec<String>) -> Result<()> {
+    let capabilities = Capabilities::detect(subcommands);
+
+    if json {
+        println!("{}", capabilities.to_json());
+        return Ok(());
+    }
+
+    let features = if capabilities.features.is_empty() {
+        "none".to_string()
+    } else {
+        capabilities.features.join(", ")
+    };
+
+    println!("GIF Toolkit Capabilities:");
+    println!(
+        "  Output formats: {}",
+        capabilities.output_formats.join(", ")
+    );
+    println!("  Optional features: {}", features);
+    println!(
+        "  Max dimensions: {}x{} pixels",
+        capabilities.max_dimension, capabilities.max_dimension
+    );
+    println!("  Subcommands: {}", capabilities.subcommands.join(", "));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_capabilities_json() {
+        let subcommands: Vec<String> = Args::command()
+            .get_subcommands()
+            .map(|command| command.get_name().to_string())
+            .collect();
+        let json = Capabilities::detect(subcommands.clone()).to_json();
+        assert!(json.starts_with('{') && json.ends_with('}'));
+
+        // Pull the subcommand names out of the JSON array
+        let start = json.find("\"subcommands\": [").unwrap() + "\"subcommands\": [".len();
+        let end = start + json[start..].find(']').unwrap();
+        let parsed: Vec<&str> = json[start..end]
+            .split(", ")
+            .map(|name| name.trim_matches('"'))
+            .collect();
+
+        assert_eq!(parsed, subcommands);
+        for name in ["speed", "compress", "tune", "info"] {
+            assert!(parsed.contains(&name), "missing {}", name);
+        }
+        assert!(json.contains("\"max_dimension\": 65535"));
+    }
+}
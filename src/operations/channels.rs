@@ -0,0 +1,105 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Output file names, in RGBA channel order
+pub const CHANNEL_FILES: [&str; 4] = ["red.gif", "green.gif", "blue.gif", "alpha.gif"];
+
+/// Export each RGBA channel as its own grayscale GIF, e.g. to debug alpha
+///
+/// Frames are composited first, so every output shows exactly what is on
+/// screen. `red.gif`, `green.gif`, `blue.gif` and `alpha.gif` are written to
+/// `output_dir` (created if missing) with the input timing; a channel value
+/// of 0 is black and 255 is white.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_dir` - Directory for the four channel GIFs
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::channels;
+///
+/// channels::run("input.gif", "channels").unwrap();
+/// ```
+pub fn run(input: &str, output_dir: &str) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+
+    // Extract what is on screen, not the stored partial frames
+    gif.normalize()?;
+    for frame in &mut gif.frames {
+        frame.unpremultiply();
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", output_dir))?;
+
+    for (channel, name) in CHANNEL_FILES.iter().enumerate() {
+        let mut channel_gif = gif.clone();
+        for frame in &mut channel_gif.frames {
+            extract_channel(frame, channel);
+        }
+
+        let path = Path::new(output_dir).join(name);
+        let path = path.to_str().context("Output path is not valid UTF-8")?;
+        io::atomic_write(path, |temp| channel_gif.to_file(temp))
+            .with_context(|| format!("Failed to save channel GIF: {}", path))?;
+        log::info!("   Wrote: {}", path);
+    }
+
+    Ok(())
+}
+
+/// Replace every pixel with an opaque gray of one channel's value
+fn extract_channel(frame: &mut Frame, channel: usize) {
+    for pixel in frame.data.chunks_exact_mut(4) {
+        let value = pixel[channel];
+        pixel.copy_from_slice(&[value, value, value, 255]);
+    }
+    frame.transparent = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha_channel_of_transparent_quadrant() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.gif");
+        let input = input.to_str().unwrap();
+
+        // 8x8 orange frame whose top-left 4x4 quadrant is transparent
+        let mut frame = Frame::new(8, 8);
+        for (i, pixel) in frame.data.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (i % 8, i / 8);
+            if x >= 4 || y >= 4 {
+                pixel.copy_from_slice(&[255, 128, 0, 255]);
+            }
+        }
+        frame.transparent = true;
+        let mut gif = Gif::new();
+        gif.add_frame(frame);
+        gif.to_file(input).unwrap();
+
+        run(input, dir.path().to_str().unwrap()).unwrap();
+        let alpha = Gif::from_file(dir.path().join("alpha.gif").to_str().unwrap()).unwrap();
+        let red = Gif::from_file(dir.path().join("red.gif").to_str().unwrap()).unwrap();
+
+        for (i, pixel) in alpha.frames[0].data.chunks_exact(4).enumerate() {
+            let (x, y) = (i % 8, i / 8);
+            let expected = if x < 4 && y < 4 { 0 } else { 255 };
+            assert_eq!(pixel, [expected, expected, expected, 255], "({}, {})", x, y);
+        }
+        assert_eq!(
+            &red.frames[0].data[(7 * 8 + 7) * 4..],
+            &[255, 255, 255, 255]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use crate::utils::{linear_to_srgb, srgb_to_linear};
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// Type of color vision deficiency to simulate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CbKind {
+    /// No functioning long-wavelength (red) cones
+    Protanopia,
+    /// No functioning medium-wavelength (green) cones
+    Deuteranopia,
+    /// No functioning short-wavelength (blue) cones
+    Tritanopia,
+}
+
+impl FromStr for CbKind {
+    type Err = anyhow::Error;
+
+    /// Parse a deficiency name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "protanopia" => Ok(Self::Protanopia),
+            "deuteranopia" => Ok(Self::Deuteranopia),
+            "tritanopia" => Ok(Self::Tritanopia),
+            _ => anyhow::bail!(
+                "Unknown deficiency '{}', expected protanopia, deuteranopia or tritanopia",
+                name.trim()
+            ),
+        }
+    }
+}
+
+/// Linear RGB to LMS cone response (Viénot, Brettel & Mollon 1999)
+const RGB_TO_LMS: [[f64; 3]; 3] = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.0299566, 0.184309, 1.46709],
+];
+
+/// Inverse of [`RGB_TO_LMS`]
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+    [0.0809444479, -0.130504409, 0.116721066],
+    [-0.0102485335, 0.0540193266, -0.113614708],
+    [-0.000365296938, -0.00412161469, 0.693511405],
+];
+
+impl CbKind {
+    /// LMS projection that replaces the missing cone response
+    fn lms_projection(self) -> [[f64; 3]; 3] {
+        match self {
+            CbKind::Protanopia => [[0.0, 2.02344, -2.52581], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            CbKind::Deuteranopia => [[1.0, 0.0, 0.0], [0.494207, 0.0, 1.24827], [0.0, 0.0, 1.0]],
+            CbKind::Tritanopia => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [-0.395913, 0.801109, 0.0]],
+        }
+    }
+}
+
+/// Simulate how a GIF looks with a color vision deficiency
+///
+/// Each pixel is converted to linear RGB, mapped to LMS cone space, projected
+/// onto the colors distinguishable with the given deficiency and converted
+/// back. Alpha and timing are unchanged.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `kind` - Deficiency to simulate
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::colorblind::{self, CbKind};
+///
+/// colorblind::run("input.gif", "protanopia.gif", CbKind::Protanopia).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, kind: CbKind) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Simulating: {:?}", kind);
+
+    gif.par_map_frames(|frame| simulate_frame(frame, kind));
+
+    // Save the simulated GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Apply the simulation to every pixel of a frame
+pub(crate) fn simulate_frame(frame: &mut Frame, kind: CbKind) {
+    frame.unpremultiply();
+    for pixel in frame.data.chunks_exact_mut(4) {
+        let [r, g, b] = simulate_pixel([pixel[0], pixel[1], pixel[2]], kind);
+        pixel[0] = r;
+        pixel[1] = g;
+        pixel[2] = b;
+    }
+}
+
+/// Simulate a single sRGB color
+pub fn simulate_pixel(rgb: [u8; 3], kind: CbKind) -> [u8; 3] {
+    let linear = rgb.map(srgb_to_linear);
+    let lms = multiply(&RGB_TO_LMS, linear);
+    let projected = multiply(&kind.lms_projection(), lms);
+    multiply(&LMS_TO_RGB, projected).map(linear_to_srgb)
+}
+
+/// Multiply a 3x3 matrix by a column vector
+fn multiply(matrix: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protanopia_red() {
+        // Pure red loses most of its brightness and shifts to a dark yellow
+        let [r, g, b] = simulate_pixel([255, 0, 0], CbKind::Protanopia);
+        assert!((r as i32 - g as i32).abs() <= 4, "{:?}", [r, g, b]);
+        assert!((80..=110).contains(&r), "{:?}", [r, g, b]);
+        assert!(b < 30, "{:?}", [r, g, b]);
+    }
+
+    #[test]
+    fn test_neutral_colors_unchanged() {
+        for kind in [CbKind::Protanopia, CbKind::Deuteranopia, CbKind::Tritanopia] {
+            for gray in [0, 128, 255] {
+                let simulated = simulate_pixel([gray, gray, gray], kind);
+                for channel in simulated {
+                    assert!((channel as i32 - gray as i32).abs() <= 2);
+                }
+            }
+        }
+    }
+}
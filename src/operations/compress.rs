@@ -1,84 +1,116 @@
-use crate::core::{Frame, Gif};
+use crate::core::{EncodeOptions, Gif, NEUQUANT_SAMPLE_FACTOR};
+use crate::io;
+use crate::operations::optimize;
+use crate::utils::{self, StageTimer};
 use anyhow::{Context, Result};
 use std::fs;
+use std::str::FromStr;
+
+/// Default for [`CompressOptions::effort`]
+pub const DEFAULT_EFFORT: u8 = 5;
+
+/// Optional behavior for [`run_with_options`]
+#[derive(Debug, Clone)]
+pub struct CompressOptions {
+    /// Merge duplicated frames at the loop boundary before compressing
+    /// (see [`Gif::trim_duplicate_boundary`])
+    pub optimize_loop: bool,
+    /// Print a quality report comparing input and output when done
+    pub report: bool,
+    /// Metric used by the quality report
+    pub metric: QualityMetric,
+    /// Print a per-frame PSNR table with this many of the worst frames
+    /// highlighted
+    pub report_per_frame: Option<usize>,
+    /// How hard to search for a smaller file (1-9)
+    ///
+    /// Every step re-encodes the whole GIF, so lower values finish sooner:
+    /// 1 runs three compression steps, 2 runs four and 3 or more run all
+    /// five. Above 5 each extra level adds one refinement that lowers the
+    /// lossy quality another rung while the file is still over the target,
+    /// giving smaller output for more encoding time.
+    pub effort: u8,
+    /// Limit the palette to `2^bits` colors (1-8) for fewer bits per pixel
+    /// (see [`EncodeOptions::palette_bits`])
+    pub palette_bits: Option<u8>,
+    /// Color space used to match pixels to the reduced palette
+    pub color_space: ColorSpace,
+    /// Keep the original colors exactly when the input has at most 256 of
+    /// them: only lossless reductions are applied and the target percentage
+    /// may not be reached
+    ///
+    /// Cannot be combined with `palette_bits` or a non-default `color_space`.
+    /// `effort` is not used for such inputs.
+    pub preserve_palette: bool,
+    /// Uniformly drop frames down to at most this many, keeping the total
+    /// duration (see [`Gif::limit_frames`])
+    pub max_frames: Option<usize>,
+}
 
-/// Normalize all frames to full GIF dimensions
-///
-/// Fill transparent areas with background color to prevent flickering with Background disposal
-fn normalize_frames(gif: &mut Gif) -> Result<()> {
-    if gif.frames.is_empty() {
-        return Ok(());
+impl Default for CompressOptions {
+    fn default() -> Self {
+        Self {
+            optimize_loop: false,
+            report: false,
+            metric: QualityMetric::default(),
+            report_per_frame: None,
+            effort: DEFAULT_EFFORT,
+            palette_bits: None,
+            color_space: ColorSpace::default(),
+            preserve_palette: false,
+            max_frames: None,
+        }
     }
+}
 
-    // Calculate expected full frame size
-    let full_frame_size = (gif.width as usize) * (gif.height as usize) * 4;
-
-    // Check if any frame needs normalization
-    let needs_normalization = gif.frames.iter().any(|f| f.data.len() < full_frame_size);
-
-    if !needs_normalization {
-        return Ok(());
-    }
+/// Color space in which pixels are matched to their nearest palette color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Manhattan distance between sRGB values (fastest)
+    #[default]
+    Rgb,
+    /// Euclidean distance in Oklab, which follows perceived difference and
+    /// avoids banding in skin tones and blues
+    Oklab,
+}
 
-    println!("      Normalizing frames to full dimensions...");
+/// Metric used to compare input and output in the quality report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QualityMetric {
+    /// Peak signal-to-noise ratio over RGBA channels
+    #[default]
+    Psnr,
+    /// Structural similarity over luma, closer to perceived quality
+    Ssim,
+}
 
-    // Get background color from global palette
-    let bg_color: u8 = if let Some(palette) = &gif.global_palette {
-        if !palette.is_empty() {
-            palette[0][0]  // palette[0] is [u8; 3], we need the first byte (R)
-        } else {
-            255  // Default to white
+impl FromStr for ColorSpace {
+    type Err = anyhow::Error;
+
+    /// Parse a color space name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "rgb" => Ok(Self::Rgb),
+            "oklab" => Ok(Self::Oklab),
+            _ => anyhow::bail!(
+                "Unknown color space '{}', expected rgb or oklab",
+                name.trim()
+            ),
         }
-    } else {
-        255  // Default to white
-    };
+    }
+}
 
-    // Apply normalization to all frames
-    for frame in &mut gif.frames {
-        // Check if this is a partial frame
-        if frame.data.len() < full_frame_size {
-            // Create a full-size canvas filled with background color
-            let mut canvas: Vec<u8> = vec![bg_color; full_frame_size];
-
-            // Calculate offset to center the partial frame
-            let offset_x = ((gif.width - frame.width) / 2) as usize;
-            let offset_y = ((gif.height - frame.height) / 2) as usize;
-
-            let frame_stride = (frame.width as usize) * 4;
-            let canvas_stride = (gif.width as usize) * 4;
-
-            // Copy the partial frame to the center of the canvas
-            for y in 0..(frame.height as usize) {
-                let frame_row_start = y * frame_stride;
-                let canvas_row_start = (offset_y * canvas_stride) + (offset_x * 4);
-
-                // Copy pixel data
-                let row_bytes = frame.width as usize * 4;
-                if frame_row_start + row_bytes <= frame.data.len()
-                    && canvas_row_start + row_bytes <= canvas.len() {
-                    // Iterate over pixels, not bytes
-                    for x in 0..frame.width as usize {
-                        let pixel_offset = x * 4;
-                        let src_alpha = frame.data[frame_row_start + pixel_offset + 3];
-                        if src_alpha > 0 {
-                            // Copy all 4 channels
-                            for c in 0..4 {
-                                canvas[canvas_row_start + pixel_offset + c] = frame.data[frame_row_start + pixel_offset + c];
-                            }
-                        }
-                        // Keep background color if transparent
-                    }
-                }
-            }
+impl FromStr for QualityMetric {
+    type Err = anyhow::Error;
 
-            // Replace frame data with the filled canvas
-            frame.data = canvas;
-            frame.width = gif.width;
-            frame.height = gif.height;
+    /// Parse a metric name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "psnr" => Ok(Self::Psnr),
+            "ssim" => Ok(Self::Ssim),
+            _ => anyhow::bail!("Unknown metric '{}', expected psnr or ssim", name.trim()),
         }
     }
-
-    Ok(())
 }
 
 /// Compress GIF file size by the given percentage
@@ -96,28 +128,210 @@ fn normalize_frames(gif: &mut Gif) -> Result<()> {
 /// compress::run("input.gif", "output.gif", 50).unwrap();
 /// ```
 pub fn run(input: &str, output: &str, target_percent: u8) -> Result<()> {
+    run_with_options(input, output, target_percent, &CompressOptions::default())
+}
+
+/// Compress GIF file size by the given percentage with extra options
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::compress::{self, CompressOptions};
+///
+/// let options = CompressOptions {
+///     optimize_loop: true,
+///     ..Default::default()
+/// };
+/// compress::run_with_options("input.gif", "output.gif", 50, &options).unwrap();
+/// ```
+pub fn run_with_options(
+    input: &str,
+    output: &str,
+    target_percent: u8,
+    options: &CompressOptions,
+) -> Result<()> {
+    compress_file(input, output, target_percent, options).map(|_| ())
+}
+
+/// Quick estimate of a compression result, for interactive previews
+#[derive(Debug, Clone)]
+pub struct CompressPreview {
+    /// Estimated size of the fully compressed GIF in bytes
+    pub estimated_size: u64,
+    /// Number of frames actually compressed for the estimate
+    pub sampled_frames: usize,
+    /// Middle frame of the compressed sample, encoded as PNG
+    pub preview_png: Vec<u8>,
+}
+
+/// Estimate the result of [`run`] by compressing only a few frames
+///
+/// Up to `sample_frames` evenly spaced frames are composited, compressed on
+/// their own and the achieved ratio is applied to the full file size. This is
+/// much faster than a full run, at the cost of accuracy for GIFs whose frames
+/// compress very differently.
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::compress;
+///
+/// let preview = compress::preview("input.gif", 50, 5).unwrap();
+/// println!("About {} bytes", preview.estimated_size);
+/// ```
+pub fn preview(input: &str, target_percent: u8, sample_frames: usize) -> Result<CompressPreview> {
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+    let original_size = fs::metadata(input)?.len();
+
+    // Sampled frames are shown out of sequence, so each needs its full content
+    gif.normalize()?;
+    let indices = sample_indices(gif.frames.len(), sample_frames);
+    let sample = gif.subsample_frames(&indices)?;
+
+    let dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let sample_path = dir.path().join("sample.gif");
+    let sample_path = sample_path.to_string_lossy();
+    let compressed_path = dir.path().join("compressed.gif");
+    let compressed_path = compressed_path.to_string_lossy();
+
+    sample
+        .to_file(&sample_path)
+        .context("Failed to write sample GIF")?;
+    run(&sample_path, &compressed_path, target_percent)?;
+
+    let estimated_size = estimate_size(
+        original_size,
+        fs::metadata(&*sample_path)?.len(),
+        fs::metadata(&*compressed_path)?.len(),
+    );
+
+    // Render the middle frame of the compressed sample
+    let mut compressed = Gif::from_file(&compressed_path).context("Failed to load sample GIF")?;
+    compressed.normalize()?;
+    let mut middle = compressed.frames.swap_remove(compressed.frames.len() / 2);
+    middle.unpremultiply();
+    let mut preview_png = Vec::new();
+    middle
+        .to_dynamic_image()
+        .write_to(
+            &mut std::io::Cursor::new(&mut preview_png),
+            image::ImageOutputFormat::Png,
+        )
+        .context("Failed to encode preview PNG")?;
+
+    Ok(CompressPreview {
+        estimated_size,
+        sampled_frames: indices.len(),
+        preview_png,
+    })
+}
+
+/// Up to `count` (at least one) evenly spaced frame indices out of `len`
+fn sample_indices(len: usize, count: usize) -> Vec<usize> {
+    let count = count.clamp(1, len.max(1));
+    (0..count).map(|i| i * len / count).collect()
+}
+
+/// Scale the full file size by the ratio the sample achieved
+fn estimate_size(original_size: u64, sample_size: u64, compressed_sample_size: u64) -> u64 {
+    if sample_size == 0 {
+        return original_size;
+    }
+
+    (original_size as f64 * compressed_sample_size as f64 / sample_size as f64).round() as u64
+}
+
+/// Compress `input` into `output`, returning the number of encoding passes
+fn compress_file(
+    input: &str,
+    output: &str,
+    target_percent: u8,
+    options: &CompressOptions,
+) -> Result<usize> {
     // Validate percentage
     if target_percent == 0 || target_percent > 99 {
         anyhow::bail!("Compression percentage must be between 1 and 99");
     }
+    if !(1..=9).contains(&options.effort) {
+        anyhow::bail!("Effort must be between 1 and 9");
+    }
+    if options.max_frames == Some(0) {
+        anyhow::bail!("Maximum frame count must be at least 1");
+    }
+    if options
+        .palette_bits
+        .is_some_and(|bits| !(1..=8).contains(&bits))
+    {
+        anyhow::bail!("Palette bits must be between 1 and 8");
+    }
+    if options.preserve_palette
+        && (options.palette_bits.is_some() || options.color_space != ColorSpace::default())
+    {
+        anyhow::bail!(
+            "Preserving the palette cannot be combined with palette bits or a color space"
+        );
+    }
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Compression target: {}%", target_percent);
+    log::info!("   Effort: {}", options.effort);
 
-    println!("   Input file: {}", input);
-    println!("   Compression target: {}%", target_percent);
+    let mut timer = StageTimer::new();
 
     // Load the input GIF
-    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    let mut gif = timer
+        .time("decode", || Gif::from_file(input))
+        .context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
 
     // Get original file size
     let original_size = fs::metadata(input)?.len();
     let target_size = (original_size * target_percent as u64) / 100;
 
-    println!("   Original size: {} bytes", original_size);
-    println!("   Target size: {} bytes", target_size);
+    log::info!("   Original size: {} bytes", original_size);
+    log::info!("   Target size: {} bytes", target_size);
+
+    if options.optimize_loop {
+        let removed = gif.trim_duplicate_boundary();
+        log::info!(
+            "   Loop optimization removed {} duplicate frame(s)",
+            removed
+        );
+    }
+
+    if let Some(max_frames) = options.max_frames {
+        if gif.frames.len() > max_frames {
+            // Kept frames must not depend on the dropped ones
+            gif.normalize()?;
+            let removed = gif.limit_frames(max_frames);
+            log::info!("   Frame limit removed {} frame(s)", removed);
+        }
+    }
+
+    // Flat-color graphics keep their exact palette instead of being re-quantized
+    if options.preserve_palette {
+        let colors = gif.color_count();
+        if colors <= 256 {
+            log::info!(
+                "   Preserving the original {} colors (lossless reductions only)",
+                colors
+            );
+            timer.time("encode", || {
+                optimize::write_lossless(&mut gif, input, output, true)
+            })?;
+            timer.report(gif.frames.len());
+            report_result(input, output, original_size, options)?;
+            return Ok(1);
+        }
+        log::info!(
+            "   Input has {} colors, too many to preserve its palette",
+            colors
+        );
+    }
 
     // Determine compression strategy based on target
     // IMPORTANT: Use 256 colors for ALL targets to prevent color shift (色差)
     // Only use lossy compression and other methods to reduce size
-    let (_skip_dedup, initial_colors, lossy_quality, apply_steps, skip_normalize) = if target_percent >= 90 {
+    let (_skip_dedup, initial_colors, lossy_quality, _apply_steps, skip_normalize) = if target_percent >= 90 {
         // Maximum quality - skip normalization entirely
         (true, 256, 100, false, true)
     } else if target_percent >= 80 {
@@ -149,158 +363,201 @@ pub fn run(input: &str, output: &str, target_percent: u8) -> Result<()> {
     // Normalize frames to full dimensions BEFORE compression
     // For high quality targets, skip normalization to preserve original quality
     if skip_normalize {
-        println!("   Skipping frame normalization to preserve quality");
+        log::info!("   Skipping frame normalization to preserve quality");
     } else {
-        normalize_frames(&mut gif)?;
+        gif.normalize()?;
     }
 
-    // Apply iterative compression strategy
-    let temp_path = format!("{}.temp", output);
-
-    let mut final_step_reached = false;
-
-    for step_num in 0..10 {
-        println!("   Applying compression step {}...", step_num + 1);
+    // Round the palette down to the requested power of two up front
+    if let Some(bits) = options.palette_bits {
+        log::info!("   Palette: {} colors ({} bits)", 1 << bits, bits);
+        reduce_colors(&mut gif, 1 << bits, options.color_space)?;
+    }
+    let encode_options = EncodeOptions {
+        palette_bits: options.palette_bits,
+        ..EncodeOptions::default()
+    };
 
-        // Apply the appropriate compression step based on target
-        match step_num {
-            0 => {
-                println!("      Frame deduplication disabled to preserve animation");
-            }
-            1 => {
-                if initial_colors < 256 {
-                    reduce_colors(&mut gif, initial_colors)?;
-                } else {
-                    println!("      Skipping color reduction (already optimal)");
+    // Effort decides how many steps and quality refinements may run
+    let max_steps = (2 + options.effort as usize).min(5);
+    let refinements = options.effort.saturating_sub(DEFAULT_EFFORT);
+    let mut passes = 0;
+
+    // Apply iterative compression strategy, writing next to the output so the
+    // final result replaces it atomically
+    let frame_count = gif.frames.len();
+    let compress = |temp_path: &str| -> Result<()> {
+        let mut final_step_reached = false;
+        // Lowest lossy quality applied so far
+        let mut quality = lossy_quality;
+
+        for step_num in 0..max_steps {
+            log::info!("   Applying compression step {}...", step_num + 1);
+
+            // Apply the appropriate compression step based on target
+            match step_num {
+                0 => {
+                    log::info!("      Frame deduplication disabled to preserve animation");
                 }
-            }
-            2 => {
-                if lossy_quality < 100 {
-                    apply_lossy_compression(&mut gif, lossy_quality)?;
-                } else {
-                    println!("      Skipping lossy compression (lossless mode)");
-                }
-            }
-            3 => {
-                // Additional color reduction based on target
-                // IMPORTANT: Keep 256 colors for all targets to avoid color shift
-                let next_colors = if target_percent >= 70 {
-                    256 // Keep max colors for 70%+
-                } else if target_percent >= 60 {
-                    256 // Keep max colors for 60%+ to avoid color shift
-                } else if target_percent >= 50 {
-                    256 // Keep max colors for 50%+ to avoid color shift
-                } else if target_percent >= 40 {
-                    256 // Keep max colors for 40%+ to avoid color shift
-                } else if target_percent >= 30 {
-                    256 // Keep max colors for 30%+ to avoid color shift
-                } else if target_percent >= 20 {
-                    256 // Keep max colors for 20%+ to avoid color shift
-                } else {
-                    256 // Keep max colors for 10% to avoid color shift
-                };
-
-                if next_colors < initial_colors {
-                    reduce_colors(&mut gif, next_colors)?;
-                } else {
-                    println!("      Skipping color reduction (preserving original colors)");
+                1 => {
+                    if initial_colors < 256 {
+                        reduce_colors(&mut gif, initial_colors, options.color_space)?;
+                    } else {
+                        log::info!("      Skipping color reduction (already optimal)");
+                    }
                 }
-            }
-            4 => {
-                // Additional lossy compression based on target
-                if target_percent < 90 && lossy_quality > 80 {
-                    let additional_quality = if target_percent >= 80 {
-                        lossy_quality // Keep same for 80%+
-                    } else if target_percent >= 70 {
-                        lossy_quality.saturating_sub(2)
-                    } else if target_percent >= 60 {
-                        lossy_quality.saturating_sub(4)
+                2 => {
+                    if lossy_quality < 100 {
+                        apply_lossy_compression(&mut gif, lossy_quality)?;
                     } else {
-                        lossy_quality.saturating_sub(6)
-                    };
+                        log::info!("      Skipping lossy compression (lossless mode)");
+                    }
+                }
+                3 => {
+                    // Additional color reduction based on target
+                    // IMPORTANT: Keep 256 colors for all targets to avoid color shift
+                    let next_colors = 256;
 
-                    if additional_quality < lossy_quality {
-                        apply_lossy_compression(&mut gif, additional_quality)?;
+                    if next_colors < initial_colors {
+                        reduce_colors(&mut gif, next_colors, options.color_space)?;
+                    } else {
+                        log::info!("      Skipping color reduction (preserving original colors)");
                     }
                 }
+                4 => {
+                    // Additional lossy compression based on target
+                    if target_percent < 90 && lossy_quality > 80 {
+                        let additional_quality = if target_percent >= 80 {
+                            lossy_quality // Keep same for 80%+
+                        } else if target_percent >= 70 {
+                            lossy_quality.saturating_sub(2)
+                        } else if target_percent >= 60 {
+                            lossy_quality.saturating_sub(4)
+                        } else {
+                            lossy_quality.saturating_sub(6)
+                        };
+
+                        if additional_quality < lossy_quality {
+                            apply_lossy_compression(&mut gif, additional_quality)?;
+                            quality = additional_quality;
+                        }
+                    }
+                }
+                _ => break,
             }
-            _ => break,
-        }
-
-        // Save to temp file and check size
-        gif.to_file(&temp_path)
-            .context("Failed to write temporary GIF")?;
 
-        let current_size = fs::metadata(&temp_path)?.len();
-        let current_percent = (current_size as f64 / original_size as f64) * 100.0;
+            // Save to temp file and check size
+            gif.to_file_with_options(temp_path, encode_options)
+                .context("Failed to write temporary GIF")?;
+            passes += 1;
+
+            let current_size = fs::metadata(temp_path)?.len();
+            let current_percent = (current_size as f64 / original_size as f64) * 100.0;
+
+            log::info!(
+                "   Current size after step {}: {} bytes ({:.1}%)",
+                step_num + 1,
+                current_size,
+                current_percent
+            );
+
+            // Check if we've reached or exceeded the target
+            if current_size <= target_size {
+                // For very low targets, stop early
+                if target_percent < 15 {
+                    log::info!("   Target size reached!");
+                    final_step_reached = true;
+                    break;
+                }
+            }
 
-        println!(
-            "   Current size after step {}: {} bytes ({:.1}%)",
-            step_num + 1,
-            current_size,
-            current_percent
-        );
+            // For higher quality targets (90%+), stop if we're close to original size
+            if target_percent >= 90 {
+                // Stop if within 10% of target or after step 3
+                if current_percent <= target_percent as f64 + 10.0 || step_num >= 3 {
+                    log::info!("   Close to target, stopping for quality");
+                    final_step_reached = true;
+                    break;
+                }
+            } else if target_percent >= 70 {
+                // Continue compressing to apply quality settings
+                if current_percent <= target_percent as f64 + 15.0 && step_num >= 3 {
+                    log::info!("   Close to target, stopping for quality");
+                    final_step_reached = true;
+                    break;
+                }
+            } else if target_percent >= 40 {
+                if current_percent >= target_percent as f64 - 5.0
+                    && current_percent <= target_percent as f64 + 10.0
+                {
+                    log::info!("   Close to target, stopping for quality");
+                    final_step_reached = true;
+                    break;
+                }
+            } else {
+                // For low quality targets, stop when close
+                if current_percent <= target_percent as f64 + 5.0 {
+                    log::info!("   Close to target, stopping for quality");
+                    final_step_reached = true;
+                    break;
+                }
+            }
 
-        // Check if we've reached or exceeded the target
-        if current_size <= target_size {
-            // For very low targets, stop early
-            if target_percent < 15 {
-                println!("   Target size reached!");
-                final_step_reached = true;
+            // If we're getting too small (less than 50% of target), stop
+            if current_size < target_size / 2 && target_percent > 20 {
+                log::info!("   Size too small, stopping compression");
                 break;
             }
         }
 
-        // For higher quality targets (90%+), stop if we're close to original size
-        if target_percent >= 90 {
-            // Stop if within 10% of target or after step 3
-            if current_percent <= target_percent as f64 + 10.0 || step_num >= 3 {
-                println!("   Close to target, stopping for quality");
-                final_step_reached = true;
-                break;
-            }
-        } else if target_percent >= 70 {
-            // Continue compressing to apply quality settings
-            if current_percent <= target_percent as f64 + 15.0 && step_num >= 3 {
-                println!("   Close to target, stopping for quality");
-                final_step_reached = true;
+        // Higher effort walks further down the quality ladder before giving up
+        for rung in 1..=refinements {
+            if final_step_reached || fs::metadata(temp_path)?.len() <= target_size {
                 break;
             }
-        } else if target_percent >= 40 {
-            if current_percent >= target_percent as f64 - 5.0 && current_percent <= target_percent as f64 + 10.0 {
-                println!("   Close to target, stopping for quality");
-                final_step_reached = true;
-                break;
-            }
-        } else {
-            // For low quality targets, stop when close
-            if current_percent <= target_percent as f64 + 5.0 {
-                println!("   Close to target, stopping for quality");
-                final_step_reached = true;
+            quality = quality.saturating_sub(4);
+            if quality <= 70 {
                 break;
             }
+
+            log::info!("   Applying refinement {} (quality: {})...", rung, quality);
+            apply_lossy_compression(&mut gif, quality)?;
+            gif.to_file_with_options(temp_path, encode_options)
+                .context("Failed to write temporary GIF")?;
+            passes += 1;
         }
 
-        // If we're getting too small (less than 50% of target), stop
-        if current_size < target_size / 2 && target_percent > 20 {
-            println!("   Size too small, stopping compression");
-            break;
+        // If no steps achieved the target, try one more aggressive step
+        // But skip this for high quality targets (70%+) to preserve quality
+        // IMPORTANT: Use stronger lossy compression instead of color reduction to avoid color shift
+        if !final_step_reached
+            && fs::metadata(temp_path)?.len() > target_size
+            && target_percent < 70
+        {
+            log::info!("   Applying final aggressive compression...");
+            // Use stronger lossy compression instead of reducing colors
+            apply_lossy_compression(&mut gif, 70)?;
+            gif.to_file_with_options(temp_path, encode_options)?;
+            passes += 1;
         }
-    }
 
-    // If no steps achieved the target, try one more aggressive step
-    // But skip this for high quality targets (70%+) to preserve quality
-    // IMPORTANT: Use stronger lossy compression instead of color reduction to avoid color shift
-    if !final_step_reached && fs::metadata(&temp_path)?.len() > target_size && target_percent < 70 {
-        println!("   Applying final aggressive compression...");
-        // Use stronger lossy compression instead of reducing colors
-        apply_lossy_compression(&mut gif, 70)?;
-        gif.to_file(&temp_path)?;
-    }
+        Ok(())
+    };
+    timer.time("compress", || io::atomic_write(output, compress))?;
+    timer.report(frame_count);
+
+    report_result(input, output, original_size, options)?;
 
-    // Rename temp file to output
-    fs::rename(&temp_path, output).context("Failed to rename temporary file")?;
+    Ok(passes)
+}
 
+/// Log the final size and, if requested, the quality report
+fn report_result(
+    input: &str,
+    output: &str,
+    original_size: u64,
+    options: &CompressOptions,
+) -> Result<()> {
     let final_size = fs::metadata(output)?.len();
     let compression_ratio = if final_size < original_size {
         ((original_size - final_size) as f64 / original_size as f64) * 100.0
@@ -308,146 +565,189 @@ pub fn run(input: &str, output: &str, target_percent: u8) -> Result<()> {
         -((final_size - original_size) as f64 / original_size as f64) * 100.0
     };
 
-    println!("   Final size: {} bytes", final_size);
+    log::info!("   Final size: {} bytes", final_size);
     if compression_ratio >= 0.0 {
-        println!("   Compression achieved: {:.1}%", compression_ratio);
+        log::info!("   Compression achieved: {:.1}%", compression_ratio);
     } else {
-        println!("   Size increased: {:.1}%", -compression_ratio);
+        log::info!("   Size increased: {:.1}%", -compression_ratio);
+    }
+
+    if options.report || options.report_per_frame.is_some() {
+        print_quality_report(input, output, options)?;
     }
 
     Ok(())
 }
 
-/// Calculate the difference between two frames
+/// Compare the composited frames of input and output and print the average
+/// score and, if requested, the per-frame PSNR table
 ///
-/// Returns a value from 0-255 representing the average pixel difference
-fn calculate_frame_difference(frame1: &Frame, frame2: &Frame) -> u8 {
-    if frame1.width != frame2.width || frame1.height != frame2.height {
-        return 255; // Maximum difference if dimensions don't match
-    }
-
-    if frame1.data.len() != frame2.data.len() {
-        return 255;
+/// The average needs matching frame counts and is skipped otherwise. The
+/// per-frame table compares each output frame with the input frame on screen
+/// when it starts, so it also works when frames were merged or dropped.
+fn print_quality_report(input: &str, output: &str, options: &CompressOptions) -> Result<()> {
+    let mut original = Gif::from_file(input).context("Failed to load input GIF")?;
+    let mut compressed = Gif::from_file(output).context("Failed to load output GIF")?;
+
+    original.normalize()?;
+    compressed.normalize()?;
+
+    log::info!("   Quality report:");
+    if options.report {
+        print_average_score(&original, &compressed, options.metric);
     }
 
-    let mut total_diff = 0u64;
-    let pixel_count = (frame1.width as u64) * (frame1.height as u64);
-
-    // Compare RGBA pixels
-    for (p1, p2) in frame1.data.chunks(4).zip(frame2.data.chunks(4)) {
-        // Calculate per-channel difference
-        let r_diff = (p1[0] as i16 - p2[0] as i16).unsigned_abs() as u64;
-        let g_diff = (p1[1] as i16 - p2[1] as i16).unsigned_abs() as u64;
-        let b_diff = (p1[2] as i16 - p2[2] as i16).unsigned_abs() as u64;
-        let a_diff = (p1[3] as i16 - p2[3] as i16).unsigned_abs() as u64;
-
-        // Average difference across channels
-        total_diff += (r_diff + g_diff + b_diff + a_diff) / 4;
-    }
+    if let Some(worst_count) = options.report_per_frame {
+        let scores = utils::per_frame_psnr(&original, &compressed);
+        let worst = utils::worst_frames(&scores, worst_count);
+
+        log::info!("      Per-frame PSNR:");
+        log::info!("      {:>6} {:>6} {:>10}", "Frame", "Input", "PSNR (dB)");
+        for score in &scores {
+            let marker = if worst.iter().any(|w| w.index == score.index) {
+                "  <- worst"
+            } else {
+                ""
+            };
+            log::info!(
+                "      {:>6} {:>6} {:>10}{}",
+                score.index,
+                score.source,
+                format_psnr(score.psnr),
+                marker
+            );
+        }
 
-    if pixel_count == 0 {
-        return 0;
+        if worst.is_empty() {
+            log::info!("      Worst frames: none (all frames lossless)");
+        } else {
+            let worst: Vec<String> = worst
+                .iter()
+                .map(|w| format!("{} ({} dB)", w.index, format_psnr(w.psnr)))
+                .collect();
+            log::info!("      Worst frames: {}", worst.join(", "));
+        }
     }
 
-    (total_diff / pixel_count) as u8
+    Ok(())
 }
 
-/// Deduplicate frames that are similar to each other
-///
-/// Frames with difference less than threshold are merged
-fn deduplicate_frames(gif: &mut Gif, threshold: u8) -> Result<()> {
-    if gif.frames.len() <= 1 {
-        return Ok(());
+/// Print the average score over frame pairs, or a note when the frame counts
+/// differ and frames cannot be paired up
+fn print_average_score(original: &Gif, compressed: &Gif, metric: QualityMetric) {
+    if original.frames.len() != compressed.frames.len() {
+        log::info!(
+            "      Skipped: frame counts differ ({} vs {}), frames cannot be aligned",
+            original.frames.len(),
+            compressed.frames.len()
+        );
+        return;
     }
 
-    let mut unique_frames = Vec::new();
-    let mut last_frame = &gif.frames[0];
-
-    unique_frames.push(last_frame.clone());
-
-    for current_frame in &gif.frames[1..] {
-        let diff = calculate_frame_difference(last_frame, current_frame);
-
-        if diff < threshold {
-            // Merge: add the delay to the last unique frame
-            if let Some(last) = unique_frames.last_mut() {
-                last.delay += current_frame.delay;
+    match metric {
+        QualityMetric::Psnr => {
+            let mse = utils::mean_squared_error(original, compressed);
+            let psnr = utils::psnr(mse);
+            if psnr.is_infinite() {
+                log::info!("      Average PSNR: inf (lossless)");
+            } else {
+                log::info!("      Average PSNR: {:.2} dB (MSE {:.2})", psnr, mse);
             }
-        } else {
-            // Keep the frame
-            unique_frames.push(current_frame.clone());
-            last_frame = current_frame;
+        }
+        QualityMetric::Ssim => {
+            let total: f64 = original
+                .frames
+                .iter()
+                .zip(&compressed.frames)
+                .map(|(a, b)| {
+                    utils::ssim(
+                        &a.to_dynamic_image().to_luma8(),
+                        &b.to_dynamic_image().to_luma8(),
+                    )
+                })
+                .sum();
+            let ssim = total / original.frames.len().max(1) as f64;
+            log::info!("      Average SSIM: {:.4}", ssim);
         }
     }
-
-    let original_count = gif.frames.len();
-    gif.frames = unique_frames;
-
-    println!(
-        "      Deduplicated: {} -> {} frames",
-        original_count,
-        gif.frames.len()
-    );
-
-    Ok(())
 }
 
-/// Reduce the color palette of the GIF
-///
-/// Uses median cut algorithm to find optimal color palette
-fn reduce_colors(gif: &mut Gif, max_colors: usize) -> Result<()> {
-    if max_colors >= 256 {
-        return Ok(());
+/// Format a PSNR value with two decimals, or `inf` for identical frames
+fn format_psnr(psnr: f64) -> String {
+    if psnr.is_infinite() {
+        "inf".to_string()
+    } else {
+        format!("{:.2}", psnr)
     }
+}
 
-    println!("      Reducing colors to {}", max_colors);
-
-    // Collect all unique colors from all frames
-    let mut all_colors = Vec::new();
+/// Build an RGB palette of at most `max_colors` entries for the GIF's visible pixels
+///
+/// Returns `None` when no frame has a visible pixel. The result is
+/// deterministic for a given input.
+fn quantize_palette(gif: &Gif, max_colors: usize) -> Option<Vec<u8>> {
+    // Collect all visible pixels as opaque RGBA, the layout NeuQuant expects
+    let mut pixels = Vec::new();
 
     for frame in &gif.frames {
         for pixel in frame.data.chunks(4) {
             // Only add opaque or semi-transparent pixels
             if pixel[3] > 0 {
-                all_colors.push([pixel[0], pixel[1], pixel[2]]);
+                pixels.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
             }
         }
     }
 
-    if all_colors.is_empty() {
+    if pixels.is_empty() {
+        return None;
+    }
+
+    let quantizer = color_quant::NeuQuant::new(NEUQUANT_SAMPLE_FACTOR, max_colors, &pixels);
+    Some(quantizer.color_map_rgb())
+}
+
+/// Reduce the color palette of the GIF
+///
+/// Uses NeuQuant to find an optimized color palette, then maps every visible
+/// pixel to its nearest palette color in `color_space`
+pub(crate) fn reduce_colors(
+    gif: &mut Gif,
+    max_colors: usize,
+    color_space: ColorSpace,
+) -> Result<()> {
+    if max_colors >= 256 {
         return Ok(());
     }
 
-    // Flatten color data for color_quant
-    let flat_colors: Vec<u8> = all_colors.iter().flatten().copied().collect();
+    log::info!(
+        "      Reducing colors to {} ({:?})",
+        max_colors,
+        color_space
+    );
 
-    // Use color_quant to create optimized palette
-    let quantizer = color_quant::NeuQuant::new(10, max_colors, &flat_colors);
-    let palette = quantizer.color_map_rgb();
+    let palette = match quantize_palette(gif, max_colors) {
+        Some(palette) => canonical_palette(&palette),
+        None => return Ok(()),
+    };
+    let oklab_palette: Vec<[f64; 3]> = match color_space {
+        ColorSpace::Rgb => Vec::new(),
+        ColorSpace::Oklab => palette
+            .iter()
+            .map(|&rgb| utils::srgb_to_oklab(rgb))
+            .collect(),
+    };
 
-    // Apply the palette to all frames
+    // Apply the palette to all frames; frames reuse few colors, so cache lookups
+    let mut cache = std::collections::HashMap::new();
     for frame in &mut gif.frames {
         for pixel in frame.data.chunks_exact_mut(4) {
             if pixel[3] > 0 {
-                let r = pixel[0];
-                let g = pixel[1];
-                let b = pixel[2];
-
-                // Get the closest color from the palette
-                let fallback = [r, g, b];
-                let closest = palette
-                    .chunks(3)
-                    .min_by_key(|color| {
-                        let dr = (color.first().copied().unwrap_or(0) as i32 - r as i32).abs();
-                        let dg = (color.get(1).copied().unwrap_or(0) as i32 - g as i32).abs();
-                        let db = (color.get(2).copied().unwrap_or(0) as i32 - b as i32).abs();
-                        dr + dg + db
-                    })
-                    .unwrap_or(&fallback);
-
-                pixel[0] = closest.first().copied().unwrap_or(r);
-                pixel[1] = closest.get(1).copied().unwrap_or(g);
-                pixel[2] = closest.get(2).copied().unwrap_or(b);
+                let rgb = [pixel[0], pixel[1], pixel[2]];
+                let closest = *cache.entry(rgb).or_insert_with(|| match color_space {
+                    ColorSpace::Rgb => nearest_rgb(&palette, rgb),
+                    ColorSpace::Oklab => palette[nearest_oklab(&oklab_palette, rgb)],
+                });
+                pixel[..3].copy_from_slice(&closest);
             }
         }
     }
@@ -455,6 +755,50 @@ fn reduce_colors(gif: &mut Gif, max_colors: usize) -> Result<()> {
     Ok(())
 }
 
+/// Sort a flat RGB palette and drop duplicate entries
+///
+/// Nearest-color ties go to the lowest index, so a canonical order makes
+/// the mapping independent of the order NeuQuant emits its colors in.
+fn canonical_palette(flat: &[u8]) -> Vec<[u8; 3]> {
+    let mut palette: Vec<[u8; 3]> = flat
+        .chunks_exact(3)
+        .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+        .collect();
+    palette.sort_unstable();
+    palette.dedup();
+    palette
+}
+
+/// Palette color with the smallest Manhattan distance to `rgb` in sRGB
+///
+/// Ties go to the color with the lowest palette index.
+fn nearest_rgb(palette: &[[u8; 3]], rgb: [u8; 3]) -> [u8; 3] {
+    palette
+        .iter()
+        .copied()
+        .min_by_key(|color| {
+            color
+                .iter()
+                .zip(&rgb)
+                .map(|(&a, &b)| (a as i32 - b as i32).abs())
+                .sum::<i32>()
+        })
+        .unwrap_or(rgb)
+}
+
+/// Index of the palette color closest to `rgb` in Oklab
+///
+/// Ties go to the lowest index.
+fn nearest_oklab(oklab_palette: &[[f64; 3]], rgb: [u8; 3]) -> usize {
+    let target = utils::srgb_to_oklab(rgb);
+    oklab_palette
+        .iter()
+        .map(|&color| utils::oklab_distance_squared(color, target))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(index, _)| index)
+}
+
 /// Apply lossy compression by simplifying similar colors
 ///
 /// quality: 0-100, where 100 is lossless
@@ -463,7 +807,7 @@ fn apply_lossy_compression(gif: &mut Gif, quality: u8) -> Result<()> {
         return Ok(());
     }
 
-    println!("      Applying lossy compression (quality: {})", quality);
+    log::info!("      Applying lossy compression (quality: {})", quality);
 
     // Calculate the quantization factor
     // Lower quality = larger factor = more aggressive compression
@@ -472,7 +816,7 @@ fn apply_lossy_compression(gif: &mut Gif, quality: u8) -> Result<()> {
         return Ok(());
     }
 
-    for frame in &mut gif.frames {
+    gif.par_map_frames(|frame| {
         for pixel in frame.data.chunks_exact_mut(4) {
             if pixel[3] > 0 {
                 // Quantize each color channel
@@ -481,7 +825,7 @@ fn apply_lossy_compression(gif: &mut Gif, quality: u8) -> Result<()> {
                 pixel[2] = (pixel[2] / factor) * factor;
             }
         }
-    }
+    });
 
     Ok(())
 }
@@ -489,6 +833,7 @@ fn apply_lossy_compression(gif: &mut Gif, quality: u8) -> Result<()> {
 /// Reduce the number of frames in the GIF
 ///
 /// Keeps the specified percentage of frames
+#[allow(dead_code)]
 fn reduce_frame_count(gif: &mut Gif, percentage: f64) -> Result<()> {
     if percentage >= 1.0 || percentage <= 0.0 {
         return Ok(());
@@ -500,7 +845,7 @@ fn reduce_frame_count(gif: &mut Gif, percentage: f64) -> Result<()> {
         return Ok(());
     }
 
-    println!(
+    log::info!(
         "      Reducing frames: {} -> {}",
         gif.frames.len(),
         target_count
@@ -521,21 +866,9 @@ fn reduce_frame_count(gif: &mut Gif, percentage: f64) -> Result<()> {
     }
 
     // Adjust delays to maintain total duration
-    let original_duration: u32 = gif.frames.iter().map(|f| f.delay as u32).sum();
-    let new_duration: u32 = selected_frames.iter().map(|f| f.delay as u32).sum();
-
-    if new_duration > 0 {
-        let ratio = original_duration as f64 / new_duration as f64;
-        for frame in &mut selected_frames {
-            frame.delay = (frame.delay as f64 * ratio) as u16;
-            // Ensure minimum delay
-            if frame.delay == 0 {
-                frame.delay = 1;
-            }
-        }
-    }
-
+    let original_duration = gif.total_duration();
     gif.frames = selected_frames;
+    gif.fit_delays_to_duration(original_duration);
 
     Ok(())
 }
@@ -545,24 +878,184 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_calculate_frame_difference() {
-        use crate::core::Frame;
+    fn test_effort_controls_passes() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("compress_effort.gif");
+        let output = output.to_str().unwrap();
+
+        let passes = |effort| {
+            let options = CompressOptions {
+                effort,
+                ..Default::default()
+            };
+            compress_file(input, output, 10, &options).unwrap()
+        };
+        let quick = passes(1);
+        let exhaustive = passes(9);
+
+        assert!(quick < exhaustive, "{} vs {} passes", quick, exhaustive);
+
+        let options = CompressOptions {
+            effort: 0,
+            ..Default::default()
+        };
+        assert!(run_with_options(input, output, 10, &options).is_err());
+    }
+
+    #[test]
+    fn test_palette_bits() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("compress_palette_bits.gif");
+        let output = output.to_str().unwrap();
+
+        let compressed_size = |palette_bits| {
+            let options = CompressOptions {
+                palette_bits,
+                ..Default::default()
+            };
+            run_with_options(input, output, 90, &options).unwrap();
+            fs::metadata(output).unwrap().len()
+        };
+        let full_size = compressed_size(None);
+        let two_bit_size = compressed_size(Some(2));
+        let two_bit = Gif::from_file(output).unwrap();
+
+        assert!(
+            two_bit.color_count() <= 4,
+            "{} colors",
+            two_bit.color_count()
+        );
+        assert!(
+            two_bit_size < full_size,
+            "{} vs {} bytes",
+            two_bit_size,
+            full_size
+        );
+    }
+
+    #[test]
+    fn test_oklab_picks_perceptually_closer_color() {
+        // Manhattan distance prefers the teal entry (60 vs 80), but it shifts
+        // the hue; the darker blue looks much closer
+        let palette = [[60, 150, 200], [40, 70, 160]];
+        let blue = [60, 90, 200];
+
+        let rgb_pick = nearest_rgb(&palette, blue);
+        let oklab_palette = palette.map(utils::srgb_to_oklab);
+        let oklab_pick = palette[nearest_oklab(&oklab_palette, blue)];
+        assert_eq!(rgb_pick, [60, 150, 200]);
+        assert_eq!(oklab_pick, [40, 70, 160]);
+
+        let perceived = |color| {
+            utils::oklab_distance_squared(utils::srgb_to_oklab(color), utils::srgb_to_oklab(blue))
+        };
+        assert!(perceived(oklab_pick) < perceived(rgb_pick));
+    }
+
+    #[test]
+    fn test_preview_size_estimate() {
+        assert_eq!(sample_indices(10, 3), vec![0, 3, 6]);
+        assert_eq!(sample_indices(2, 5), vec![0, 1]);
+        assert_eq!(sample_indices(4, 0), vec![0]);
+
+        // A sample that halves in size predicts half the original
+        assert_eq!(estimate_size(10_000, 2_000, 1_000), 5_000);
+        assert_eq!(estimate_size(10_000, 0, 0), 10_000);
+
+        let preview = preview("tests/fixtures/colorful.gif", 50, 3).unwrap();
+        assert_eq!(preview.sampled_frames, 3);
+        assert!(preview.estimated_size > 0);
+        assert!(preview.preview_png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_nearest_color_ties_are_deterministic() {
+        // Gray 10 is exactly as far from black as from gray 20, whichever
+        // order the quantizer produced them in
+        let forward = canonical_palette(&[0, 0, 0, 20, 20, 20, 0, 0, 0]);
+        let reversed = canonical_palette(&[20, 20, 20, 0, 0, 0]);
+        assert_eq!(forward, reversed);
+
+        let pixel = [10, 10, 10];
+        for palette in [&forward, &reversed] {
+            assert_eq!(nearest_rgb(palette, pixel), [0, 0, 0]);
+        }
+        // Black is equally far from all three
+        let equidistant = [[0, 20, 0], [20, 0, 0], [0, 0, 20]];
+        let palette = canonical_palette(equidistant.as_flattened());
+        assert_eq!(nearest_rgb(&palette, [0, 0, 0]), [0, 0, 20]);
+    }
+
+    #[test]
+    fn test_preserve_palette_is_lossless() {
+        let input = "tests/fixtures/simple.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("compress_preserve_palette.gif");
+        let output = output.to_str().unwrap();
+
+        let options = CompressOptions {
+            preserve_palette: true,
+            ..Default::default()
+        };
+        run_with_options(input, output, 30, &options).unwrap();
+        let mut compressed = Gif::from_file(output).unwrap();
+
+        let mut original = Gif::from_file(input).unwrap();
+        original.normalize().unwrap();
+        compressed.normalize().unwrap();
+        assert_eq!(compressed.frames.len(), original.frames.len());
+        assert_eq!(utils::mean_squared_error(&original, &compressed), 0.0);
+    }
+
+    #[test]
+    fn test_preserve_palette_rejects_palette_options() {
+        let options = CompressOptions {
+            preserve_palette: true,
+            palette_bits: Some(4),
+            ..Default::default()
+        };
+        let error =
+            run_with_options("tests/fixtures/simple.gif", "unused.gif", 30, &options).unwrap_err();
+        assert!(error.to_string().contains("Preserving the palette"));
+        assert!(!std::path::Path::new("unused.gif").exists());
+    }
+
+    #[test]
+    fn test_max_frames() {
+        let input = "tests/fixtures/high_fps.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("compress_max_frames.gif");
+        let output = output.to_str().unwrap();
+
+        let options = CompressOptions {
+            max_frames: Some(10),
+            ..Default::default()
+        };
+        run_with_options(input, output, 80, &options).unwrap();
+        let compressed = Gif::from_file(output).unwrap();
+
+        let original = Gif::from_file(input).unwrap();
+        assert_eq!(compressed.frames.len(), 10);
+        assert_eq!(compressed.total_duration(), original.total_duration());
+    }
 
-        // Create two identical frames
-        let data = vec![255u8; 10 * 10 * 4];
-        let frame1 = Frame::from_rgba(data.clone(), 10, 10);
-        let frame2 = Frame::from_rgba(data, 10, 10);
+    #[test]
+    fn test_reduce_colors_is_deterministic() {
+        let gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
 
-        let diff = calculate_frame_difference(&frame1, &frame2);
-        assert_eq!(diff, 0);
+        let palette_a = quantize_palette(&gif, 16).unwrap();
+        let palette_b = quantize_palette(&gif, 16).unwrap();
+        assert_eq!(palette_a, palette_b);
 
-        // Create completely different frames
-        let data1 = vec![0u8; 10 * 10 * 4];
-        let data2 = vec![255u8; 10 * 10 * 4];
-        let frame3 = Frame::from_rgba(data1, 10, 10);
-        let frame4 = Frame::from_rgba(data2, 10, 10);
+        let mut first = gif.clone();
+        let mut second = gif.clone();
+        reduce_colors(&mut first, 16, ColorSpace::Rgb).unwrap();
+        reduce_colors(&mut second, 16, ColorSpace::Rgb).unwrap();
 
-        let diff2 = calculate_frame_difference(&frame3, &frame4);
-        assert!(diff2 > 200);
+        for (a, b) in first.frames.iter().zip(&second.frames) {
+            assert_eq!(a.data, b.data);
+        }
     }
 }
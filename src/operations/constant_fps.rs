@@ -0,0 +1,181 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+
+/// Resample a GIF onto a constant frame rate
+///
+/// For each output tick the frame that is on screen at that moment (based on
+/// cumulative delays) is emitted with a constant delay, so the frame count
+/// changes but playback timing becomes uniform.
+///
+/// Delays are whole centiseconds, so most frame rates cannot be hit exactly
+/// (30 fps is 3.33cs per frame). With `preserve_timing` the rounding error is
+/// spread over the frames (e.g. 3, 3, 4, 3, 3, 4) so the total duration
+/// matches the input; without it every frame gets the same rounded delay and
+/// the total can drift.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `fps` - Target frames per second (GIF delays limit this to at most 100)
+/// * `preserve_timing` - Keep the total duration of the input
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::constant_fps;
+///
+/// constant_fps::run("input.gif", "output.gif", 10.0, true).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, fps: f64, preserve_timing: bool) -> Result<()> {
+    // Validate fps
+    if !(fps > 0.0 && fps <= 100.0) {
+        anyhow::bail!("FPS must be greater than 0 and at most 100");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Target FPS: {:.2}", fps);
+    log::info!("   Original frames: {}", gif.frames.len());
+
+    // Frames are picked independently, so each must hold its complete image
+    gif.normalize()?;
+
+    let original_duration = gif.total_duration();
+    resample_to_fps(&mut gif, fps);
+    if preserve_timing {
+        spread_duration(&mut gif, original_duration);
+    }
+
+    log::info!("   Resampled frames: {}", gif.frames.len());
+    log::info!(
+        "   Frame delay: {} ms",
+        gif.frames.first().map_or(0, |f| f.delay) * 10
+    );
+
+    // Save the modified GIF
+    gif.to_file(output).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Replace the GIF's frames with the frames visible at each tick of `fps`
+fn resample_to_fps(gif: &mut Gif, fps: f64) {
+    let total_duration = gif.total_duration() as f64;
+    if gif.frames.is_empty() || total_duration == 0.0 {
+        return;
+    }
+
+    // Exact tick length in centiseconds and the delay actually written
+    let tick = 100.0 / fps;
+    let delay = (tick.round() as u16).max(1);
+
+    // Cumulative end time of each source frame
+    let mut end_times = Vec::with_capacity(gif.frames.len());
+    let mut elapsed = 0.0;
+    for frame in &gif.frames {
+        elapsed += frame.delay as f64;
+        end_times.push(elapsed);
+    }
+
+    let mut resampled = Vec::new();
+    let mut source_index = 0;
+    let mut tick_index = 0u32;
+
+    loop {
+        let time = tick_index as f64 * tick;
+        if time >= total_duration {
+            break;
+        }
+
+        // Advance to the frame on screen at this time
+        while end_times[source_index] <= time {
+            source_index += 1;
+        }
+
+        let mut frame = gif.frames[source_index].clone();
+        frame.delay = delay;
+        resampled.push(frame);
+
+        tick_index += 1;
+    }
+
+    gif.frames = resampled;
+}
+
+/// Set the frame delays so they add up to `total` centiseconds
+///
+/// Each frame ends at its exact share of `total`, rounded, so delays differ
+/// by at most 1cs. Every frame keeps a delay of at least 1.
+fn spread_duration(gif: &mut Gif, total: u32) {
+    let count = gif.frames.len() as u64;
+    let mut previous_end = 0u64;
+
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        let end = ((index as u64 + 1) * total as u64 + count / 2) / count;
+        frame.delay = (end.saturating_sub(previous_end) as u16).max(1);
+        previous_end = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Frame;
+
+    fn variable_delay_gif() -> Gif {
+        let mut gif = Gif::new();
+        for (i, delay) in [2u16, 7, 2, 7, 12].iter().enumerate() {
+            let mut frame = Frame::from_rgba(vec![(i * 40) as u8; 8 * 8 * 4], 8, 8);
+            frame.delay = *delay;
+            gif.add_frame(frame);
+        }
+        gif
+    }
+
+    #[test]
+    fn test_resample_picks_active_frames() {
+        let mut gif = variable_delay_gif();
+        // Frames end at 2, 9, 11, 18, 30 centiseconds; ticks every 10cs at 0, 10, 20
+        resample_to_fps(&mut gif, 10.0);
+
+        let picked: Vec<u8> = gif.frames.iter().map(|f| f.data[0]).collect();
+        assert_eq!(picked, vec![0, 80, 160]);
+    }
+
+    #[test]
+    fn test_constant_fps_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("variable_delay.gif");
+        let output = dir.path().join("constant_fps.gif");
+        let input = input.to_str().unwrap();
+        let output = output.to_str().unwrap();
+
+        variable_delay_gif().to_file(input).unwrap();
+        run(input, output, 10.0, false).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        assert!(!result.frames.is_empty());
+        assert!(result.frames.iter().all(|f| f.delay == 10));
+    }
+
+    #[test]
+    fn test_preserve_timing() {
+        let input = "tests/fixtures/simple.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("constant_fps_timing.gif");
+        let output = output.to_str().unwrap();
+        let original = Gif::from_file(input).unwrap().total_duration();
+
+        // 30 fps is 3.33cs per frame, which rounds to 3
+        run(input, output, 30.0, false).unwrap();
+        let drifted = Gif::from_file(output).unwrap().total_duration();
+        run(input, output, 30.0, true).unwrap();
+        let preserved = Gif::from_file(output).unwrap();
+
+        assert_ne!(drifted, original);
+        assert!(preserved.total_duration().abs_diff(original) <= 1);
+        assert!(preserved.frames.iter().all(|f| (3..=4).contains(&f.delay)));
+    }
+}
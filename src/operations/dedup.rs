@@ -0,0 +1,150 @@
+use crate::core::{calculate_frame_difference, warn_if_tagged, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Merge consecutive near-duplicate frames into one longer frame
+///
+/// Frames are composited first. Each frame is compared with the last kept
+/// frame; if their average difference is below `threshold` it is dropped and
+/// its delay added to the kept frame. The total duration is unchanged.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `threshold` - Average per-pixel difference (0-255) below which frames merge
+/// * `window` - Maximum distance in frames between a frame and the kept frame
+///   it merges into; `usize::MAX` places no limit
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::dedup;
+///
+/// // Merge near-identical frames, but never across more than 4 frames
+/// dedup::run("input.gif", "output.gif", 3, 4).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, threshold: u8, window: usize) -> Result<()> {
+    if window == 0 {
+        anyhow::bail!("Dedup window must be at least 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Threshold: {}", threshold);
+    if window != usize::MAX {
+        log::info!("   Window: {} frames", window);
+    }
+
+    // Compare what is actually on screen
+    gif.normalize()?;
+    deduplicate_frames(&mut gif, threshold, window);
+
+    // Save the deduplicated GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Deduplicate frames that are similar to each other
+///
+/// Frames with difference less than threshold are merged, as long as they
+/// are at most `window` positions after the frame they merge into. Longer
+/// runs start a new kept frame, so slow fades cannot drift into one frame.
+///
+/// Returns the warnings logged for merged frames that carried tags.
+pub(crate) fn deduplicate_frames(gif: &mut Gif, threshold: u8, window: usize) -> Vec<String> {
+    if gif.frames.len() <= 1 {
+        return Vec::new();
+    }
+
+    let original_count = gif.frames.len();
+    let mut unique_frames = Vec::new();
+    let mut last_index = 0;
+    let mut warnings = Vec::new();
+
+    unique_frames.push(gif.frames[0].clone());
+
+    for (index, current_frame) in gif.frames.iter().enumerate().skip(1) {
+        let last_frame = &gif.frames[last_index];
+        let diff = calculate_frame_difference(last_frame, current_frame);
+
+        if diff < threshold && index - last_index <= window {
+            // Merge: add the delay to the last unique frame
+            if let Some(last) = unique_frames.last_mut() {
+                last.delay = last.delay.saturating_add(current_frame.delay);
+            }
+            warnings.extend(warn_if_tagged(current_frame, index, "deduplicating frames"));
+        } else {
+            // Keep the frame
+            unique_frames.push(current_frame.clone());
+            last_index = index;
+        }
+    }
+
+    gif.frames = unique_frames;
+
+    log::info!(
+        "      Deduplicated: {} -> {} frames",
+        original_count,
+        gif.frames.len()
+    );
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Frame;
+
+    fn solid(value: u8) -> Frame {
+        Frame::from_rgba(vec![value; 4 * 4 * 4], 4, 4)
+    }
+
+    #[test]
+    fn test_window_limits_merging() {
+        let mut gif = Gif::builder()
+            .dimensions(4, 4)
+            .add_frame(solid(0))
+            .add_frame(solid(0))
+            .add_frame(solid(0))
+            .add_frame(solid(255))
+            .add_frame(solid(0))
+            .delay_all(10)
+            .build()
+            .unwrap();
+
+        let mut unlimited = gif.clone();
+        deduplicate_frames(&mut unlimited, 1, usize::MAX);
+        let delays: Vec<u16> = unlimited.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![30, 10, 10]);
+
+        // Identical frames separated by a distinct frame are never merged,
+        // and a window of 1 splits the leading run as well
+        deduplicate_frames(&mut gif, 1, 1);
+        let delays: Vec<u16> = gif.frames.iter().map(|f| f.delay).collect();
+        assert_eq!(delays, vec![20, 10, 10, 10]);
+    }
+
+    #[test]
+    fn test_merging_tagged_frame_warns() {
+        let mut gif = Gif::builder()
+            .add_frame(solid(0))
+            .add_frame(solid(0))
+            .add_frame(solid(255))
+            .build()
+            .unwrap();
+        gif.frames[1].tags.push("keyframe".to_string());
+        gif.frames[2].tags.push("ending".to_string());
+
+        let warnings = deduplicate_frames(&mut gif, 1, usize::MAX);
+        assert_eq!(gif.frames.len(), 2);
+        assert_eq!(
+            warnings,
+            vec!["deduplicating frames removed frame 1 tagged keyframe"]
+        );
+    }
+}
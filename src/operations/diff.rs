@@ -0,0 +1,102 @@
+use crate::core::Gif;
+use crate::utils::{self, DiffReport};
+use anyhow::{Context, Result};
+
+/// Compare two GIFs frame by frame, failing if they differ
+///
+/// Both GIFs are composited so frames compare as they are displayed. The
+/// report lists size and frame-count mismatches and every frame whose pixels
+/// differ by more than `tolerance` in any channel. Returns an error when the
+/// GIFs differ, so the command exits non-zero (useful in CI).
+///
+/// # Arguments
+/// * `a` - Path to the first GIF file
+/// * `b` - Path to the second GIF file
+/// * `tolerance` - Largest per-channel difference still counted as equal
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::diff;
+///
+/// diff::run("expected.gif", "actual.gif", 2).unwrap();
+/// ```
+pub fn run(a: &str, b: &str, tolerance: u8) -> Result<()> {
+    // Load both GIFs
+    let mut gif_a = Gif::from_file(a).with_context(|| format!("Failed to load GIF: {}", a))?;
+    let mut gif_b = Gif::from_file(b).with_context(|| format!("Failed to load GIF: {}", b))?;
+    gif_a
+        .ensure_nonempty()
+        .with_context(|| format!("Cannot compare {}", a))?;
+    gif_b
+        .ensure_nonempty()
+        .with_context(|| format!("Cannot compare {}", b))?;
+
+    gif_a.normalize()?;
+    gif_b.normalize()?;
+
+    let report = utils::diff_report(&gif_a, &gif_b);
+    print_report(&report, tolerance);
+
+    if !report.is_within(tolerance) {
+        anyhow::bail!("GIFs differ: {} vs {}", a, b);
+    }
+
+    Ok(())
+}
+
+/// Print the differences found in a report
+fn print_report(report: &DiffReport, tolerance: u8) {
+    println!("GIF Comparison:");
+
+    let (wa, ha) = report.dimensions_a;
+    let (wb, hb) = report.dimensions_b;
+    if report.dimensions_match() {
+        println!("  Dimensions: {}x{} pixels", wa, ha);
+    } else {
+        println!("  Dimensions differ: {}x{} vs {}x{}", wa, ha, wb, hb);
+    }
+
+    if report.frame_counts_match() {
+        println!("  Frames: {}", report.frame_count_a);
+    } else {
+        println!(
+            "  Frame counts differ: {} vs {}",
+            report.frame_count_a, report.frame_count_b
+        );
+    }
+
+    // Pixels are only compared when the canvases match
+    if !report.dimensions_match() {
+        return;
+    }
+
+    let differing: Vec<_> = report.frames_exceeding(tolerance).collect();
+    println!(
+        "  Frames differing (tolerance {}): {}/{}",
+        tolerance,
+        differing.len(),
+        report.frames.len()
+    );
+    for diff in differing {
+        println!(
+            "    Frame {}: max {}, mean {:.2}",
+            diff.index, diff.max, diff.mean
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_files() {
+        assert!(run("tests/fixtures/simple.gif", "tests/fixtures/simple.gif", 0).is_ok());
+        assert!(run(
+            "tests/fixtures/simple.gif",
+            "tests/fixtures/colorful.gif",
+            0
+        )
+        .is_err());
+    }
+}
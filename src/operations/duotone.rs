@@ -0,0 +1,105 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Map every pixel's brightness onto a two-color gradient
+///
+/// Each pixel is converted to luma (Rec. 709 weights on the sRGB values, as
+/// `image`'s grayscale conversion does) and then interpolated between `dark`
+/// (luma 0) and `light` (luma 255). Alpha and timing are unchanged.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `dark` - Color for black
+/// * `light` - Color for white
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::duotone;
+///
+/// // Deep blue shadows, warm yellow highlights
+/// duotone::run("input.gif", "duotone.gif", [20, 30, 90], [250, 220, 120]).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, dark: [u8; 3], light: [u8; 3]) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!(
+        "   Gradient: #{:02x}{:02x}{:02x} -> #{:02x}{:02x}{:02x}",
+        dark[0],
+        dark[1],
+        dark[2],
+        light[0],
+        light[1],
+        light[2]
+    );
+
+    gif.par_map_frames(|frame| duotone_frame(frame, dark, light));
+
+    // Save the recolored GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Recolor every pixel of a frame
+pub(crate) fn duotone_frame(frame: &mut Frame, dark: [u8; 3], light: [u8; 3]) {
+    frame.unpremultiply();
+    for pixel in frame.data.chunks_exact_mut(4) {
+        let luma = luma([pixel[0], pixel[1], pixel[2]]);
+        pixel[..3].copy_from_slice(&gradient(dark, light, luma));
+    }
+}
+
+/// Luma of an sRGB color (0-255)
+fn luma(rgb: [u8; 3]) -> u8 {
+    let [r, g, b] = rgb.map(f64::from);
+    (0.2126 * r + 0.7152 * g + 0.0722 * b).round() as u8
+}
+
+/// Interpolate between `dark` and `light` at position `luma / 255`
+fn gradient(dark: [u8; 3], light: [u8; 3], luma: u8) -> [u8; 3] {
+    let t = luma as f64 / 255.0;
+    std::array::from_fn(|i| {
+        let (d, l) = (dark[i] as f64, light[i] as f64);
+        (d + (l - d) * t).round() as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_black_and_white_map_to_ends() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("duotone_input.gif");
+        let input = input.to_str().unwrap();
+        let output = dir.path().join("duotone.gif");
+        let output = output.to_str().unwrap();
+
+        let solid = |value: u8| {
+            let data = [value, value, value, 255].repeat(4 * 4);
+            Frame::from_rgba(data, 4, 4)
+        };
+        Gif::builder()
+            .add_frame(solid(0))
+            .add_frame(solid(255))
+            .build()
+            .unwrap()
+            .to_file(input)
+            .unwrap();
+
+        let (dark, light) = ([20, 30, 90], [250, 220, 120]);
+        run(input, output, dark, light).unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        assert_eq!(&gif.frames[0].data[..4], &[20, 30, 90, 255]);
+        assert_eq!(&gif.frames[1].data[..4], &[250, 220, 120, 255]);
+        assert_eq!(gradient(dark, light, 128), [135, 125, 105]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// Direction frames are laid out in a filmstrip
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// One row, first frame on the left
+    #[default]
+    Horizontal,
+    /// One column, first frame on top
+    Vertical,
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    /// Parse a direction name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "horizontal" => Ok(Self::Horizontal),
+            "vertical" => Ok(Self::Vertical),
+            _ => anyhow::bail!(
+                "Unknown direction '{}', expected horizontal or vertical",
+                name.trim()
+            ),
+        }
+    }
+}
+
+/// Lay out every composited frame side by side in a single PNG
+///
+/// Frames keep the canvas size, so a horizontal strip is
+/// `frame_count * width` pixels wide and a vertical one
+/// `frame_count * height` pixels tall.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_png` - Path to output PNG file
+/// * `direction` - Row or column layout
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::filmstrip::{self, Direction};
+///
+/// filmstrip::run("input.gif", "strip.png", Direction::Horizontal).unwrap();
+/// ```
+pub fn run(input: &str, output_png: &str, direction: Direction) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let (width, height) = (gif.width as u32, gif.height as u32);
+    let count = gif.frames.len() as u32;
+    let (strip_width, strip_height) = match direction {
+        Direction::Horizontal => (width.checked_mul(count), Some(height)),
+        Direction::Vertical => (Some(width), height.checked_mul(count)),
+    };
+    let (Some(strip_width), Some(strip_height)) = (strip_width, strip_height) else {
+        anyhow::bail!("Filmstrip of {} frames is too large", count);
+    };
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", count);
+    log::info!("   Filmstrip size: {}x{}", strip_width, strip_height);
+
+    // Every cell shows the full image at that point of the animation
+    gif.normalize()?;
+
+    let mut strip = image::RgbaImage::new(strip_width, strip_height);
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        frame.unpremultiply();
+        let offset = index as i64;
+        let (x, y) = match direction {
+            Direction::Horizontal => (offset * width as i64, 0),
+            Direction::Vertical => (0, offset * height as i64),
+        };
+        image::imageops::replace(&mut strip, &frame.to_image_buffer(), x, y);
+    }
+
+    strip
+        .save(output_png)
+        .context("Failed to save output PNG")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_horizontal_filmstrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("filmstrip.png");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/simple.gif", output, Direction::Horizontal).unwrap();
+        let strip = image::open(output).unwrap().to_rgba8();
+
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        let frame_count = gif.frames.len() as u32;
+        assert_eq!(strip.width(), frame_count * gif.width as u32);
+        assert_eq!(strip.height(), gif.height as u32);
+
+        // Red first frame on the left, blue second frame on the right
+        assert_eq!(strip.get_pixel(50, 50).0, [255, 0, 0, 255]);
+        assert_eq!(strip.get_pixel(150, 50).0, [0, 0, 255, 255]);
+    }
+}
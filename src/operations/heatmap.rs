@@ -0,0 +1,145 @@
+use crate::core::{Frame, Gif};
+use anyhow::{Context, Result};
+
+/// Export a GIF showing where each frame differs from the previous one
+///
+/// Every output frame visualizes the per-pixel difference between two
+/// consecutive composited input frames: unchanged pixels are black, small
+/// changes are dark red and large changes brighten towards white. The output
+/// has one frame fewer than the input and keeps the input timing.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_gif` - Path to output GIF file
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::heatmap;
+///
+/// heatmap::run("input.gif", "heatmap.gif").unwrap();
+/// ```
+pub fn run(input: &str, output_gif: &str) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    if gif.frames.len() < 2 {
+        anyhow::bail!(
+            "GIF needs at least 2 frames to compare, found {}",
+            gif.frames.len()
+        );
+    }
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+
+    // Compare what is actually on screen, not the stored partial frames
+    gif.normalize()?;
+
+    let mut heatmap = Gif::new();
+    heatmap.loop_count = gif.loop_count;
+
+    for pair in gif.frames.windows(2) {
+        heatmap.add_frame(difference_frame(&pair[0], &pair[1]));
+    }
+
+    let changed = heatmap
+        .frames
+        .iter()
+        .filter(|f| f.data.chunks_exact(4).any(|p| p[0] > 0))
+        .count();
+    log::info!(
+        "   Transitions with changes: {}/{}",
+        changed,
+        heatmap.frames.len()
+    );
+
+    // Save the heatmap GIF
+    heatmap
+        .to_file(output_gif)
+        .context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Build a heatmap frame from the per-pixel difference of two equal-sized frames
+///
+/// The difference of a pixel is the largest absolute change across its RGBA
+/// channels. Frames of different sizes are compared over their overlap.
+fn difference_frame(previous: &Frame, current: &Frame) -> Frame {
+    let mut data = vec![0u8; current.data.len()];
+    let width = current.width.min(previous.width) as usize;
+    let height = current.height.min(previous.height) as usize;
+
+    for y in 0..height {
+        for x in 0..width {
+            let prev = (y * previous.width as usize + x) * 4;
+            let curr = (y * current.width as usize + x) * 4;
+
+            let diff = (0..4)
+                .map(|c| previous.data[prev + c].abs_diff(current.data[curr + c]))
+                .max()
+                .unwrap_or(0);
+
+            data[curr..curr + 4].copy_from_slice(&heat_color(diff));
+        }
+    }
+
+    // Pixels outside the overlap are opaque black so the heatmap has no transparency
+    for pixel in data.chunks_exact_mut(4) {
+        pixel[3] = 255;
+    }
+
+    let mut frame = Frame::from_rgba(data, current.width, current.height);
+    frame.delay = current.delay;
+    frame
+}
+
+/// Map a difference (0-255) to a black -> red -> white color
+fn heat_color(diff: u8) -> [u8; 4] {
+    let scaled = diff as u32 * 2;
+    let red = scaled.min(255) as u8;
+    let other = scaled.saturating_sub(255).min(255) as u8;
+    [red, other, other, 255]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_color() {
+        assert_eq!(heat_color(0), [0, 0, 0, 255]);
+        assert_eq!(heat_color(100), [200, 0, 0, 255]);
+        assert_eq!(heat_color(255), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_heatmap_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("heatmap.gif");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/duplicates.gif", output).unwrap();
+        let heatmap = Gif::from_file(output).unwrap();
+
+        // Frames 1=2=3 and 4=5, so only the 3->4 transition has changes
+        assert_eq!(heatmap.frames.len(), 4);
+        let brightest: Vec<u8> = heatmap
+            .frames
+            .iter()
+            .map(|f| {
+                f.data
+                    .chunks_exact(4)
+                    .map(|p| p[0].max(p[1]).max(p[2]))
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+
+        assert!(brightest[0] <= 8);
+        assert!(brightest[1] <= 8);
+        assert!(brightest[2] > 8);
+        assert!(brightest[3] <= 8);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+
+/// Height of the rendered histogram in pixels (the width is one column per value)
+pub const HISTOGRAM_HEIGHT: u32 = 200;
+
+/// Per-channel value counts: `[red, green, blue]`, each indexed by value 0-255
+pub type ChannelCounts = [[u64; 256]; 3];
+
+/// Export an RGB histogram of the colors shown across all frames
+///
+/// Renders a 256x200 PNG with the red, green and blue distributions drawn
+/// as overlaid filled curves on black (overlaps add up, so values common to
+/// all channels appear white). Fully transparent pixels are not counted.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_png` - Path to output PNG file
+/// * `json_out` - Optional path to also write the raw counts as JSON
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::histogram;
+///
+/// histogram::run("input.gif", "histogram.png", Some("counts.json")).unwrap();
+/// ```
+pub fn run(input: &str, output_png: &str, json_out: Option<&str>) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+
+    // Count the colors actually on screen, not the stored partial frames
+    gif.normalize()?;
+
+    let counts = channel_counts(&gif);
+    let pixels: u64 = counts[0].iter().sum();
+    log::info!("   Pixels counted: {}", pixels);
+
+    render(&counts)
+        .save(output_png)
+        .context("Failed to save output PNG")?;
+
+    if let Some(json_path) = json_out {
+        std::fs::write(json_path, counts_to_json(&counts))
+            .with_context(|| format!("Failed to write JSON file: {}", json_path))?;
+        log::info!("   Counts written to: {}", json_path);
+    }
+
+    Ok(())
+}
+
+/// Count how often each value occurs in each RGB channel across all frames
+pub fn channel_counts(gif: &Gif) -> ChannelCounts {
+    let mut counts = [[0u64; 256]; 3];
+
+    for frame in &gif.frames {
+        let mut straight = frame.clone();
+        straight.unpremultiply();
+
+        for pixel in straight.data.chunks_exact(4).filter(|p| p[3] > 0) {
+            for (channel, value) in pixel[..3].iter().enumerate() {
+                counts[channel][*value as usize] += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Draw the channel counts as overlaid filled curves, scaled to the largest count
+fn render(counts: &ChannelCounts) -> image::RgbImage {
+    let max = counts.iter().flatten().copied().max().unwrap_or(0).max(1);
+    let mut image = image::RgbImage::new(256, HISTOGRAM_HEIGHT);
+
+    for (channel, channel_counts) in counts.iter().enumerate() {
+        for (value, &count) in channel_counts.iter().enumerate() {
+            let bar = (count as f64 / max as f64 * HISTOGRAM_HEIGHT as f64).round() as u32;
+            for y in (HISTOGRAM_HEIGHT - bar)..HISTOGRAM_HEIGHT {
+                image.get_pixel_mut(value as u32, y)[channel] = 255;
+            }
+        }
+    }
+
+    image
+}
+
+/// Serialize the counts as `{"red": [...], "green": [...], "blue": [...]}`
+fn counts_to_json(counts: &ChannelCounts) -> String {
+    let channels: Vec<String> = ["red", "green", "blue"]
+        .iter()
+        .zip(counts)
+        .map(|(name, values)| {
+            let values: Vec<String> = values.iter().map(u64::to_string).collect();
+            format!("\"{}\": [{}]", name, values.join(", "))
+        })
+        .collect();
+
+    format!("{{{}}}\n", channels.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_colorful() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("histogram.png");
+        let json = dir.path().join("histogram.json");
+        let output = output.to_str().unwrap();
+        let json = json.to_str().unwrap();
+
+        run("tests/fixtures/colorful.gif", output, Some(json)).unwrap();
+        let size = std::fs::metadata(output).unwrap().len();
+        let image = image::open(output).unwrap().to_rgb8();
+        let counts = std::fs::read_to_string(json).unwrap();
+
+        assert!(size > 0);
+        assert_eq!((image.width(), image.height()), (256, HISTOGRAM_HEIGHT));
+        assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+        assert!(counts.starts_with("{\"red\": ["));
+        assert_eq!(counts.matches(',').count(), 3 * 255 + 2);
+    }
+
+    #[test]
+    fn test_channel_counts() {
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        let counts = channel_counts(&gif);
+
+        // Every opaque pixel is counted once per channel
+        let pixels = 2 * 100 * 100;
+        for channel in &counts {
+            assert_eq!(channel.iter().sum::<u64>(), pixels);
+        }
+        // Frame 1 has a 60x60 red square, frame 2 a blue one
+        assert_eq!(counts[0][255], 60 * 60);
+        assert_eq!(counts[2][255], 60 * 60);
+    }
+}
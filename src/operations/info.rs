@@ -1,45 +1,96 @@
 use crate::core::Gif;
+use crate::io;
+use crate::operations::colorblind::{self, CbKind};
+use crate::utils::contrast_ratio;
 use anyhow::{Context, Result};
 use std::fs;
 
+/// Loop seam scores above this get a hint that the loop may visibly jump
+const POOR_SEAM_THRESHOLD: u8 = 10;
+
+/// Consecutive frames less similar than this (0-255) count as unrelated
+const ANIMATION_SIMILARITY: u8 = 200;
+
+/// WCAG AA minimum contrast ratio for normal-size text
+const WCAG_AA_CONTRAST: f64 = 4.5;
+
+/// Colors closer than this (largest channel difference) look the same
+const INDISTINGUISHABLE_DIFFERENCE: u8 = 24;
+
+/// Contrast between the two most prominent colors of a GIF
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccessibilityReport {
+    /// The two most prominent colors, most frequent first
+    pub colors: ([u8; 3], [u8; 3]),
+    /// WCAG contrast ratio between them (1.0 to 21.0)
+    pub contrast_ratio: f64,
+    /// The colors look alike to viewers with red/green color blindness
+    pub red_green_confusable: bool,
+}
+
 /// Display information about a GIF file
 ///
 /// # Arguments
 /// * `input` - Path to the GIF file
+/// * `verbose` - Also show load-time adjustments and per-frame details
+/// * `a11y` - Also report color contrast and color-blindness concerns
 ///
 /// # Example
 /// ```no_run
 /// use gif_toolkit::operations::info;
 ///
-/// info::run("example.gif").unwrap();
+/// info::run("example.gif", false, false).unwrap();
 /// ```
-pub fn run(input: &str) -> Result<()> {
+pub fn run(input: &str, verbose: bool, a11y: bool) -> Result<()> {
     // Load the GIF
     let gif = Gif::from_file(input).context("Failed to load GIF")?;
+    gif.ensure_nonempty()?;
 
-    // Get file size
-    let metadata = fs::metadata(input).context("Failed to read file metadata")?;
-    let file_size = metadata.len();
-    let file_size_mb = file_size as f64 / (1024.0 * 1024.0);
+    // Get file size (remote inputs have no local file to measure)
+    let file_size = if io::is_url(input) {
+        None
+    } else {
+        let metadata = fs::metadata(input).context("Failed to read file metadata")?;
+        Some(metadata.len())
+    };
 
-    // Calculate duration in seconds
-    let total_duration_cs = gif.total_duration();
-    let total_duration_sec = total_duration_cs as f64 / 100.0;
+    let duration = gif.duration();
 
     println!("GIF Information:");
     println!("  File: {}", input);
-    println!("  Size: {} bytes ({:.2} MB)", file_size, file_size_mb);
+    if let Some(file_size) = file_size {
+        let file_size_mb = file_size as f64 / (1024.0 * 1024.0);
+        println!("  Size: {} bytes ({:.2} MB)", file_size, file_size_mb);
+    }
+    println!("  Version: {}", gif.version);
     println!("  Dimensions: {}x{} pixels", gif.width, gif.height);
     println!("  Frames: {}", gif.frame_count());
     println!(
         "  Duration: {:.2} seconds ({} centiseconds)",
-        total_duration_sec, total_duration_cs
+        duration.as_secs_f64(),
+        gif.total_duration()
     );
 
     // Calculate average frame delay
-    if !gif.frames.is_empty() {
-        let avg_delay = total_duration_cs / gif.frame_count() as u32;
-        println!("  Average frame delay: {} ms", avg_delay * 10);
+    let avg_delay = duration / gif.frame_count() as u32;
+    println!("  Average frame delay: {} ms", avg_delay.as_millis());
+
+    // Dominant color (sampled, so cheap even for large GIFs)
+    println!(
+        "  Dominant color: {}",
+        format_hex_color(gif.dominant_color())
+    );
+
+    // Loop seam: how much the last frame differs from the first
+    if gif.frames.len() > 1 {
+        let seam = gif.loop_seam_score();
+        println!("  Loop seam score: {} (0 = seamless)", seam);
+        if seam > POOR_SEAM_THRESHOLD {
+            println!("    Hint: the last frame differs noticeably from the first, so the loop may visibly jump");
+        }
+        if !gif.looks_like_animation(ANIMATION_SIMILARITY)? {
+            println!("    Hint: most consecutive frames are unrelated, so this may be a collection of still images rather than an animation");
+        }
     }
 
     // Get color count (this might be slow for large GIFs, so we'll skip for now)
@@ -47,11 +98,7 @@ pub fn run(input: &str) -> Result<()> {
     // println!("  Colors: {}", colors);
 
     // Loop count
-    if gif.loop_count == 0 {
-        println!("  Loop: Infinite");
-    } else {
-        println!("  Loop: {} times", gif.loop_count);
-    }
+    println!("  Loop: {}", gif.loop_count);
 
     // Global palette info
     if let Some(palette) = &gif.global_palette {
@@ -60,23 +107,136 @@ pub fn run(input: &str) -> Result<()> {
         println!("  Global palette: None");
     }
 
-    // Optional: Show detailed frame information
-    // Uncomment if you want per-frame details
-    // println!("\nFrame Details:");
-    // for (i, frame) in gif.frames.iter().enumerate() {
-    //     println!("  Frame {}: delay={}ms, size={}x{}",
-    //              i + 1, frame.delay * 10, frame.width, frame.height);
-    // }
+    if a11y {
+        print_accessibility_report(&gif);
+    }
+
+    if verbose {
+        println!(
+            "  Frames with delay raised to minimum on load: {}",
+            gif.delay_adjustments
+        );
+        println!(
+            "  Interlaced frames: {}",
+            gif.frames.iter().filter(|f| f.interlaced).count()
+        );
+
+        println!("\nFrame Details:");
+        for (i, frame) in gif.frames.iter().enumerate() {
+            // Show the stored delay when loading raised it (e.g. a true 0)
+            let stored = if frame.original_delay != frame.delay {
+                format!(" (stored {}ms)", frame.original_delay as u32 * 10)
+            } else {
+                String::new()
+            };
+            println!(
+                "  Frame {}: delay={}ms{}, size={}x{}",
+                i + 1,
+                frame.delay as u32 * 10,
+                stored,
+                frame.width,
+                frame.height
+            );
+        }
+    }
 
     Ok(())
 }
 
+/// Compare the two most prominent colors, or `None` if there are fewer than two
+pub fn accessibility_report(gif: &Gif) -> Option<AccessibilityReport> {
+    let colors = gif.prominent_colors(2);
+    let [first, second] = colors[..] else {
+        return None;
+    };
+
+    // Distinct colors that collapse together under protanopia or deuteranopia
+    let differ = |a: [u8; 3], b: [u8; 3]| {
+        (0..3).any(|c| a[c].abs_diff(b[c]) >= INDISTINGUISHABLE_DIFFERENCE)
+    };
+    let looks_alike = |kind| {
+        let simulated_first = colorblind::simulate_pixel(first, kind);
+        let simulated_second = colorblind::simulate_pixel(second, kind);
+        !differ(simulated_first, simulated_second)
+    };
+    let red_green_confusable = differ(first, second)
+        && (looks_alike(CbKind::Protanopia) || looks_alike(CbKind::Deuteranopia));
+
+    Some(AccessibilityReport {
+        colors: (first, second),
+        contrast_ratio: contrast_ratio(first, second),
+        red_green_confusable,
+    })
+}
+
+/// Print the accessibility section of the info output
+fn print_accessibility_report(gif: &Gif) {
+    println!("  Accessibility:");
+
+    let Some(report) = accessibility_report(gif) else {
+        println!("    Fewer than two colors, no contrast to measure");
+        return;
+    };
+
+    println!(
+        "    Prominent colors: {} and {}",
+        format_hex_color(report.colors.0),
+        format_hex_color(report.colors.1)
+    );
+    println!("    Contrast ratio: {:.2}:1", report.contrast_ratio);
+    if report.contrast_ratio < WCAG_AA_CONTRAST {
+        println!(
+            "    Warning: below the WCAG AA minimum of {}:1, text may be hard to read",
+            WCAG_AA_CONTRAST
+        );
+    }
+    if report.red_green_confusable {
+        println!("    Warning: the colors differ mainly in red/green and look alike with color blindness");
+    }
+}
+
+/// Format an RGB color as a `#rrggbb` hex string
+pub fn format_hex_color(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_info_display() {
-        // This test requires a sample GIF file
-        // For now, we'll just test that it compiles
-        assert!(true);
+        assert!(run("tests/fixtures/simple.gif", true, true).is_ok());
+        assert!(run("tests/fixtures/missing.gif", false, false).is_err());
+    }
+
+    #[test]
+    fn test_format_hex_color() {
+        assert_eq!(format_hex_color([255, 0, 0]), "#ff0000");
+        assert_eq!(format_hex_color([0, 128, 255]), "#0080ff");
+        assert_eq!(format_hex_color([0, 0, 0]), "#000000");
+    }
+
+    #[test]
+    fn test_accessibility_black_and_white() {
+        // White background with a black ball
+        let gif = Gif::from_file("tests/fixtures/high_fps.gif").unwrap();
+        let report = accessibility_report(&gif).unwrap();
+
+        assert_eq!(report.colors, ([255, 255, 255], [0, 0, 0]));
+        assert!(report.contrast_ratio > 20.9, "{}", report.contrast_ratio);
+        assert!(!report.red_green_confusable);
+    }
+
+    #[test]
+    fn test_accessibility_red_green() {
+        // A red and a green that look the same with deuteranopia
+        let mut gif = Gif::new();
+        let mut data = [200, 60, 40, 255].repeat(6);
+        data.extend([0, 145, 10, 255].repeat(4));
+        gif.add_frame(crate::core::Frame::from_rgba(data, 5, 2));
+
+        let report = accessibility_report(&gif).unwrap();
+        assert!(report.red_green_confusable);
     }
 }
@@ -0,0 +1,64 @@
+use crate::core::Gif;
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Cap the number of frames, e.g. for chat platforms with a frame limit
+///
+/// Frames are composited first, then evenly spaced frames are kept and
+/// each one absorbs the delays of the frames dropped after it, so the total
+/// duration is unchanged. GIFs already within the limit are re-encoded as is.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `max_frames` - Maximum number of frames in the output
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::limit_frames;
+///
+/// limit_frames::run("input.gif", "output.gif", 50).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, max_frames: usize) -> Result<()> {
+    if max_frames == 0 {
+        anyhow::bail!("Maximum frame count must be at least 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Maximum frames: {}", max_frames);
+
+    // Dropped frames may hold content later partial frames build on
+    gif.normalize()?;
+    let removed = gif.limit_frames(max_frames);
+    log::info!("   Removed frames: {}", removed);
+
+    // Save the limited GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_high_fps() {
+        let input = "tests/fixtures/high_fps.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("limit_frames.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, 10).unwrap();
+        let limited = Gif::from_file(output).unwrap();
+
+        let original = Gif::from_file(input).unwrap();
+        assert_eq!(limited.frames.len(), 10);
+        assert_eq!(limited.total_duration(), original.total_duration());
+    }
+}
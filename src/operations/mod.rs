@@ -1,4 +1,33 @@
+pub mod alpha;
+pub mod annotate;
+pub mod border;
+pub mod capabilities;
+pub mod channels;
+pub mod colorblind;
 pub mod compress;
+pub mod constant_fps;
+pub mod dedup;
+pub mod diff;
+pub mod duotone;
+pub mod filmstrip;
+pub mod heatmap;
+pub mod histogram;
 pub mod info;
+pub mod limit_frames;
+pub mod onionskin;
+pub mod optimize;
+pub mod pan;
+pub mod pick_frame;
+pub mod pipeline;
+pub mod preview;
+pub mod quantize;
+pub mod repeat;
+pub mod scenes;
+pub mod set_disposal;
+pub mod spec;
 pub mod speed;
+pub mod split;
+pub mod split_chunks;
+pub mod stamp;
+pub mod thumbnail;
 pub mod tune;
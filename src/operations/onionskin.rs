@@ -0,0 +1,91 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+
+/// Overlay all frames into a single "motion trail" PNG
+///
+/// Frames are drawn from first to last, so the last frame is on top at full
+/// opacity and each earlier frame is fainter by `opacity_falloff`.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_png` - Path to output PNG file
+/// * `opacity_falloff` - Opacity multiplier per step back in time (0.0 < falloff <= 1.0)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::onionskin;
+///
+/// // Each earlier frame is drawn at 60% of the opacity of the next one
+/// onionskin::run("input.gif", "trail.png", 0.6).unwrap();
+/// ```
+pub fn run(input: &str, output_png: &str, opacity_falloff: f32) -> Result<()> {
+    // Validate falloff
+    if !(opacity_falloff > 0.0 && opacity_falloff <= 1.0) {
+        anyhow::bail!("Opacity falloff must be greater than 0 and at most 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Opacity falloff: {:.2}", opacity_falloff);
+
+    // Each layer must hold the complete image shown at that point
+    gif.normalize()?;
+
+    let canvas = onion_skin(&gif, opacity_falloff);
+
+    let image = image::RgbaImage::from_raw(gif.width as u32, gif.height as u32, canvas)
+        .context("Failed to create output image")?;
+    image
+        .save(output_png)
+        .context("Failed to save output PNG")?;
+
+    Ok(())
+}
+
+/// Composite every frame onto a transparent canvas with decaying opacity
+fn onion_skin(gif: &Gif, opacity_falloff: f32) -> Vec<u8> {
+    let mut canvas = vec![0u8; gif.width as usize * gif.height as usize * 4];
+    let last = gif.frames.len() - 1;
+
+    for (index, frame) in gif.frames.iter().enumerate() {
+        let opacity = opacity_falloff.powi((last - index) as i32);
+
+        let mut layer = frame.clone();
+        for pixel in layer.data.chunks_exact_mut(4) {
+            pixel[3] = (pixel[3] as f32 * opacity).round() as u8;
+        }
+
+        layer.composite_onto(&mut canvas, gif.width, layer.left, layer.top);
+    }
+
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_onionskin_colorful() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("onionskin.png");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/colorful.gif", output, 0.7).unwrap();
+        let size = std::fs::metadata(output).unwrap().len();
+        let image = image::open(output).unwrap();
+
+        assert!(size > 0);
+        assert_eq!((image.width(), image.height()), (200, 200));
+    }
+
+    #[test]
+    fn test_invalid_falloff() {
+        assert!(run("tests/fixtures/simple.gif", "unused.png", 0.0).is_err());
+        assert!(run("tests/fixtures/simple.gif", "unused.png", 1.5).is_err());
+    }
+}
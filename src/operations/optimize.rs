@@ -0,0 +1,150 @@
+use crate::core::Gif;
+use crate::io;
+use anyhow::{Context, Result};
+use std::fs;
+
+/// Make a GIF smaller using only lossless passes
+///
+/// Applies exact-duplicate frame merging, unused palette color removal and
+/// changed-region encoding (see [`Gif::to_file_optimized`]). Colors are never
+/// reduced, so the displayed animation is unchanged. If the result is not
+/// smaller than the input, the input is copied unchanged.
+///
+/// With `drop_bad_last`, a damaged file is decoded up to the first broken
+/// frame and an incomplete last frame is removed (see
+/// [`Gif::from_file_lossy`]); a damaged input is then always re-encoded.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `drop_bad_last` - Drop a truncated or corrupt last frame
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::optimize;
+///
+/// optimize::run("input.gif", "output.gif", false).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, drop_bad_last: bool) -> Result<()> {
+    // Load the GIF
+    let (mut gif, damaged) = if drop_bad_last {
+        let (gif, warnings) = Gif::from_file_lossy(input).context("Failed to load input GIF")?;
+        for warning in &warnings {
+            log::warn!("{}", warning);
+        }
+        (gif, !warnings.is_empty())
+    } else {
+        let gif = Gif::from_file(input).context("Failed to load input GIF")?;
+        (gif, false)
+    };
+    gif.ensure_nonempty()?;
+    let original_size = fs::metadata(input)?.len();
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Original size: {} bytes", original_size);
+    log::info!("   Original frames: {}", gif.frames.len());
+
+    // Copying a damaged input back would restore the dropped frame
+    write_lossless(&mut gif, input, output, !damaged)?;
+
+    let final_size = fs::metadata(output)?.len();
+    log::info!("   Final size: {} bytes", final_size);
+    log::info!(
+        "   Saved: {:.1}%",
+        io::calculate_compression_ratio(original_size, final_size)
+    );
+
+    Ok(())
+}
+
+/// Apply the lossless passes to `gif` and write it to `output`
+///
+/// With `allow_copy`, falls back to copying `input` when the re-encoded file
+/// would be larger.
+pub(crate) fn write_lossless(
+    gif: &mut Gif,
+    input: &str,
+    output: &str,
+    allow_copy: bool,
+) -> Result<()> {
+    let original_size = fs::metadata(input)?.len();
+
+    // Compare and encode what is actually on screen
+    gif.normalize()?;
+
+    let merged = gif.merge_identical_frames();
+    log::info!("   Merged duplicate frames: {}", merged);
+
+    if let Some(palette) = &gif.global_palette {
+        let original_colors = palette.len();
+        gif.trim_palette();
+        let trimmed_colors = gif.global_palette.as_ref().map_or(0, |p| p.len());
+        log::info!(
+            "   Global palette: {} -> {} colors",
+            original_colors,
+            trimmed_colors
+        );
+    }
+
+    io::atomic_write(output, |path| gif.to_file_optimized(path))
+        .context("Failed to save output GIF")?;
+
+    let optimized_size = fs::metadata(output)?.len();
+    if allow_copy && optimized_size > original_size {
+        log::info!("   Input is already smaller, keeping original encoding");
+        io::atomic_write(output, |path| {
+            fs::copy(input, path)?;
+            Ok(())
+        })
+        .context("Failed to save output GIF")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Composited frames with consecutive duplicates merged
+    fn timeline(path: &str) -> Vec<(Vec<u8>, u16)> {
+        let mut gif = Gif::from_file(path).unwrap();
+        gif.normalize().unwrap();
+        gif.merge_identical_frames();
+        gif.frames.into_iter().map(|f| (f.data, f.delay)).collect()
+    }
+
+    #[test]
+    fn test_optimize_is_lossless() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["duplicates", "colorful", "simple"] {
+            let input = format!("tests/fixtures/{}.gif", name);
+            let output = dir.path().join(format!("{}.gif", name));
+            let output = output.to_str().unwrap();
+
+            run(&input, output, false).unwrap();
+            let input_size = fs::metadata(&input).unwrap().len();
+            let output_size = fs::metadata(output).unwrap().len();
+            let optimized = timeline(output);
+
+            assert!(output_size <= input_size, "{} grew", name);
+            assert!(optimized == timeline(&input), "{} changed pixels", name);
+        }
+    }
+
+    #[test]
+    fn test_drop_bad_last() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("optimized.gif");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/truncated.gif", output, true).unwrap();
+        assert_eq!(Gif::from_file(output).unwrap().frames.len(), 2);
+
+        // An intact input keeps the fallback to its original encoding
+        let input = "tests/fixtures/colorful.gif";
+        run(input, output, true).unwrap();
+        assert!(fs::metadata(output).unwrap().len() <= fs::metadata(input).unwrap().len());
+        assert!(timeline(output) == timeline(input));
+    }
+}
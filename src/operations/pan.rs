@@ -0,0 +1,118 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Shift frame content by an offset that grows with each frame (pan effect)
+///
+/// Frames are composited first, then frame `i` is moved by `(dx * i, dy * i)`
+/// pixels. With `wrap` the content scrolls around the edges like a torus;
+/// otherwise the exposed area becomes transparent.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `dx` - Horizontal shift per frame in pixels (negative moves left)
+/// * `dy` - Vertical shift per frame in pixels (negative moves up)
+/// * `wrap` - Wrap content around the edges instead of exposing transparency
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::pan;
+///
+/// // Scroll right by 4 pixels per frame, wrapping around
+/// pan::run("input.gif", "output.gif", 4, 0, true).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, dx: i32, dy: i32, wrap: bool) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!(
+        "   Shift per frame: ({}, {}){}",
+        dx,
+        dy,
+        if wrap { ", wrapping" } else { "" }
+    );
+
+    // Shift what is on screen, not the stored partial frames
+    gif.normalize()?;
+
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        let offset_x = dx as i64 * index as i64;
+        let offset_y = dy as i64 * index as i64;
+        shift_frame(frame, offset_x, offset_y, wrap);
+    }
+
+    // Save the panned GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Move a full-canvas frame's pixels by an offset
+fn shift_frame(frame: &mut Frame, offset_x: i64, offset_y: i64, wrap: bool) {
+    let (width, height) = (frame.width as i64, frame.height as i64);
+    let mut shifted = vec![0u8; frame.data.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            // Source pixel that lands on (x, y)
+            let (mut sx, mut sy) = (x - offset_x, y - offset_y);
+            if wrap {
+                sx = sx.rem_euclid(width);
+                sy = sy.rem_euclid(height);
+            } else if sx < 0 || sx >= width || sy < 0 || sy >= height {
+                continue;
+            }
+
+            let src = ((sy * width + sx) * 4) as usize;
+            let dst = ((y * width + x) * 4) as usize;
+            shifted[dst..dst + 4].copy_from_slice(&frame.data[src..src + 4]);
+        }
+    }
+
+    frame.data = shifted;
+    frame.transparent = frame.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pan_displaces_content() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("pan.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, 3, 2, true).unwrap();
+        let panned = Gif::from_file(output).unwrap();
+
+        let mut original = Gif::from_file(input).unwrap();
+        original.normalize().unwrap();
+
+        // Frame 4 is shifted by (12, 8): pixel (x, y) shows original (x-12, y-8)
+        let width = original.width as usize;
+        let (before, after) = (&original.frames[4], &panned.frames[4]);
+        for (x, y) in [(12, 8), (50, 60), (5, 3)] {
+            let src_x = (x + width - 12) % width;
+            let src_y = (y + original.height as usize - 8) % original.height as usize;
+            let dst = (y * width + x) * 4;
+            let src = (src_y * width + src_x) * 4;
+            assert_eq!(after.data[dst..dst + 4], before.data[src..src + 4]);
+        }
+    }
+
+    #[test]
+    fn test_shift_without_wrap_exposes_transparency() {
+        let mut frame = Frame::from_rgba([255, 0, 0, 255].repeat(16), 4, 4);
+        shift_frame(&mut frame, 1, 0, false);
+
+        assert_eq!(&frame.data[0..4], &[0, 0, 0, 0]);
+        assert_eq!(&frame.data[4..8], &[255, 0, 0, 255]);
+        assert!(frame.transparent);
+    }
+}
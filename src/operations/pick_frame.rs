@@ -0,0 +1,90 @@
+use crate::core::Gif;
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Save a single frame as a static, one-frame GIF
+///
+/// The frame is composited first, so the output shows exactly what is on
+/// screen at that point of the animation (useful as a poster image).
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `index` - 0-based frame index; negative values count from the end
+///   (-1 is the last frame)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::pick_frame;
+///
+/// // Use the last frame as a poster
+/// pick_frame::run("input.gif", "poster.gif", -1).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, index: i64) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+    let frame_count = gif.frames.len();
+
+    let resolved = resolve_index(index, frame_count).with_context(|| {
+        format!(
+            "Frame index {} is out of range (GIF has {} frames)",
+            index, frame_count
+        )
+    })?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", frame_count);
+    log::info!("   Picked frame: {}", resolved);
+
+    // The frame must show its full composited content on its own
+    gif.normalize()?;
+    let picked = gif.subsample_frames(&[resolved])?;
+
+    // Save the single-frame GIF
+    io::atomic_write(output, |path| picked.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Map a possibly negative index onto `0..len`
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+
+    usize::try_from(resolved).ok().filter(|&i| i < len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_index() {
+        assert_eq!(resolve_index(0, 3), Some(0));
+        assert_eq!(resolve_index(2, 3), Some(2));
+        assert_eq!(resolve_index(-1, 3), Some(2));
+        assert_eq!(resolve_index(-3, 3), Some(0));
+        assert_eq!(resolve_index(3, 3), None);
+        assert_eq!(resolve_index(-4, 3), None);
+        assert_eq!(resolve_index(0, 0), None);
+    }
+
+    #[test]
+    fn test_pick_last_frame() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("pick_frame.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, -1).unwrap();
+        let picked = Gif::from_file(output).unwrap();
+
+        let mut expected = Gif::from_file(input).unwrap();
+        expected.normalize().unwrap();
+        let last = expected.frames.last().unwrap();
+
+        assert_eq!(picked.frames.len(), 1);
+        assert_eq!(picked.frames[0].data, last.data);
+        assert!(run(input, output, 10).is_err());
+    }
+}
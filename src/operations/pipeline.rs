@@ -0,0 +1,337 @@
+use crate::core::{resize_frame, Frame, Gif};
+use crate::io;
+use crate::operations::colorblind::{self, CbKind};
+use crate::operations::{duotone, tune};
+use crate::processor::FrameOp;
+use crate::utils::parse_hex_color;
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// Stage names accepted by [`Pipeline::parse`], with their arguments
+pub const STAGES: &[&str] = &[
+    "grayscale",
+    "duotone:#dark,#light",
+    "colorblind:protanopia/deuteranopia/tritanopia",
+    "resize:W or resize:WxH",
+    "speed:FACTOR",
+    "set-delay:CENTISECONDS",
+    "limit-frames:N",
+];
+
+/// A pipeline stage that changes the whole GIF (size, timing, frame count)
+pub trait GifOp {
+    /// Apply the stage to a composited GIF
+    fn apply(&self, gif: &mut Gif) -> Result<()>;
+}
+
+/// Every [`FrameOp`] is a stage that runs on all frames, in parallel with
+/// the `parallel` feature
+impl<T: FrameOp> GifOp for T {
+    fn apply(&self, gif: &mut Gif) -> Result<()> {
+        gif.par_map_frames(|frame| self.apply_frame(frame));
+        Ok(())
+    }
+}
+
+/// Convert colors to gray by their luma
+struct Grayscale;
+
+impl FrameOp for Grayscale {
+    fn apply_frame(&self, frame: &mut Frame) {
+        duotone::duotone_frame(frame, [0; 3], [255; 3]);
+    }
+}
+
+/// Map luma onto a two-color gradient
+struct Duotone {
+    dark: [u8; 3],
+    light: [u8; 3],
+}
+
+impl FrameOp for Duotone {
+    fn apply_frame(&self, frame: &mut Frame) {
+        duotone::duotone_frame(frame, self.dark, self.light);
+    }
+}
+
+/// Simulate a color vision deficiency
+struct Colorblind(CbKind);
+
+impl FrameOp for Colorblind {
+    fn apply_frame(&self, frame: &mut Frame) {
+        colorblind::simulate_frame(frame, self.0);
+    }
+}
+
+/// Scale the canvas to a width, and a height or the original aspect ratio
+struct Resize {
+    width: u16,
+    height: Option<u16>,
+}
+
+impl GifOp for Resize {
+    fn apply(&self, gif: &mut Gif) -> Result<()> {
+        let height = match self.height {
+            Some(height) => height,
+            None => {
+                let height = (self.width as f64 * gif.height as f64 / gif.width as f64).round();
+                tune::to_gif_dimension((height as u32).max(1), "height")?
+            }
+        };
+
+        let width = self.width;
+        gif.par_map_frames(|frame| resize_frame(frame, width as u32, height as u32, true));
+        gif.width = width;
+        gif.height = height;
+        Ok(())
+    }
+}
+
+/// Multiply playback speed without dropping frames
+struct Speed(f64);
+
+impl GifOp for Speed {
+    fn apply(&self, gif: &mut Gif) -> Result<()> {
+        let intended: Vec<f64> = gif.frames.iter().map(|f| f.delay as f64 / self.0).collect();
+        gif.quantize_delays_preserving_total(&intended)
+    }
+}
+
+/// Give every frame the same delay
+struct SetDelay(u16);
+
+impl GifOp for SetDelay {
+    fn apply(&self, gif: &mut Gif) -> Result<()> {
+        for frame in &mut gif.frames {
+            frame.delay = self.0;
+            frame.original_delay = self.0;
+        }
+        Ok(())
+    }
+}
+
+/// Uniformly drop frames down to a maximum count
+struct LimitFrames(usize);
+
+impl GifOp for LimitFrames {
+    fn apply(&self, gif: &mut Gif) -> Result<()> {
+        gif.limit_frames(self.0);
+        Ok(())
+    }
+}
+
+/// An ordered list of stages parsed from a spec such as
+/// `grayscale|resize:200|speed:2.0`
+pub struct Pipeline {
+    stages: Vec<(String, Box<dyn GifOp>)>,
+}
+
+impl Pipeline {
+    /// Parse a `|`-separated list of `name` or `name:args` stages
+    ///
+    /// See [`STAGES`] for the stage names and their arguments.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let stages = spec
+            .split('|')
+            .map(str::trim)
+            .map(|stage| Ok((stage.to_string(), parse_stage(stage)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { stages })
+    }
+
+    /// Number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Whether the pipeline has no stages (never true for a parsed pipeline)
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Apply every stage in order to a GIF
+    ///
+    /// Frames are composited first, so stages always see full-canvas frames
+    /// with straight alpha.
+    pub fn apply(&self, gif: &mut Gif) -> Result<()> {
+        gif.normalize()?;
+        gif.par_map_frames(Frame::unpremultiply);
+
+        for (name, stage) in &self.stages {
+            log::info!("   Stage: {}", name);
+            stage
+                .apply(gif)
+                .with_context(|| format!("Pipeline stage '{}' failed", name))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Pipeline {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Self::parse(spec)
+    }
+}
+
+/// Parse one `name` or `name:args` stage
+fn parse_stage(stage: &str) -> Result<Box<dyn GifOp>> {
+    let (name, args) = match stage.split_once(':') {
+        Some((name, args)) => (name.trim(), Some(args.trim())),
+        None => (stage, None),
+    };
+    let args = || args.with_context(|| format!("Stage '{}' needs an argument", name));
+    let number = |what: &str| -> Result<f64> {
+        let text = args()?;
+        text.parse()
+            .with_context(|| format!("Invalid {} '{}' for stage '{}'", what, text, name))
+    };
+
+    let op: Box<dyn GifOp> = match name {
+        "grayscale" => Box::new(Grayscale),
+        "duotone" => {
+            let (dark, light) = args()?
+                .split_once(',')
+                .context("Stage 'duotone' expects #dark,#light")?;
+            Box::new(Duotone {
+                dark: parse_hex_color(dark)?,
+                light: parse_hex_color(light)?,
+            })
+        }
+        "colorblind" => {
+            let kind = args()?
+                .parse::<CbKind>()
+                .context("Invalid stage 'colorblind'")?;
+            Box::new(Colorblind(kind))
+        }
+        "resize" => {
+            let text = args()?;
+            let size = |value: &str| -> Result<u16> {
+                value
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|&v| v > 0)
+                    .with_context(|| format!("Invalid size '{}' for stage 'resize'", text))
+            };
+            match text.split_once('x') {
+                Some((width, height)) => Box::new(Resize {
+                    width: size(width)?,
+                    height: Some(size(height)?),
+                }),
+                None => Box::new(Resize {
+                    width: size(text)?,
+                    height: None,
+                }),
+            }
+        }
+        "speed" => {
+            let factor = number("factor")?;
+            if !(factor > 0.0 && factor.is_finite()) {
+                anyhow::bail!("Speed factor must be greater than 0");
+            }
+            Box::new(Speed(factor))
+        }
+        "set-delay" => {
+            let delay = number("delay")?;
+            if !(1.0..=u16::MAX as f64).contains(&delay) || delay.fract() != 0.0 {
+                anyhow::bail!("Frame delay must be a whole number of centiseconds, at least 1");
+            }
+            Box::new(SetDelay(delay as u16))
+        }
+        "limit-frames" => {
+            let max_frames = number("frame count")?;
+            if max_frames < 1.0 || max_frames.fract() != 0.0 {
+                anyhow::bail!("Maximum frame count must be a whole number, at least 1");
+            }
+            Box::new(LimitFrames(max_frames as usize))
+        }
+        _ => anyhow::bail!(
+            "Unknown pipeline stage '{}', expected one of: {}",
+            name,
+            STAGES.join(", ")
+        ),
+    };
+
+    Ok(op)
+}
+
+/// Apply a chain of effects in one pass, e.g. `grayscale|resize:200|speed:2.0`
+///
+/// The spec is parsed before the input is loaded, so typos fail fast. Stages
+/// run in order on the composited frames (see [`Pipeline::apply`]).
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `spec` - `|`-separated stages, see [`STAGES`]
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::pipeline;
+///
+/// pipeline::run("input.gif", "output.gif", "grayscale|resize:200|speed:2.0").unwrap();
+/// ```
+pub fn run(input: &str, output: &str, spec: &str) -> Result<()> {
+    let pipeline = Pipeline::parse(spec)?;
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Stages: {}", pipeline.len());
+
+    pipeline.apply(&mut gif)?;
+
+    // Save the processed GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pipeline() {
+        assert_eq!(Pipeline::parse("grayscale").unwrap().len(), 1);
+        let pipeline: Pipeline = "grayscale | resize:200x100 | speed:2.0 | colorblind:Tritanopia"
+            .parse()
+            .unwrap();
+        assert_eq!(pipeline.len(), 4);
+
+        assert!(Pipeline::parse("blur").is_err());
+        assert!(Pipeline::parse("grayscale||speed:2").is_err());
+        assert!(Pipeline::parse("resize").is_err());
+        assert!(Pipeline::parse("resize:0").is_err());
+        assert!(Pipeline::parse("speed:-1").is_err());
+        assert!(Pipeline::parse("set-delay:2.5").is_err());
+        assert!(Pipeline::parse("duotone:#000000").is_err());
+    }
+
+    #[test]
+    fn test_two_stage_pipeline() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("pipeline.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, "grayscale|resize:50").unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        assert_eq!((result.width, result.height), (50, 50));
+        assert_eq!(result.frames.len(), 10);
+        for frame in &result.frames {
+            assert!(frame
+                .data
+                .chunks_exact(4)
+                .filter(|p| p[3] > 0)
+                .all(|p| p[0] == p[1] && p[1] == p[2]));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+use image::imageops::FilterType;
+use image::RgbaImage;
+
+/// Terminal width used when it cannot be detected (e.g. output is piped)
+const DEFAULT_COLUMNS: u32 = 80;
+
+/// Upper half block: foreground colors the top pixel, background the bottom one
+const HALF_BLOCK: char = '\u{2580}';
+
+/// Print a frame of the GIF to the terminal using 24-bit ANSI colors
+///
+/// # Arguments
+/// * `input` - Path to the GIF file
+/// * `frame` - Index of the frame to render (0-based)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::preview;
+///
+/// preview::run("example.gif", 0).unwrap();
+/// ```
+pub fn run(input: &str, frame: usize) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load GIF")?;
+    gif.ensure_nonempty()?;
+
+    if frame >= gif.frames.len() {
+        anyhow::bail!(
+            "Frame index {} is out of range (GIF has {} frames)",
+            frame,
+            gif.frames.len()
+        );
+    }
+
+    // Composite partial frames so the preview shows what a viewer would display
+    gif.normalize()?;
+
+    let image = gif.frames[frame].to_image_buffer();
+    let columns = terminal_columns().min(image.width()).max(1);
+
+    for line in render(&image, columns) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// Detect the terminal width in columns, falling back to 80
+fn terminal_columns() -> u32 {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as u32)
+        .filter(|&w| w > 0)
+        .unwrap_or(DEFAULT_COLUMNS)
+}
+
+/// Render an image as lines of ANSI half-block characters
+///
+/// The image is scaled to `columns` characters wide, preserving aspect ratio.
+/// Each output line covers two pixel rows, so an image scaled to `h` pixels
+/// tall produces `ceil(h / 2)` lines. Transparent pixels are drawn over black.
+pub fn render(image: &RgbaImage, columns: u32) -> Vec<String> {
+    if image.width() == 0 || image.height() == 0 || columns == 0 {
+        return Vec::new();
+    }
+
+    let rows =
+        ((image.height() as f64 * columns as f64 / image.width() as f64).round() as u32).max(1);
+    let scaled = image::imageops::resize(image, columns, rows, FilterType::Triangle);
+
+    let mut lines = Vec::with_capacity(rows.div_ceil(2) as usize);
+
+    for y in (0..rows).step_by(2) {
+        let mut line = String::new();
+
+        for x in 0..columns {
+            let [tr, tg, tb] = over_black(scaled.get_pixel(x, y).0);
+            line.push_str(&format!("\x1b[38;2;{};{};{}m", tr, tg, tb));
+
+            // The last line of an odd-height image has no bottom pixel
+            if y + 1 < rows {
+                let [br, bg, bb] = over_black(scaled.get_pixel(x, y + 1).0);
+                line.push_str(&format!("\x1b[48;2;{};{};{}m", br, bg, bb));
+            } else {
+                line.push_str("\x1b[49m");
+            }
+
+            line.push(HALF_BLOCK);
+        }
+
+        // Reset colors at the end of each line
+        line.push_str("\x1b[0m");
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Blend an RGBA pixel over a black background
+fn over_black(pixel: [u8; 4]) -> [u8; 3] {
+    let alpha = pixel[3] as u32;
+    [
+        (pixel[0] as u32 * alpha / 255) as u8,
+        (pixel[1] as u32 * alpha / 255) as u8,
+        (pixel[2] as u32 * alpha / 255) as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_line_count() {
+        // 20x10 image at 10 columns scales to 10x5 pixels -> 3 lines
+        let image = RgbaImage::from_pixel(20, 10, image::Rgba([255, 0, 0, 255]));
+        let lines = render(&image, 10);
+        assert_eq!(lines.len(), 3);
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().filter(|&c| c == HALF_BLOCK).count() == 10));
+
+        // Square image at 8 columns is 8 pixel rows -> 4 lines
+        let image = RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 255, 255]));
+        assert_eq!(render(&image, 8).len(), 4);
+    }
+}
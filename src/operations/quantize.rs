@@ -0,0 +1,92 @@
+use crate::core::{EncodeOptions, Gif};
+use crate::io;
+use crate::operations::compress::{reduce_colors, ColorSpace};
+use anyhow::{Context, Result};
+
+/// Reduce a GIF to at most `colors` distinct colors
+///
+/// Colors are chosen with NeuQuant across all frames and every frame is
+/// written against one shared global palette, so per-frame requantization
+/// cannot introduce extra colors. The encoded file is reloaded to verify
+/// the limit holds before it replaces `output`.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `colors` - Maximum number of colors (1-256)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::quantize;
+///
+/// quantize::run("input.gif", "output.gif", 16).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, colors: u16) -> Result<()> {
+    if !(1..=256).contains(&colors) {
+        anyhow::bail!("Color count must be between 1 and 256, got {}", colors);
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Input colors: {}", gif.color_count());
+    log::info!("   Target colors: {}", colors);
+
+    // Quantize the composited frames so every pixel uses the reduced palette
+    gif.normalize()?;
+    reduce_colors(&mut gif, colors as usize, ColorSpace::Rgb)?;
+
+    // A single palette keeps the exact reduced colors
+    let options = EncodeOptions {
+        per_frame_palette: false,
+        ..EncodeOptions::default()
+    };
+    io::atomic_write(output, |path| {
+        gif.to_file_with_options(path, options)?;
+        verify_color_count(path, colors as usize)
+    })
+    .context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Reload an encoded GIF and check it has at most `max_colors` colors
+fn verify_color_count(path: &str, max_colors: usize) -> Result<()> {
+    let written = Gif::from_file(path).context("Failed to reload output GIF")?;
+    let output_colors = written.color_count();
+    log::info!("   Output colors: {}", output_colors);
+    if output_colors > max_colors {
+        anyhow::bail!(
+            "Output has {} colors, more than the requested {}",
+            output_colors,
+            max_colors
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_to_eight_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("quantize.gif");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/colorful.gif", output, 8).unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        assert_eq!(gif.frames.len(), 10);
+        assert!(gif.color_count() <= 8, "{} colors", gif.color_count());
+        assert!(run("tests/fixtures/colorful.gif", output, 0).is_err());
+        assert!(run("tests/fixtures/colorful.gif", output, 257).is_err());
+
+        assert!(verify_color_count(output, 8).is_ok());
+        assert!(verify_color_count(output, 1).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::core::{Gif, Loop};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Frame counts above this get a warning about the output size
+const LARGE_FRAME_COUNT: usize = 1000;
+
+/// Physically repeat the animation, for players that ignore the loop count
+///
+/// The whole frame sequence is appended `times` times in total, so the
+/// file grows roughly linearly with `times`. With `play_once` the loop count
+/// is set to play the result a single time instead of looping forever.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `times` - How many copies of the animation the output contains
+/// * `play_once` - Mark the output to play once instead of looping
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::repeat;
+///
+/// // Three plays in a row, then stop
+/// repeat::run("input.gif", "output.gif", 3, true).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, times: u32, play_once: bool) -> Result<()> {
+    if times == 0 {
+        anyhow::bail!("Repeat count must be at least 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let original_count = gif.frames.len();
+    let total = original_count
+        .checked_mul(times as usize)
+        .context("Repeated frame count overflows")?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {} -> {}", original_count, total);
+    if total > LARGE_FRAME_COUNT {
+        log::info!("   Warning: {} frames may produce a very large file", total);
+    }
+
+    let sequence = gif.frames.clone();
+    gif.frames.reserve(total - original_count);
+    for _ in 1..times {
+        gif.frames.extend_from_slice(&sequence);
+    }
+
+    if play_once {
+        gif.loop_count = Loop::Finite(0);
+    }
+
+    // Save the repeated GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat_three_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("repeat.gif");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/simple.gif", output, 3, false).unwrap();
+        let gif = Gif::from_file(output).unwrap();
+
+        let original = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        assert_eq!(gif.frames.len(), 6);
+        assert_eq!(gif.frames[4].data, original.frames[0].data);
+        assert_eq!(gif.frames[5].data, original.frames[1].data);
+        assert!(run("tests/fixtures/simple.gif", output, 0, false).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::Gif;
+use anyhow::{Context, Result};
+
+/// Default for the `threshold` of [`run`]: a cut changes at least a quarter
+/// of the average pixel value
+pub const DEFAULT_SCENE_THRESHOLD: u8 = 64;
+
+/// Print the frames where a new scene starts, with their timestamps
+///
+/// Frames are compared as displayed (see [`Gif::scene_cuts`]). The cut
+/// indices can be fed to frame-range operations to split a long recording.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `threshold` - Average per-pixel difference (0-255) above which a frame
+///   starts a new scene
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::scenes;
+///
+/// scenes::run("recording.gif", scenes::DEFAULT_SCENE_THRESHOLD).unwrap();
+/// ```
+pub fn run(input: &str, threshold: u8) -> Result<()> {
+    // Load the GIF
+    let gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let cuts = gif.scene_cuts(threshold)?;
+    let starts = frame_start_times(&gif);
+
+    println!("Scene Cuts:");
+    println!("  Frames: {}", gif.frames.len());
+    println!("  Threshold: {}", threshold);
+    println!("  Scenes: {}", cuts.len() + 1);
+    for cut in cuts {
+        println!("    Frame {} at {:.2}s", cut, starts[cut] as f64 / 100.0);
+    }
+
+    Ok(())
+}
+
+/// Start time of every frame in 10ms units
+fn frame_start_times(gif: &Gif) -> Vec<u32> {
+    gif.frames
+        .iter()
+        .scan(0u32, |elapsed, frame| {
+            let start = *elapsed;
+            *elapsed += frame.delay as u32;
+            Some(start)
+        })
+        .collect()
+}
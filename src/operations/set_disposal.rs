@@ -0,0 +1,119 @@
+use crate::core::{EncodeOptions, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+use gif::DisposalMethod;
+use std::str::FromStr;
+
+/// Disposal method to force on every frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disposal {
+    /// Leave the frame on screen; the next frame draws over it
+    Keep,
+    /// Clear the frame's area to transparent before the next frame
+    Background,
+    /// Restore what was on screen before the frame was drawn
+    Previous,
+}
+
+impl FromStr for Disposal {
+    type Err = anyhow::Error;
+
+    /// Parse a disposal method name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "background" => Ok(Self::Background),
+            "previous" => Ok(Self::Previous),
+            _ => anyhow::bail!(
+                "Unknown disposal method '{}', expected keep, background or previous",
+                name.trim()
+            ),
+        }
+    }
+}
+
+impl From<Disposal> for DisposalMethod {
+    fn from(disposal: Disposal) -> Self {
+        match disposal {
+            Disposal::Keep => DisposalMethod::Keep,
+            Disposal::Background => DisposalMethod::Background,
+            Disposal::Previous => DisposalMethod::Previous,
+        }
+    }
+}
+
+/// Overwrite every frame's disposal method, e.g. to fix flickering GIFs
+///
+/// Frames are re-encoded as stored (not composited), so the override changes
+/// how they stack up on screen. Forcing `Background` on a GIF whose frames
+/// only cover changed regions clears everything else between frames, which
+/// can leave areas blank; `Keep` on frames that rely on clearing can leave
+/// trails.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `method` - Disposal method for every frame
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::set_disposal::{self, Disposal};
+///
+/// set_disposal::run("input.gif", "output.gif", Disposal::Background).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, method: Disposal) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let partial_frames = gif
+        .frames
+        .iter()
+        .filter(|f| f.width != gif.width || f.height != gif.height)
+        .count();
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Disposal: {:?}", method);
+    if method == Disposal::Background && partial_frames > 0 {
+        log::info!(
+            "   Warning: {} partial frames may leave blank areas with Background disposal",
+            partial_frames
+        );
+    }
+
+    gif.set_uniform_disposal(method.into());
+
+    // Save with the new disposal methods
+    let options = EncodeOptions {
+        preserve_disposal: true,
+        ..EncodeOptions::default()
+    };
+    io::atomic_write(output, |path| gif.to_file_with_options(path, options))
+        .context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_uniform_disposal() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("set_disposal.gif");
+        let output = output.to_str().unwrap();
+
+        for method in [Disposal::Background, Disposal::Keep] {
+            run("tests/fixtures/colorful.gif", output, method).unwrap();
+            let gif = Gif::from_file(output).unwrap();
+
+            assert_eq!(gif.frames.len(), 10);
+            assert!(gif
+                .frames
+                .iter()
+                .all(|f| f.disposal == DisposalMethod::from(method)));
+        }
+    }
+}
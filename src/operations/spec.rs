@@ -0,0 +1,134 @@
+//! Machine-readable descriptions of the operations front ends can run
+//!
+//! The desktop UI builds its forms from [`OPERATIONS`] instead of hardcoding
+//! each operation, and the tests check every entry against the CLI so both
+//! stay in sync.
+
+/// Type of value an operation parameter takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Floating-point number
+    Float,
+    /// Whole number
+    Integer,
+}
+
+/// One parameter of an operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    /// Parameter name, as used by the CLI flag and the GUI command
+    pub name: &'static str,
+    /// Short human-readable description
+    pub description: &'static str,
+    /// Value type
+    pub kind: ParamKind,
+    /// Whether a value must be given
+    pub required: bool,
+    /// Smallest value to offer, if bounded
+    pub min: Option<f64>,
+    /// Largest value to offer, if bounded
+    pub max: Option<f64>,
+    /// Value suggested when nothing is chosen yet
+    pub default: Option<f64>,
+}
+
+/// An operation and the parameters it takes besides input and output paths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperationSpec {
+    /// Operation name, matching its CLI subcommand
+    pub name: &'static str,
+    /// Short human-readable description
+    pub description: &'static str,
+    /// Operation-specific parameters
+    pub params: &'static [ParamSpec],
+}
+
+/// Operations available to the desktop UI
+pub const OPERATIONS: &[OperationSpec] = &[
+    OperationSpec {
+        name: "speed",
+        description: "Adjust GIF playback speed",
+        params: &[ParamSpec {
+            name: "factor",
+            description: "Speed factor (2.0 plays twice as fast, 0.5 half as fast)",
+            kind: ParamKind::Float,
+            required: true,
+            min: Some(0.1),
+            max: Some(10.0),
+            default: Some(2.0),
+        }],
+    },
+    OperationSpec {
+        name: "compress",
+        description: "Compress GIF file size",
+        params: &[ParamSpec {
+            name: "percent",
+            description: "Target size as a percentage of the original",
+            kind: ParamKind::Integer,
+            required: true,
+            min: Some(1.0),
+            max: Some(99.0),
+            default: Some(50.0),
+        }],
+    },
+    OperationSpec {
+        name: "tune",
+        description: "Resize the GIF (give one side to keep the aspect ratio)",
+        params: &[
+            ParamSpec {
+                name: "width",
+                description: "New width in pixels",
+                kind: ParamKind::Integer,
+                required: false,
+                min: Some(1.0),
+                max: Some(u16::MAX as f64),
+                default: None,
+            },
+            ParamSpec {
+                name: "height",
+                description: "New height in pixels",
+                kind: ParamKind::Integer,
+                required: false,
+                min: Some(1.0),
+                max: Some(u16::MAX as f64),
+                default: None,
+            },
+        ],
+    },
+];
+
+/// Look up an operation by name
+pub fn find(name: &str) -> Option<&'static OperationSpec> {
+    OPERATIONS.iter().find(|spec| spec.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_specs_match_cli() {
+        let command = Args::command();
+
+        for spec in OPERATIONS {
+            let subcommand = command
+                .find_subcommand(spec.name)
+                .unwrap_or_else(|| panic!("No CLI subcommand for '{}'", spec.name));
+
+            for param in spec.params {
+                let arg = subcommand
+                    .get_arguments()
+                    .find(|arg| arg.get_id() == param.name)
+                    .unwrap_or_else(|| panic!("No '{}' flag on '{}'", param.name, spec.name));
+                assert_eq!(arg.is_required_set(), param.required, "{}", param.name);
+            }
+        }
+
+        let speed = find("speed").unwrap();
+        assert_eq!(speed.params[0].kind, ParamKind::Float);
+        assert_eq!(find("compress").unwrap().params[0].max, Some(99.0));
+        assert_eq!(find("tune").unwrap().params.len(), 2);
+    }
+}
@@ -1,6 +1,31 @@
-use crate::core::Gif;
+use crate::core::{warn_if_tagged, EncodeOptions, Gif};
+use crate::io;
+use crate::utils::StageTimer;
 use anyhow::{Context, Result};
 
+/// Optional behavior for [`run_with_options`]
+#[derive(Debug, Clone)]
+pub struct SpeedOptions {
+    /// Allow dropping frames for extreme speedups (> 4x); when false only
+    /// delays are scaled and every frame is kept
+    pub drop_frames: bool,
+    /// Write frames that were interlaced in the input interlaced again
+    pub preserve_interlace: bool,
+    /// Write 0-delay input frames with a delay of 0 again instead of raising
+    /// them to 1 (see [`EncodeOptions::emit_zero_delay`])
+    pub emit_zero_delay: bool,
+}
+
+impl Default for SpeedOptions {
+    fn default() -> Self {
+        Self {
+            drop_frames: true,
+            preserve_interlace: false,
+            emit_zero_delay: false,
+        }
+    }
+}
+
 /// Adjust GIF playback speed by the given factor
 ///
 /// # Arguments
@@ -15,51 +40,214 @@ use anyhow::{Context, Result};
 /// speed::run("input.gif", "output.gif", 2.0).unwrap();
 /// ```
 pub fn run(input: &str, output: &str, factor: f64) -> Result<()> {
+    run_with_options(input, output, factor, &SpeedOptions::default())
+}
+
+/// Adjust GIF playback speed by the given factor with extra options
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::speed::{self, SpeedOptions};
+///
+/// // Scale delays only, keeping every frame
+/// let options = SpeedOptions {
+///     drop_frames: false,
+///     ..Default::default()
+/// };
+/// speed::run_with_options("input.gif", "output.gif", 8.0, &options).unwrap();
+/// ```
+pub fn run_with_options(
+    input: &str,
+    output: &str,
+    factor: f64,
+    options: &SpeedOptions,
+) -> Result<()> {
     // Validate factor
     if factor <= 0.0 {
         anyhow::bail!("Speed factor must be greater than 0");
     }
 
+    let mut timer = StageTimer::new();
+
     // Load the GIF
-    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    let mut gif = timer
+        .time("decode", || Gif::from_file(input))
+        .context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Speed factor: {:.2}x", factor);
+    log::info!("   Original frames: {}", gif.frames.len());
 
-    println!("   Input file: {}", input);
-    println!("   Speed factor: {:.2}x", factor);
-    println!("   Original frames: {}", gif.frames.len());
+    let original_duration = gif.total_duration();
 
     // Adjust frame delays
-    for frame in &mut gif.frames {
-        let new_delay = (frame.delay as f64 / factor).round() as u16;
-        frame.delay = new_delay.max(1); // Minimum delay is 1 centisecond
-    }
+    timer.time("retime", || gif.scale_delays(factor))?;
 
     // For extreme speedups (> 4.0), consider dropping frames
-    if factor > 4.0 {
-        let frames_to_keep = (gif.frames.len() as f64 / factor).ceil() as usize;
-        let frames_to_keep = frames_to_keep.max(1);
-
-        // Keep every Nth frame
-        let step = (gif.frames.len() as f64 / frames_to_keep as f64).ceil() as usize;
-
-        let mut filtered_frames = Vec::new();
-        for (i, frame) in gif.frames.iter().enumerate() {
-            if i % step == 0 {
-                filtered_frames.push(frame.clone());
-            }
-        }
+    if options.drop_frames && factor > 4.0 {
+        timer.time("drop", || {
+            drop_frames(&mut gif, factor);
+            log::info!("   Frames after dropping: {}", gif.frames.len());
 
-        gif.frames = filtered_frames;
-        println!("   Frames after dropping: {}", gif.frames.len());
+            // Stretch the surviving frames so the total duration still matches the factor
+            let target_duration = (original_duration as f64 / factor).round() as u32;
+            gif.fit_delays_to_duration(target_duration);
+        });
     }
 
     // Save the modified GIF
-    gif.to_file(output).context("Failed to save output GIF")?;
+    let encode_options = EncodeOptions {
+        preserve_interlace: options.preserve_interlace,
+        emit_zero_delay: options.emit_zero_delay,
+        ..EncodeOptions::default()
+    };
+    timer
+        .time("encode", || {
+            io::atomic_write(output, |path| {
+                gif.to_file_with_options(path, encode_options)
+            })
+        })
+        .context("Failed to save output GIF")?;
+    timer.report(gif.frames.len());
+
+    Ok(())
+}
+
+/// Ramp playback speed linearly from `start_factor` to `end_factor`
+///
+/// Frame `i` of `n` has its delay divided by the factor interpolated at
+/// `i / (n - 1)`, so a ramp from 0.5 to 2.0 starts at half speed and ends
+/// at double speed. Rounding to centiseconds is spread across frames (see
+/// [`Gif::quantize_delays_preserving_total`]); no frames are dropped.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `start_factor` - Speed multiplier for the first frame
+/// * `end_factor` - Speed multiplier for the last frame
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::speed;
+///
+/// // Start slow, end fast
+/// speed::run_ramp("input.gif", "output.gif", 0.5, 2.0).unwrap();
+/// ```
+pub fn run_ramp(input: &str, output: &str, start_factor: f64, end_factor: f64) -> Result<()> {
+    // Validate factors
+    let valid = |factor: f64| factor > 0.0 && factor.is_finite();
+    if !(valid(start_factor) && valid(end_factor)) {
+        anyhow::bail!("Speed factors must be greater than 0");
+    }
+
+    let mut timer = StageTimer::new();
+
+    // Load the GIF
+    let mut gif = timer
+        .time("decode", || Gif::from_file(input))
+        .context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Speed ramp: {:.2}x -> {:.2}x", start_factor, end_factor);
+    log::info!("   Frames: {}", gif.frames.len());
+
+    // Scale each delay by its interpolated factor
+    let last = gif.frames.len().saturating_sub(1).max(1) as f64;
+    let intended: Vec<f64> = gif
+        .frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let factor = start_factor + (end_factor - start_factor) * index as f64 / last;
+            frame.delay as f64 / factor
+        })
+        .collect();
+    timer.time("retime", || gif.quantize_delays_preserving_total(&intended))?;
+    log::info!("   New duration: {} centiseconds", gif.total_duration());
+
+    // Save the ramped GIF
+    timer
+        .time("encode", || {
+            io::atomic_write(output, |path| gif.to_file(path))
+        })
+        .context("Failed to save output GIF")?;
+    timer.report(gif.frames.len());
+
+    Ok(())
+}
+
+/// Keep every Nth frame so roughly `1 / factor` of the frames remain
+///
+/// Returns the warnings logged for dropped frames that carried tags.
+fn drop_frames(gif: &mut Gif, factor: f64) -> Vec<String> {
+    let frames_to_keep = (gif.frames.len() as f64 / factor).ceil() as usize;
+    let frames_to_keep = frames_to_keep.max(1);
+    let step = (gif.frames.len() as f64 / frames_to_keep as f64).ceil() as usize;
+
+    let mut warnings = Vec::new();
+    let frames = std::mem::take(&mut gif.frames);
+    for (i, frame) in frames.into_iter().enumerate() {
+        if i % step == 0 {
+            gif.frames.push(frame);
+        } else {
+            warnings.extend(warn_if_tagged(&frame, i, "speed-up frame dropping"));
+        }
+    }
+
+    warnings
+}
+
+/// Give every frame the same delay, regardless of the original timing
+///
+/// Unlike [`run`], which scales the existing delays, this overwrites them,
+/// so frames that were held longer lose their pause. No frames are dropped.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `delay_cs` - Delay for every frame in 10ms units (at least 1)
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::speed;
+///
+/// // Play every frame for 50ms
+/// speed::set_uniform_delay("input.gif", "output.gif", 5).unwrap();
+/// ```
+pub fn set_uniform_delay(input: &str, output: &str, delay_cs: u16) -> Result<()> {
+    if delay_cs == 0 {
+        anyhow::bail!("Frame delay must be at least 1 centisecond");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!(
+        "   Original duration: {} centiseconds",
+        gif.total_duration()
+    );
+
+    for frame in &mut gif.frames {
+        frame.delay = delay_cs;
+        frame.original_delay = delay_cs;
+    }
+    log::info!("   New duration: {} centiseconds", gif.total_duration());
+
+    // Save the retimed GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_validate_factor() {
         // Test that factor validation works (factor > 0)
@@ -69,4 +257,143 @@ mod tests {
             assert!(factor > 0.0, "Factor {} should be positive", factor);
         }
     }
+
+    #[test]
+    fn test_empty_gif_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("speed_empty.gif");
+        let input = input.to_str().unwrap();
+        let output = dir.path().join("speed_empty_out.gif");
+        let output = output.to_str().unwrap();
+
+        // A valid file with a screen but no image data decodes to zero frames
+        let mut empty = Gif::new();
+        empty.width = 10;
+        empty.height = 10;
+        empty.to_file(input).unwrap();
+        assert!(Gif::from_file(input).unwrap().frames.is_empty());
+
+        let error = run(input, output, 2.0).unwrap_err();
+
+        assert!(format!("{:#}", error).contains("no frames"), "{:#}", error);
+        assert!(!std::path::Path::new(output).exists());
+    }
+
+    #[test]
+    fn test_dropping_tagged_frame_warns() {
+        let mut gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        gif.frames[1].tags.push("keyframe".to_string());
+        gif.frames[2].tags.push("ending".to_string());
+
+        // 10 frames at 2x keeps every second frame
+        let warnings = drop_frames(&mut gif, 2.0);
+        assert_eq!(gif.frame_count(), 5);
+        assert_eq!(
+            warnings,
+            vec!["speed-up frame dropping removed frame 1 tagged keyframe"]
+        );
+    }
+
+    #[test]
+    fn test_fractional_speed_keeps_exact_total() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("speed_fractional.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, 1.5).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        // 10 frames of 10cs each: 100 / 1.5 = 66.7, not 10 * round(6.67) = 70
+        assert_eq!(result.frame_count(), 10);
+        assert_eq!(result.total_duration(), 67);
+    }
+
+    #[test]
+    fn test_speed_ramp() {
+        let input = "tests/fixtures/high_fps.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("speed_ramp.gif");
+        let output = output.to_str().unwrap();
+
+        let original = Gif::from_file(input).unwrap();
+        run_ramp(input, output, 0.5, 2.0).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        // Uniform 3cs delays: slowed down at the start, sped up at the end
+        assert!(original.frames.iter().all(|f| f.delay == 3));
+        assert_eq!(result.frame_count(), original.frame_count());
+        assert_eq!(result.frames[0].delay, 6);
+        assert!(result.frames[..5].iter().all(|f| f.delay > 3));
+        assert!(result.frames[25..].iter().all(|f| f.delay < 3));
+
+        assert!(run_ramp(input, output, 0.0, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_set_uniform_delay() {
+        let dir = tempfile::tempdir().unwrap();
+        let ramped = dir.path().join("set_delay_ramped.gif");
+        let ramped = ramped.to_str().unwrap();
+        let output = dir.path().join("set_delay.gif");
+        let output = output.to_str().unwrap();
+
+        // Start from variable delays
+        run_ramp("tests/fixtures/colorful.gif", ramped, 0.5, 3.0).unwrap();
+        let variable = Gif::from_file(ramped).unwrap();
+        assert!(variable
+            .frames
+            .iter()
+            .any(|f| f.delay != variable.frames[0].delay));
+
+        set_uniform_delay(ramped, output, 5).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        assert_eq!(result.frame_count(), variable.frame_count());
+        assert!(result.frames.iter().all(|f| f.delay == 5));
+
+        assert!(set_uniform_delay(ramped, output, 0).is_err());
+    }
+
+    #[test]
+    fn test_speed_without_dropping_frames() {
+        let input = "tests/fixtures/high_fps.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("speed_no_drop.gif");
+        let output = output.to_str().unwrap();
+
+        let original = Gif::from_file(input).unwrap();
+        let options = SpeedOptions {
+            drop_frames: false,
+            ..Default::default()
+        };
+        run_with_options(input, output, 8.0, &options).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        assert_eq!(result.frame_count(), original.frame_count());
+        assert!(result.frames.iter().all(|f| f.delay == 1));
+    }
+
+    #[test]
+    fn test_speed_dropping_frames_preserves_duration() {
+        let input = "tests/fixtures/high_fps.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("speed_drop.gif");
+        let output = output.to_str().unwrap();
+
+        let original = Gif::from_file(input).unwrap();
+        run(input, output, 10.0).unwrap();
+        let result = Gif::from_file(output).unwrap();
+
+        assert!(result.frame_count() < original.frame_count());
+
+        let expected = original.total_duration() as f64 / 10.0;
+        let actual = result.total_duration() as f64;
+        assert!(
+            (actual - expected).abs() <= 1.0,
+            "expected ~{} centiseconds, got {}",
+            expected,
+            actual
+        );
+    }
 }
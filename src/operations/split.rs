@@ -0,0 +1,145 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Split a GIF into one PNG per frame
+///
+/// Frames are composited first, so each PNG shows the full image at that
+/// point of the animation. Files are written as `frame_001.png`,
+/// `frame_002.png` and so on inside `output_dir`, which is created if needed.
+///
+/// With `indexed` set and a global palette present, the PNGs are
+/// palette-indexed using the GIF's global palette (smaller, and colors stay
+/// palette-accurate); fully transparent pixels get an extra palette entry
+/// marked transparent. Otherwise, or without a global palette, RGBA PNGs are
+/// written.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_dir` - Directory for the PNG files
+/// * `indexed` - Write palette-indexed PNGs when a global palette exists
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::split;
+///
+/// // Writes frames/frame_001.png, frames/frame_002.png, ...
+/// split::run("input.gif", "frames", true).unwrap();
+/// ```
+pub fn run(input: &str, output_dir: &str, indexed: bool) -> Result<()> {
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    let palette = gif.global_palette.clone().filter(|_| indexed);
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    match &palette {
+        Some(palette) => log::info!("   Format: indexed ({} colors)", palette.len()),
+        None if indexed => log::info!("   Format: RGBA (no global palette to index with)"),
+        None => log::info!("   Format: RGBA"),
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", output_dir))?;
+
+    // Every PNG shows the full image at that point of the animation
+    gif.normalize()?;
+
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        frame.unpremultiply();
+        let path = frame_path(output_dir, index);
+        let path = path.to_str().context("Output path is not valid UTF-8")?;
+
+        io::atomic_write(path, |temp_path| match &palette {
+            Some(palette) => write_indexed_png(frame, palette, Path::new(temp_path)),
+            None => {
+                frame
+                    .to_image_buffer()
+                    .save_with_format(temp_path, image::ImageFormat::Png)?;
+                Ok(())
+            }
+        })
+        .with_context(|| format!("Failed to save frame: {}", path))?;
+    }
+
+    log::info!("   Wrote {} PNG files", gif.frames.len());
+
+    Ok(())
+}
+
+/// Path of the PNG for the frame at 0-based `index`
+fn frame_path(output_dir: &str, index: usize) -> std::path::PathBuf {
+    Path::new(output_dir).join(format!("frame_{:03}.png", index + 1))
+}
+
+/// Write a full-canvas, unpremultiplied frame as a palette-indexed PNG
+fn write_indexed_png(frame: &Frame, palette: &[[u8; 3]], path: &Path) -> Result<()> {
+    let (indices, transparent) = frame.to_indexed(palette);
+
+    let mut colors: Vec<[u8; 3]> = palette.iter().take(256).copied().collect();
+    if let Some(index) = transparent {
+        colors.truncate(index as usize);
+        colors.push([0, 0, 0]);
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file),
+        frame.width as u32,
+        frame.height as u32,
+    );
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(colors.concat());
+    if let Some(index) = transparent {
+        let mut alphas = vec![255u8; index as usize];
+        alphas.push(0);
+        encoder.set_trns(alphas);
+    }
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&indices)?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_indexed() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_dir = dir.path().join("split_indexed");
+        let output_dir = output_dir.to_str().unwrap();
+
+        run("tests/fixtures/simple.gif", output_dir, true).unwrap();
+        let gif = Gif::from_file("tests/fixtures/simple.gif").unwrap();
+        let palette_size = gif.global_palette.as_ref().unwrap().len();
+
+        for index in 0..gif.frames.len() {
+            let path = frame_path(output_dir, index);
+            let decoder = png::Decoder::new(std::fs::File::open(&path).unwrap());
+            let reader = decoder.read_info().unwrap();
+            let info = reader.info();
+
+            assert_eq!(info.color_type, png::ColorType::Indexed);
+            assert_eq!(info.palette.as_ref().unwrap().len(), palette_size * 3);
+            assert_eq!((info.width, info.height), (100, 100));
+        }
+
+        // The indexed frames still decode to the original colors
+        let first = image::open(frame_path(output_dir, 0)).unwrap().to_rgba8();
+        let second = image::open(frame_path(output_dir, 1)).unwrap().to_rgba8();
+        assert_eq!(first.get_pixel(50, 50).0, [255, 0, 0, 255]);
+        assert_eq!(second.get_pixel(50, 50).0, [0, 0, 255, 255]);
+
+        // Only the finished PNGs are left, no temporary files
+        let entries = std::fs::read_dir(output_dir).unwrap().count();
+        assert_eq!(entries, gif.frames.len());
+    }
+}
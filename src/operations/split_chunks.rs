@@ -0,0 +1,79 @@
+use crate::core::Gif;
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Split a long GIF into several shorter GIFs, e.g. for platforms that cap
+/// frame counts or file sizes
+///
+/// Frames are composited first so every chunk starts with a complete image.
+/// Chunks are written as `<output_prefix>_001.gif`, `<output_prefix>_002.gif`
+/// and so on, each keeping the input's size, timing and loop count.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output_prefix` - Path prefix for the chunk files
+/// * `frames_per_chunk` - Maximum number of frames in each chunk
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::split_chunks;
+///
+/// // Writes part_001.gif, part_002.gif, ...
+/// split_chunks::run("input.gif", "part", 50).unwrap();
+/// ```
+pub fn run(input: &str, output_prefix: &str, frames_per_chunk: usize) -> Result<()> {
+    if frames_per_chunk == 0 {
+        anyhow::bail!("Frames per chunk must be at least 1");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+    log::info!("   Frames per chunk: {}", frames_per_chunk);
+
+    // A chunk cannot rely on frames left on screen by the previous chunk
+    gif.normalize()?;
+
+    let chunks = gif.split_into_chunks(frames_per_chunk);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let path = chunk_path(output_prefix, index);
+        io::atomic_write(&path, |temp| chunk.to_file(temp))
+            .with_context(|| format!("Failed to save chunk: {}", path))?;
+        log::info!("   Wrote: {} ({} frames)", path, chunk.frames.len());
+    }
+
+    Ok(())
+}
+
+/// File name of the chunk at 0-based `index`
+fn chunk_path(output_prefix: &str, index: usize) -> String {
+    format!("{}_{:03}.gif", output_prefix, index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_into_chunk_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("split_chunks");
+        let prefix = prefix.to_str().unwrap();
+
+        run("tests/fixtures/colorful.gif", prefix, 4).unwrap();
+        let sizes: Vec<usize> = (0..3)
+            .map(|index| {
+                let path = chunk_path(prefix, index);
+                let chunk = Gif::from_file(&path).unwrap();
+                chunk.frames.len()
+            })
+            .collect();
+
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert!(!std::path::Path::new(&chunk_path(prefix, 3)).exists());
+        assert!(run("tests/fixtures/colorful.gif", prefix, 0).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::core::{Frame, Gif};
+use crate::io;
+use anyhow::{Context, Result};
+
+/// Glyphs of the built-in 3x5 pixel font, one bit per pixel (MSB = left)
+const GLYPHS: [(char, [u8; 5]); 14] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('#', [0b101, 0b111, 0b101, 0b111, 0b101]),
+    ('.', [0b000, 0b000, 0b000, 0b000, 0b010]),
+    ('s', [0b000, 0b011, 0b110, 0b011, 0b110]),
+    (' ', [0b000, 0b000, 0b000, 0b000, 0b000]),
+];
+
+/// Size of each font pixel on screen
+const SCALE: usize = 2;
+
+/// Distance of the text from the top-left corner of the canvas
+const MARGIN: usize = 2;
+
+/// Burn a frame counter and timestamp into the corner of every frame
+///
+/// Each composited frame gets `#<index> <t.tt>s` (the frame number and the
+/// time it appears) drawn in white with a black outline in the top-left
+/// corner, so it stays legible on any background. Useful for QA reviews
+/// where frames need to be referred to exactly.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output GIF file
+/// * `show_index` - Include the frame number
+/// * `show_time` - Include the time the frame appears, in seconds
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::stamp;
+///
+/// stamp::run("input.gif", "stamped.gif", true, true).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, show_index: bool, show_time: bool) -> Result<()> {
+    if !show_index && !show_time {
+        anyhow::bail!("Nothing to stamp: enable the frame index, the time or both");
+    }
+
+    // Load the GIF
+    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Frames: {}", gif.frames.len());
+
+    // Stamps are drawn in canvas coordinates
+    gif.normalize()?;
+
+    let mut elapsed_cs = 0u32;
+    for (index, frame) in gif.frames.iter_mut().enumerate() {
+        let label = stamp_label(index, elapsed_cs, show_index, show_time);
+        elapsed_cs += frame.delay as u32;
+        draw_label(frame, &label);
+    }
+
+    // Save the stamped GIF
+    io::atomic_write(output, |path| gif.to_file(path)).context("Failed to save output GIF")?;
+
+    Ok(())
+}
+
+/// Build the text for one frame, e.g. `#3 0.30s`
+fn stamp_label(index: usize, elapsed_cs: u32, show_index: bool, show_time: bool) -> String {
+    let mut parts = Vec::new();
+    if show_index {
+        parts.push(format!("#{}", index));
+    }
+    if show_time {
+        parts.push(format!("{}.{:02}s", elapsed_cs / 100, elapsed_cs % 100));
+    }
+    parts.join(" ")
+}
+
+/// Draw white text with a black outline onto a full-canvas frame
+fn draw_label(frame: &mut Frame, label: &str) {
+    frame.unpremultiply();
+
+    // Rasterize the text into a mask with a 1-pixel border for the outline
+    let mask_width = label.chars().count() * 4 * SCALE + 2;
+    let mask_height = 5 * SCALE + 2;
+    let mut mask = vec![false; mask_width * mask_height];
+    for (position, c) in label.chars().enumerate() {
+        let Some((_, rows)) = GLYPHS.iter().find(|(glyph, _)| *glyph == c) else {
+            continue;
+        };
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        let x = 1 + (position * 4 + column) * SCALE + dx;
+                        let y = 1 + row * SCALE + dy;
+                        mask[y * mask_width + x] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let text_at = |x: i64, y: i64| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < mask_width
+            && (y as usize) < mask_height
+            && mask[y as usize * mask_width + x as usize]
+    };
+
+    let width = frame.width as usize;
+    for my in 0..mask_height {
+        for mx in 0..mask_width {
+            let (x, y) = (MARGIN + mx, MARGIN + my);
+            if x >= width || y >= frame.height as usize {
+                continue;
+            }
+
+            let (mx, my) = (mx as i64, my as i64);
+            let color = if text_at(mx, my) {
+                [255, 255, 255, 255]
+            } else if (-1..=1).any(|dy| (-1..=1).any(|dx| text_at(mx + dx, my + dy))) {
+                [0, 0, 0, 255]
+            } else {
+                continue;
+            };
+
+            let index = (y * width + x) * 4;
+            frame.data[index..index + 4].copy_from_slice(&color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_label() {
+        assert_eq!(stamp_label(3, 30, true, true), "#3 0.30s");
+        assert_eq!(stamp_label(12, 1234, false, true), "12.34s");
+        assert_eq!(stamp_label(0, 0, true, false), "#0");
+        for c in "#0123456789 .s".chars() {
+            assert!(GLYPHS.iter().any(|(glyph, _)| *glyph == c));
+        }
+    }
+
+    #[test]
+    fn test_stamp_colorful() {
+        let input = "tests/fixtures/colorful.gif";
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("stamp.gif");
+        let output = output.to_str().unwrap();
+
+        run(input, output, true, true).unwrap();
+        let mut stamped = Gif::from_file(output).unwrap();
+        stamped.normalize().unwrap();
+
+        let mut original = Gif::from_file(input).unwrap();
+        original.normalize().unwrap();
+
+        let region = |frame: &Frame, x0: usize, y0: usize, size: usize| -> Vec<u8> {
+            (y0..y0 + size)
+                .flat_map(|y| {
+                    let start = (y * frame.width as usize + x0) * 4;
+                    frame.data[start..start + size * 4].to_vec()
+                })
+                .collect()
+        };
+
+        assert_eq!(stamped.frames.len(), original.frames.len());
+        for (before, after) in original.frames.iter().zip(&stamped.frames) {
+            // The corner carries the stamp, the center is untouched
+            assert_ne!(region(before, 0, 0, 16), region(after, 0, 0, 16));
+            assert_eq!(region(before, 80, 80, 40), region(after, 80, 80, 40));
+        }
+    }
+}
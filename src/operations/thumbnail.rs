@@ -0,0 +1,116 @@
+use crate::core::Gif;
+use crate::io;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Output format of a thumbnail, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ThumbnailFormat {
+    Png,
+    Gif,
+}
+
+impl ThumbnailFormat {
+    /// Pick the format from the output path's extension
+    fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("png") => Ok(Self::Png),
+            Some("gif") => Ok(Self::Gif),
+            _ => anyhow::bail!(
+                "Unsupported thumbnail format for '{}', expected a .png or .gif file",
+                path
+            ),
+        }
+    }
+}
+
+/// Write a small single-frame preview, e.g. for gallery pages
+///
+/// The frame is composited first and scaled so its longer side is `size`
+/// pixels (see [`Gif::thumbnail`]). The output format follows the file
+/// extension: `.png` keeps smooth alpha edges, while `.gif` writes a static
+/// one-frame GIF with alpha snapped to on/off.
+///
+/// # Arguments
+/// * `input` - Path to input GIF file
+/// * `output` - Path to output `.png` or `.gif` file
+/// * `size` - Length of the thumbnail's longer side in pixels
+/// * `frame` - 0-based index of the frame to use
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::thumbnail;
+///
+/// thumbnail::run("input.gif", "thumb.png", 128, 0).unwrap();
+/// ```
+pub fn run(input: &str, output: &str, size: u16, frame: usize) -> Result<()> {
+    let format = ThumbnailFormat::from_path(output)?;
+
+    // Load the GIF
+    let gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
+
+    log::info!("   Input file: {}", input);
+    log::info!("   Original size: {}x{}", gif.width, gif.height);
+
+    let mut thumb = gif.thumbnail(size, frame)?;
+    log::info!("   Thumbnail size: {}x{}", thumb.width, thumb.height);
+
+    match format {
+        ThumbnailFormat::Png => io::atomic_write(output, |path| {
+            thumb
+                .to_image_buffer()
+                .save_with_format(path, image::ImageFormat::Png)?;
+            Ok(())
+        }),
+        ThumbnailFormat::Gif => {
+            // GIF transparency is on/off
+            for pixel in thumb.data.chunks_exact_mut(4) {
+                pixel[3] = if pixel[3] < 128 { 0 } else { 255 };
+            }
+            thumb.transparent = thumb.data.chunks_exact(4).any(|pixel| pixel[3] == 0);
+            let still = Gif::builder().add_frame(thumb).build()?;
+            io::atomic_write(output, |path| still.to_file(path))
+        }
+    }
+    .context("Failed to save thumbnail")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_thumbnail_of_large_gif() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("thumbnail.png");
+        let output = output.to_str().unwrap();
+
+        run("tests/fixtures/large.gif", output, 64, 0).unwrap();
+        let thumb = image::open(output).unwrap();
+
+        assert_eq!(thumb.width().max(thumb.height()), 64);
+        assert_eq!((thumb.width(), thumb.height()), (64, 48));
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ThumbnailFormat::from_path("a/thumb.PNG").unwrap(),
+            ThumbnailFormat::Png
+        );
+        assert_eq!(
+            ThumbnailFormat::from_path("thumb.gif").unwrap(),
+            ThumbnailFormat::Gif
+        );
+        assert!(ThumbnailFormat::from_path("thumb.jpg").is_err());
+        assert!(ThumbnailFormat::from_path("thumb").is_err());
+    }
+}
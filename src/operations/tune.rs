@@ -1,97 +1,27 @@
-use crate::core::{Frame, Gif};
+use crate::core::{resize_frame, EncodeOptions, Gif};
+use crate::io;
+use crate::utils::StageTimer;
 use anyhow::{Context, Result};
-use gif::DisposalMethod;
-use image::imageops::FilterType;
-use image::{ImageBuffer, Rgba};
 
-/// Normalize frames by compositing them with proper disposal handling
-///
-/// This is critical for GIFs with partial frames and Keep disposal.
-/// Each frame must be composited onto the previous frame's result.
-fn normalize_frames_composited(gif: &mut Gif) -> Result<()> {
-    if gif.frames.is_empty() {
-        return Ok(());
-    }
-
-    let full_frame_size = (gif.width as usize) * (gif.height as usize) * 4;
-
-    // Check if any frame needs normalization
-    let needs_normalization = gif.frames.iter().any(|f| f.data.len() < full_frame_size);
-
-    if !needs_normalization {
-        return Ok(());
-    }
-
-    println!("   Normalizing frames with composite disposal handling...");
-
-    // Get background color (transparent black by default for GIFs)
-    let mut canvas: Vec<u8> = vec![0; full_frame_size]; // Start with transparent black
-
-    for (i, frame) in gif.frames.iter_mut().enumerate() {
-        // Save current canvas state for disposal handling
-        let previous_canvas = canvas.clone();
-
-        // If this is a partial frame, composite it onto the canvas
-        if frame.data.len() < full_frame_size {
-            let frame_stride = (frame.width as usize) * 4;
-            let gif_stride = (gif.width as usize) * 4;
-
-            // Calculate offset to center the partial frame
-            let offset_x = ((gif.width - frame.width) / 2) as usize;
-            let offset_y = ((gif.height - frame.height) / 2) as usize;
-
-            for y in 0..(frame.height as usize) {
-                let frame_row_start = y * frame_stride;
-                let canvas_row_start = (offset_y * gif_stride) + (y * gif_stride);
-                let canvas_row_start_with_x = canvas_row_start + (offset_x * 4);
-
-                for x in 0..(frame.width as usize) {
-                    let pixel_offset = x * 4;
-                    let src_alpha = frame.data[frame_row_start + pixel_offset + 3];
-
-                    if src_alpha > 0 {
-                        // Composite pixel onto canvas (simple replace for now)
-                        for c in 0..4 {
-                            canvas[canvas_row_start_with_x + pixel_offset + c] =
-                                frame.data[frame_row_start + pixel_offset + c];
-                        }
-                    }
-                }
-            }
-
-            // Update frame with composited result
-            frame.data = canvas.clone();
-            frame.width = gif.width;
-            frame.height = gif.height;
-        } else {
-            // Full frame, replace canvas
-            canvas = frame.data.clone();
-        }
-
-        // Handle disposal for next frame
-        match frame.disposal {
-            DisposalMethod::Keep => {
-                // Keep current canvas for next frame (nothing to do)
-            }
-            DisposalMethod::Background => {
-                // Restore to background (transparent black)
-                canvas = vec![0; full_frame_size];
-            }
-            DisposalMethod::Previous => {
-                // Restore to previous state
-                canvas = previous_canvas;
-            }
-            _ => {
-                // Any/Other - treat as Keep
-            }
-        }
-    }
-
-    Ok(())
+/// Optional behavior for [`run_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct TuneOptions {
+    /// Scale each stored frame by the same factor instead of compositing
+    /// first
+    ///
+    /// Keeps each frame's relative offset, size and disposal; this preserves
+    /// the smaller partial frames of optimized GIFs.
+    pub independent: bool,
 }
 
 /// Tune GIF parameters (resize, crop, etc.)
 ///
+/// GIF dimensions are 16-bit, so target sizes above 65535 pixels (given or
+/// derived from the aspect ratio) are rejected.
+///
+/// Frames are composited first, so every output frame covers the whole
+/// canvas (see [`TuneOptions::independent`] for the alternative).
+///
 /// # Arguments
 /// * `input` - Path to input GIF file
 /// * `output` - Path to output GIF file
@@ -109,13 +39,37 @@ fn normalize_frames_composited(gif: &mut Gif) -> Result<()> {
 /// tune::run("input.gif", "output.gif", Some(400), None).unwrap();
 /// ```
 pub fn run(input: &str, output: &str, width: Option<u32>, height: Option<u32>) -> Result<()> {
+    run_with_options(input, output, width, height, &TuneOptions::default())
+}
+
+/// Tune GIF parameters with extra options
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::operations::tune::{self, TuneOptions};
+///
+/// let options = TuneOptions { independent: true };
+/// tune::run_with_options("input.gif", "output.gif", Some(400), None, &options).unwrap();
+/// ```
+pub fn run_with_options(
+    input: &str,
+    output: &str,
+    width: Option<u32>,
+    height: Option<u32>,
+    options: &TuneOptions,
+) -> Result<()> {
     // Validate at least one dimension is specified
     if width.is_none() && height.is_none() {
         anyhow::bail!("At least one dimension (width or height) must be specified");
     }
 
+    let mut timer = StageTimer::new();
+
     // Load the GIF
-    let mut gif = Gif::from_file(input).context("Failed to load input GIF")?;
+    let mut gif = timer
+        .time("decode", || Gif::from_file(input))
+        .context("Failed to load input GIF")?;
+    gif.ensure_nonempty()?;
 
     let original_width = gif.width as u32;
     let original_height = gif.height as u32;
@@ -135,40 +89,89 @@ pub fn run(input: &str, output: &str, width: Option<u32>, height: Option<u32>) -
         _ => unreachable!(),
     };
 
-    println!("   Input file: {}", input);
-    println!("   Original size: {}x{}", original_width, original_height);
-    println!("   Target size: {}x{}", new_width, new_height);
+    log::info!("   Input file: {}", input);
+    log::info!("   Original size: {}x{}", original_width, original_height);
+    log::info!("   Target size: {}x{}", new_width, new_height);
 
     // Ensure new dimensions are valid
     if new_width == 0 || new_height == 0 {
         anyhow::bail!("Invalid target dimensions: {}x{}", new_width, new_height);
     }
+    let new_width = to_gif_dimension(new_width, "width")?;
+    let new_height = to_gif_dimension(new_height, "height")?;
+
+    if options.independent {
+        // Scale every frame's rectangle; disposal keeps them stacking as before
+        let scale_x = new_width as f64 / original_width as f64;
+        let scale_y = new_height as f64 / original_height as f64;
+        timer.time("resize", || {
+            gif.par_map_frames(|frame| {
+                let (left, width) = scale_span(frame.left, frame.width, scale_x, new_width);
+                let (top, height) = scale_span(frame.top, frame.height, scale_y, new_height);
+                resize_frame(frame, width as u32, height as u32, true);
+                frame.left = left;
+                frame.top = top;
+            })
+        });
+    } else {
+        // CRITICAL: Normalize frames BEFORE resizing
+        // This ensures partial frames are properly composited
+        timer.time("composite", || gif.normalize())?;
 
-    // CRITICAL: Normalize frames BEFORE resizing
-    // This ensures partial frames are properly composited
-    normalize_frames_composited(&mut gif)?;
-
-    // Resize all frames
-    for frame in &mut gif.frames {
-        let img_buffer = frame.to_image_buffer();
-        // Use Triangle filter for smoother edges without ringing artifacts
-        let resized =
-            image::imageops::resize(&img_buffer, new_width, new_height, FilterType::Triangle);
-        frame.update_from_image_buffer(&resized);
+        // Resize all frames
+        timer.time("resize", || {
+            gif.par_map_frames(|frame| {
+                resize_frame(frame, new_width as u32, new_height as u32, true)
+            })
+        });
     }
 
     // Update GIF dimensions
-    gif.width = new_width as u16;
-    gif.height = new_height as u16;
+    gif.width = new_width;
+    gif.height = new_height;
 
     // Save the modified GIF
-    gif.to_file(output).context("Failed to save output GIF")?;
+    let options = EncodeOptions {
+        preserve_disposal: options.independent,
+        ..EncodeOptions::default()
+    };
+    timer
+        .time("encode", || {
+            io::atomic_write(output, |path| gif.to_file_with_options(path, options))
+        })
+        .context("Failed to save output GIF")?;
+    timer.report(gif.frames.len());
 
     Ok(())
 }
 
+/// Scale a frame's offset and length along one axis, staying on the canvas
+///
+/// Both edges are scaled and rounded so neighbouring frames still line up.
+/// The result is at least one pixel long.
+fn scale_span(offset: u16, length: u16, scale: f64, canvas: u16) -> (u16, u16) {
+    let start = ((offset as f64 * scale).round() as u16).min(canvas - 1);
+    let end = (((offset as f64 + length as f64) * scale).round() as u16).min(canvas);
+    (start, end.saturating_sub(start).max(1))
+}
+
+/// Convert a target dimension to the 16-bit size a GIF can store
+pub(crate) fn to_gif_dimension(value: u32, name: &str) -> Result<u16> {
+    u16::try_from(value).map_err(|_| {
+        anyhow::anyhow!(
+            "Target {} {} exceeds the GIF maximum of {} pixels",
+            name,
+            value,
+            u16::MAX
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::core::Frame;
+
     #[test]
     fn test_validate_dimensions() {
         // Test dimension validation logic
@@ -186,4 +189,111 @@ mod tests {
         // Invalid combination
         assert!(!(no_width.is_some() || no_height.is_some()));
     }
+
+    #[test]
+    fn test_oversized_target_dimensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("tune_oversized.gif");
+        let output = output.to_str().unwrap();
+
+        let error = run("tests/fixtures/simple.gif", output, Some(70000), None).unwrap_err();
+        let message = format!("{:#}", error);
+        assert!(message.contains("width 70000"), "{}", message);
+        assert!(message.contains("65535"), "{}", message);
+        assert!(!std::path::Path::new(output).exists());
+
+        // A height derived from the aspect ratio is checked too
+        let error = run("tests/fixtures/large.gif", output, None, Some(60000)).unwrap_err();
+        assert!(format!("{:#}", error).contains("width 80000"));
+    }
+
+    #[test]
+    fn test_resize_keeps_transparency() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("tune_transparent_in.gif");
+        let input = input.to_str().unwrap();
+        let output = dir.path().join("tune_transparent_out.gif");
+        let output = output.to_str().unwrap();
+
+        // 16x16 green frame whose top-left 8x8 quadrant is transparent
+        let mut frame = Frame::new(16, 16);
+        for (i, pixel) in frame.data.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (i % 16, i / 16);
+            if x >= 8 || y >= 8 {
+                pixel.copy_from_slice(&[0, 200, 0, 255]);
+            }
+        }
+        frame.transparent = true;
+        let mut gif = Gif::new();
+        gif.add_frame(frame);
+        gif.to_file(input).unwrap();
+
+        run(input, output, Some(8), Some(8)).unwrap();
+        let resized = Gif::from_file(output).unwrap();
+
+        let frame = &resized.frames[0];
+        assert_eq!((frame.width, frame.height), (8, 8));
+        for (i, pixel) in frame.data.chunks_exact(4).enumerate() {
+            let (x, y) = (i % 8, i / 8);
+            if x < 4 && y < 4 {
+                assert_eq!(pixel[3], 0, "({}, {}) should be transparent", x, y);
+            } else {
+                assert_eq!(pixel, [0, 200, 0, 255], "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_frames_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("tune_partial_in.gif");
+        let input = input.to_str().unwrap();
+        let output = dir.path().join("tune_partial_out.gif");
+        let output = output.to_str().unwrap();
+
+        // A full red 40x40 frame, then a 10x10 blue patch at (20, 20)
+        let mut background = Frame::new(40, 40);
+        background
+            .data
+            .chunks_exact_mut(4)
+            .for_each(|p| p.copy_from_slice(&[255, 0, 0, 255]));
+        let mut patch = Frame::new(10, 10);
+        patch
+            .data
+            .chunks_exact_mut(4)
+            .for_each(|p| p.copy_from_slice(&[0, 0, 255, 255]));
+        patch.left = 20;
+        patch.top = 20;
+        let mut gif = Gif::new();
+        gif.add_frame(background);
+        gif.add_frame(patch);
+        gif.set_uniform_disposal(gif::DisposalMethod::Keep);
+        let options = EncodeOptions {
+            preserve_disposal: true,
+            ..EncodeOptions::default()
+        };
+        gif.to_file_with_options(input, options).unwrap();
+
+        let sizes = |independent| {
+            let options = TuneOptions { independent };
+            run_with_options(input, output, Some(20), None, &options).unwrap();
+            let resized = Gif::from_file(output).unwrap();
+            assert_eq!((resized.width, resized.height), (20, 20));
+            resized
+                .frames
+                .iter()
+                .map(|f| (f.left, f.top, f.width, f.height))
+                .collect::<Vec<_>>()
+        };
+        let composited = sizes(false);
+        let independent = sizes(true);
+        let shown = Gif::from_file(output).unwrap();
+
+        assert_eq!(composited, vec![(0, 0, 20, 20), (0, 0, 20, 20)]);
+        assert_eq!(independent, vec![(0, 0, 20, 20), (10, 10, 5, 5)]);
+
+        // The patch still lands on top of the scaled background
+        assert_eq!(shown.pixel_at(1, 12, 12).unwrap(), [0, 0, 255, 255]);
+        assert_eq!(shown.pixel_at(1, 2, 2).unwrap(), [255, 0, 0, 255]);
+    }
 }
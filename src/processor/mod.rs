@@ -0,0 +1,189 @@
+// Reusable, thread-safe processing of in-memory GIFs
+
+use crate::core::{EncodeOptions, Frame, Gif};
+use crate::operations::compress::{self, ColorSpace};
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+/// A filter that changes each frame on its own
+///
+/// Used by [`GifProcessor::filter`] and the stages of
+/// [`Pipeline`](crate::operations::pipeline::Pipeline). Frames may be
+/// filtered in parallel, so implementations must be `Sync`. Any
+/// `Fn(&mut Frame)` closure is a filter.
+pub trait FrameOp: Sync {
+    /// Apply the filter to one full-canvas frame with straight alpha
+    fn apply_frame(&self, frame: &mut Frame);
+}
+
+impl<F: Fn(&mut Frame) + Sync> FrameOp for F {
+    fn apply_frame(&self, frame: &mut Frame) {
+        self(frame)
+    }
+}
+
+/// Processing configuration that can be shared across threads
+///
+/// The processor only holds immutable configuration, so it is `Send + Sync`
+/// and one instance (e.g. in an `Arc`) can serve many threads calling
+/// [`process`](Self::process) at once.
+///
+/// # Example
+/// ```no_run
+/// use gif_toolkit::core::Frame;
+/// use gif_toolkit::processor::GifProcessor;
+/// use std::sync::Arc;
+///
+/// let processor = Arc::new(
+///     GifProcessor::new()
+///         .max_colors(64)
+///         .unwrap()
+///         .merge_duplicates(true)
+///         .filter(|frame: &mut Frame| {
+///             frame.data.chunks_exact_mut(4).for_each(|p| p.swap(0, 2))
+///         }),
+/// );
+///
+/// let input = std::fs::read("input.gif").unwrap();
+/// let output = processor.process(&input).unwrap();
+/// std::fs::write("output.gif", output).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct GifProcessor {
+    max_colors: Option<usize>,
+    color_space: ColorSpace,
+    merge_duplicates: bool,
+    encode_options: EncodeOptions,
+    filters: Vec<Arc<dyn FrameOp + Send>>,
+}
+
+impl GifProcessor {
+    /// Create a processor that re-encodes GIFs unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reduce every GIF to at most `max_colors` colors
+    ///
+    /// Returns an error unless `max_colors` is between 2 and 256.
+    pub fn max_colors(mut self, max_colors: usize) -> Result<Self> {
+        if !(2..=256).contains(&max_colors) {
+            anyhow::bail!("Color count must be between 2 and 256, got {}", max_colors);
+        }
+        self.max_colors = Some(max_colors);
+        Ok(self)
+    }
+
+    /// Color space used to match pixels to the reduced palette
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Merge runs of consecutive identical frames
+    pub fn merge_duplicates(mut self, merge: bool) -> Self {
+        self.merge_duplicates = merge;
+        self
+    }
+
+    /// Options used to encode the output
+    pub fn encode_options(mut self, options: EncodeOptions) -> Self {
+        self.encode_options = options;
+        self
+    }
+
+    /// Append a filter to the chain
+    ///
+    /// Filters run in the order they were added, on full-canvas frames with
+    /// straight (not premultiplied) alpha. Frames may be filtered in parallel.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: FrameOp + Send + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Decode `input`, apply the configured steps and return the encoded GIF
+    ///
+    /// Frames are composited, then filtered, merged and color-reduced in that
+    /// order.
+    pub fn process(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mut gif = Gif::from_bytes(input).context("Failed to load input GIF")?;
+        gif.ensure_nonempty()?;
+        gif.normalize()?;
+        gif.par_map_frames(Frame::unpremultiply);
+
+        for filter in &self.filters {
+            gif.par_map_frames(|frame| filter.apply_frame(frame));
+        }
+
+        if self.merge_duplicates {
+            gif.merge_identical_frames();
+        }
+
+        if let Some(max_colors) = self.max_colors {
+            compress::reduce_colors(&mut gif, max_colors, self.color_space)?;
+        }
+
+        gif.to_bytes_with_options(self.encode_options)
+            .context("Failed to encode output GIF")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    /// Invert the color channels
+    struct Invert;
+
+    impl FrameOp for Invert {
+        fn apply_frame(&self, frame: &mut Frame) {
+            for pixel in frame.data.chunks_exact_mut(4) {
+                pixel[..3].iter_mut().for_each(|c| *c = 255 - *c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_process_concurrently() {
+        assert_send_sync::<GifProcessor>();
+
+        let input = Arc::new(std::fs::read("tests/fixtures/colorful.gif").unwrap());
+        let processor = Arc::new(
+            GifProcessor::new()
+                .max_colors(16)
+                .unwrap()
+                .merge_duplicates(true)
+                .filter(Invert),
+        );
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let processor = Arc::clone(&processor);
+                let input = Arc::clone(&input);
+                thread::spawn(move || processor.process(&input))
+            })
+            .collect();
+        let outputs: Vec<Vec<u8>> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .collect();
+
+        assert!(outputs.windows(2).all(|pair| pair[0] == pair[1]));
+        let gif = Gif::from_bytes(&outputs[0]).unwrap();
+        assert_eq!(gif.frames.len(), 10);
+        assert!(processor.process(b"not a gif").is_err());
+    }
+
+    #[test]
+    fn test_max_colors_validated() {
+        assert!(GifProcessor::new().max_colors(1).is_err());
+        assert!(GifProcessor::new().max_colors(257).is_err());
+        assert!(GifProcessor::new().max_colors(256).is_ok());
+    }
+}
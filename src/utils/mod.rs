@@ -1,6 +1,9 @@
 // Utility functions and helpers
 
-use std::time::Duration;
+use crate::core::{Frame, Gif};
+use image::GrayImage;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 /// Format a duration as a human-readable string
 pub fn format_duration(duration: Duration) -> String {
@@ -14,6 +17,74 @@ pub fn format_duration(duration: Duration) -> String {
     }
 }
 
+/// Log target for stage timings; `main` only lets it through with `--timings`
+pub const TIMINGS_TARGET: &str = "gif_toolkit::timings";
+
+/// Wall-clock timer for the stages of an operation
+///
+/// Stages are recorded in the order they run. [`StageTimer::report`] logs
+/// them on [`TIMINGS_TARGET`] as e.g.
+/// `decode: 120ms, resize: 45ms, encode: 88ms`, followed by the total and the
+/// frame throughput.
+#[derive(Debug, Clone, Default)]
+pub struct StageTimer {
+    stages: Vec<(&'static str, Duration)>,
+}
+
+impl StageTimer {
+    /// Create a timer with no recorded stages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run a closure and record how long it took under `stage`
+    pub fn time<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.stages.push((stage, start.elapsed()));
+        result
+    }
+
+    /// Recorded stages and their durations, in order
+    pub fn stages(&self) -> &[(&'static str, Duration)] {
+        &self.stages
+    }
+
+    /// Sum of all recorded stage durations
+    pub fn total(&self) -> Duration {
+        self.stages.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// Stages as `name: duration` pairs separated by commas
+    pub fn summary(&self) -> String {
+        self.stages
+            .iter()
+            .map(|(stage, duration)| format!("{}: {}", stage, format_duration(*duration)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Log the stage summary, total time and throughput for `frames` frames
+    pub fn report(&self, frames: usize) {
+        let total = self.total();
+        let secs = total.as_secs_f64();
+        let throughput = if secs > 0.0 {
+            format!("{:.1} frames/s", frames as f64 / secs)
+        } else {
+            "n/a".to_string()
+        };
+
+        log::info!(target: TIMINGS_TARGET, "   Timings: {}", self.summary());
+        log::info!(
+            target: TIMINGS_TARGET,
+            "   Total: {} ({} frames, {})",
+            format_duration(total),
+            frames,
+            throughput
+        );
+    }
+}
+
 /// Format bytes as a human-readable string
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
@@ -43,10 +114,531 @@ pub fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> T {
     }
 }
 
+/// A set of frame indices parsed from a spec like `1,3,5-9,^2`
+///
+/// Indices are 0-based. Comma-separated terms are either a single index or an
+/// inclusive range `a-b`; a leading `^` excludes the term instead. Exclusions
+/// win over inclusions, and a spec with only exclusions selects every other frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameSelector {
+    include: Vec<(usize, usize)>,
+    exclude: Vec<(usize, usize)>,
+}
+
+impl FrameSelector {
+    /// Parse a selector spec
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+
+        for term in spec.split(',').map(str::trim) {
+            if term.is_empty() {
+                anyhow::bail!("Empty term in frame selection '{}'", spec);
+            }
+
+            let (target, term) = match term.strip_prefix('^') {
+                Some(rest) => (&mut exclude, rest.trim()),
+                None => (&mut include, term),
+            };
+
+            let range = match term.split_once('-') {
+                Some((start, end)) => (parse_index(start)?, parse_index(end)?),
+                None => {
+                    let index = parse_index(term)?;
+                    (index, index)
+                }
+            };
+
+            if range.0 > range.1 {
+                anyhow::bail!("Invalid frame range '{}': start is after end", term);
+            }
+            target.push(range);
+        }
+
+        Ok(Self { include, exclude })
+    }
+
+    /// Check whether a frame index is selected
+    pub fn contains(&self, index: usize) -> bool {
+        let in_ranges = |ranges: &[(usize, usize)]| {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&index))
+        };
+
+        (self.include.is_empty() || in_ranges(&self.include)) && !in_ranges(&self.exclude)
+    }
+
+    /// Check whether any explicitly included index is beyond `frame_count`
+    pub fn exceeds(&self, frame_count: usize) -> bool {
+        self.include.iter().any(|&(_, end)| end >= frame_count)
+    }
+}
+
+impl FromStr for FrameSelector {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Self::parse(spec)
+    }
+}
+
+/// Parse a single frame index from a selector term
+fn parse_index(text: &str) -> anyhow::Result<usize> {
+    text.trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid frame index '{}'", text.trim()))
+}
+
+/// Parse a `#rrggbb` (or `rrggbb`) hex color
+pub fn parse_hex_color(text: &str) -> anyhow::Result<[u8; 3]> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid color '{}', expected #rrggbb", text.trim());
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    Ok([channel(0), channel(2), channel(4)])
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` hex color; without alpha it is opaque
+pub fn parse_hex_rgba(text: &str) -> anyhow::Result<[u8; 4]> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() == 6 {
+        let [r, g, b] = parse_hex_color(hex)?;
+        return Ok([r, g, b, 255]);
+    }
+    if hex.len() != 8 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!(
+            "Invalid color '{}', expected #rrggbb or #rrggbbaa",
+            text.trim()
+        );
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    Ok([channel(0), channel(2), channel(4), channel(6)])
+}
+
+/// Mean squared error between two frames over all RGBA channels
+///
+/// Frames of different sizes are treated as maximally different.
+pub fn frame_mean_squared_error(a: &Frame, b: &Frame) -> f64 {
+    if a.width != b.width || a.height != b.height || a.data.len() != b.data.len() {
+        return 255.0 * 255.0;
+    }
+
+    if a.data.is_empty() {
+        return 0.0;
+    }
+
+    let sum: u64 = a
+        .data
+        .iter()
+        .zip(&b.data)
+        .map(|(x, y)| {
+            let diff = *x as i64 - *y as i64;
+            (diff * diff) as u64
+        })
+        .sum();
+
+    sum as f64 / a.data.len() as f64
+}
+
+/// Mean squared error between two GIFs, averaged over aligned frame pairs
+///
+/// Frames are compared 1:1 by index; callers should composite both GIFs and
+/// check that frame counts match first. Returns 0.0 if there are no frames.
+pub fn mean_squared_error(a: &Gif, b: &Gif) -> f64 {
+    let pairs = a.frames.len().min(b.frames.len());
+    if pairs == 0 {
+        return 0.0;
+    }
+
+    let total: f64 = a
+        .frames
+        .iter()
+        .zip(&b.frames)
+        .map(|(fa, fb)| frame_mean_squared_error(fa, fb))
+        .sum();
+
+    total / pairs as f64
+}
+
+/// PSNR of one output frame against the input frame it was aligned with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramePsnr {
+    /// Frame index in the second (output) GIF
+    pub index: usize,
+    /// Index of the aligned frame in the first (input) GIF
+    pub source: usize,
+    /// PSNR in decibels; infinite for identical frames
+    pub psnr: f64,
+}
+
+/// Pair each frame of `b` with a frame of `a`
+///
+/// With equal frame counts frames are paired 1:1 by index. Otherwise each
+/// frame of `b` is paired with the frame of `a` on screen at the time it
+/// starts, so GIFs whose frames were merged or dropped can still be compared.
+/// Returns `(index_in_a, index_in_b)` pairs.
+pub fn align_frames_by_time(a: &Gif, b: &Gif) -> Vec<(usize, usize)> {
+    if a.frames.is_empty() {
+        return Vec::new();
+    }
+    if a.frames.len() == b.frames.len() {
+        return (0..a.frames.len()).map(|i| (i, i)).collect();
+    }
+
+    let mut pairs = Vec::with_capacity(b.frames.len());
+    let mut source = 0;
+    let mut source_end = a.frames[0].delay as u32;
+    let mut start = 0u32;
+
+    for (index, frame) in b.frames.iter().enumerate() {
+        while start >= source_end && source + 1 < a.frames.len() {
+            source += 1;
+            source_end += a.frames[source].delay as u32;
+        }
+        pairs.push((source, index));
+        start += frame.delay as u32;
+    }
+
+    pairs
+}
+
+/// PSNR of every frame of `b` against its aligned frame of `a`
+///
+/// Frames are aligned with [`align_frames_by_time`]; both GIFs should be
+/// composited first.
+pub fn per_frame_psnr(a: &Gif, b: &Gif) -> Vec<FramePsnr> {
+    align_frames_by_time(a, b)
+        .into_iter()
+        .map(|(source, index)| FramePsnr {
+            index,
+            source,
+            psnr: psnr(frame_mean_squared_error(
+                &a.frames[source],
+                &b.frames[index],
+            )),
+        })
+        .collect()
+}
+
+/// The `count` frames with the lowest PSNR, worst first
+///
+/// Identical frames (infinite PSNR) are never included.
+pub fn worst_frames(scores: &[FramePsnr], count: usize) -> Vec<FramePsnr> {
+    let mut sorted: Vec<FramePsnr> = scores
+        .iter()
+        .filter(|s| s.psnr.is_finite())
+        .copied()
+        .collect();
+    sorted.sort_by(|x, y| x.psnr.total_cmp(&y.psnr).then(x.index.cmp(&y.index)));
+    sorted.truncate(count);
+    sorted
+}
+
+/// Pixel difference between one pair of aligned frames
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameDiff {
+    /// Frame index
+    pub index: usize,
+    /// Largest absolute difference of any RGBA channel (0-255)
+    pub max: u8,
+    /// Mean absolute difference over all RGBA channels
+    pub mean: f64,
+}
+
+/// Frame-by-frame comparison of two GIFs, see [`diff_report`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffReport {
+    /// Canvas size of the first GIF
+    pub dimensions_a: (u16, u16),
+    /// Canvas size of the second GIF
+    pub dimensions_b: (u16, u16),
+    /// Frame count of the first GIF
+    pub frame_count_a: usize,
+    /// Frame count of the second GIF
+    pub frame_count_b: usize,
+    /// Differences of the frame pairs both GIFs have (empty if the
+    /// dimensions differ)
+    pub frames: Vec<FrameDiff>,
+}
+
+impl DiffReport {
+    /// Whether both GIFs have the same canvas size
+    pub fn dimensions_match(&self) -> bool {
+        self.dimensions_a == self.dimensions_b
+    }
+
+    /// Whether both GIFs have the same number of frames
+    pub fn frame_counts_match(&self) -> bool {
+        self.frame_count_a == self.frame_count_b
+    }
+
+    /// Frames where some channel differs by more than `tolerance`
+    pub fn frames_exceeding(&self, tolerance: u8) -> impl Iterator<Item = &FrameDiff> {
+        self.frames.iter().filter(move |diff| diff.max > tolerance)
+    }
+
+    /// Whether the GIFs match in size and frame count and every frame is
+    /// within `tolerance`
+    pub fn is_within(&self, tolerance: u8) -> bool {
+        self.dimensions_match()
+            && self.frame_counts_match()
+            && self.frames_exceeding(tolerance).next().is_none()
+    }
+}
+
+/// Compare two GIFs frame by frame
+///
+/// Frames are compared 1:1 by index up to the shorter GIF; callers should
+/// composite both GIFs first so partial frames compare as displayed. Frame
+/// pairs of different sizes count as maximally different.
+pub fn diff_report(a: &Gif, b: &Gif) -> DiffReport {
+    let mut report = DiffReport {
+        dimensions_a: (a.width, a.height),
+        dimensions_b: (b.width, b.height),
+        frame_count_a: a.frames.len(),
+        frame_count_b: b.frames.len(),
+        frames: Vec::new(),
+    };
+
+    if !report.dimensions_match() {
+        return report;
+    }
+
+    for (index, (fa, fb)) in a.frames.iter().zip(&b.frames).enumerate() {
+        let diff = if fa.width != fb.width || fa.height != fb.height {
+            FrameDiff {
+                index,
+                max: 255,
+                mean: 255.0,
+            }
+        } else {
+            let (max, sum) =
+                fa.data
+                    .iter()
+                    .zip(&fb.data)
+                    .fold((0u8, 0u64), |(max, sum), (x, y)| {
+                        let d = x.abs_diff(*y);
+                        (max.max(d), sum + d as u64)
+                    });
+            FrameDiff {
+                index,
+                max,
+                mean: sum as f64 / fa.data.len().max(1) as f64,
+            }
+        };
+        report.frames.push(diff);
+    }
+
+    report
+}
+
+/// Encode bytes as standard base64 with padding (RFC 4648)
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Peak signal-to-noise ratio in decibels for 8-bit channels
+///
+/// Returns `f64::INFINITY` when the mean squared error is zero (identical input).
+pub fn psnr(mse: f64) -> f64 {
+    if mse <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    10.0 * (255.0 * 255.0 / mse).log10()
+}
+
+/// Convert an 8-bit sRGB channel value to linear light (0.0-1.0)
+pub fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light value (clamped to 0.0-1.0) back to an 8-bit sRGB channel
+pub fn linear_to_srgb(value: f64) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let encoded = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+/// WCAG 2 relative luminance of an sRGB color (0.0 = black, 1.0 = white)
+pub fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Convert an 8-bit sRGB color to Oklab `[L, a, b]` (Björn Ottosson, 2020)
+///
+/// Euclidean distance in Oklab tracks perceived color difference far better
+/// than distance in sRGB.
+pub fn srgb_to_oklab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let l = 0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b;
+    let m = 0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b;
+    let s = 0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b;
+    let [l, m, s] = [l, m, s].map(f64::cbrt);
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// Squared Euclidean distance between two Oklab colors
+pub fn oklab_distance_squared(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter().zip(&b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// WCAG 2 contrast ratio between two colors, from 1.0 (none) to 21.0 (black on white)
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Gaussian window radius and standard deviation used by [`ssim`] (11x11, sigma 1.5)
+const SSIM_WINDOW_RADIUS: i64 = 5;
+const SSIM_WINDOW_SIGMA: f64 = 1.5;
+
+/// Mean structural similarity index between two grayscale images
+///
+/// Uses the standard formulation of Wang et al.: local statistics are taken
+/// over an 11x11 Gaussian window (sigma 1.5) and the SSIM map is averaged over
+/// the whole image. Edges are handled by clamping. Returns a value in roughly
+/// `-1.0..=1.0`, where 1.0 means identical; images of different sizes score 0.0.
+pub fn ssim(a: &GrayImage, b: &GrayImage) -> f64 {
+    if a.dimensions() != b.dimensions() {
+        return 0.0;
+    }
+
+    let (width, height) = (a.width() as usize, a.height() as usize);
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+    let x: Vec<f64> = a.as_raw().iter().map(|&v| v as f64).collect();
+    let y: Vec<f64> = b.as_raw().iter().map(|&v| v as f64).collect();
+    let xx: Vec<f64> = x.iter().map(|v| v * v).collect();
+    let yy: Vec<f64> = y.iter().map(|v| v * v).collect();
+    let xy: Vec<f64> = x.iter().zip(&y).map(|(p, q)| p * q).collect();
+
+    let kernel = gaussian_kernel(SSIM_WINDOW_RADIUS, SSIM_WINDOW_SIGMA);
+    let mu_x = gaussian_blur(&x, width, height, &kernel);
+    let mu_y = gaussian_blur(&y, width, height, &kernel);
+    let e_xx = gaussian_blur(&xx, width, height, &kernel);
+    let e_yy = gaussian_blur(&yy, width, height, &kernel);
+    let e_xy = gaussian_blur(&xy, width, height, &kernel);
+
+    let total: f64 = (0..width * height)
+        .map(|i| {
+            let (mx, my) = (mu_x[i], mu_y[i]);
+            let var_x = e_xx[i] - mx * mx;
+            let var_y = e_yy[i] - my * my;
+            let cov = e_xy[i] - mx * my;
+
+            ((2.0 * mx * my + C1) * (2.0 * cov + C2))
+                / ((mx * mx + my * my + C1) * (var_x + var_y + C2))
+        })
+        .sum();
+
+    total / (width * height) as f64
+}
+
+/// Normalized 1D Gaussian kernel with the given radius
+fn gaussian_kernel(radius: i64, sigma: f64) -> Vec<f64> {
+    let weights: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f64 = weights.iter().sum();
+    weights.into_iter().map(|w| w / sum).collect()
+}
+
+/// Separable Gaussian blur of a row-major buffer with clamped edges
+fn gaussian_blur(values: &[f64], width: usize, height: usize, kernel: &[f64]) -> Vec<f64> {
+    let radius = (kernel.len() / 2) as i64;
+    let clamp_index = |i: i64, len: usize| i.clamp(0, len as i64 - 1) as usize;
+
+    let mut horizontal = vec![0.0; values.len()];
+    for y in 0..height {
+        let row = &values[y * width..(y + 1) * width];
+        for x in 0..width {
+            horizontal[y * width + x] = kernel
+                .iter()
+                .enumerate()
+                .map(|(k, w)| w * row[clamp_index(x as i64 + k as i64 - radius, width)])
+                .sum();
+        }
+    }
+
+    let mut blurred = vec![0.0; values.len()];
+    for y in 0..height {
+        for x in 0..width {
+            blurred[y * width + x] = kernel
+                .iter()
+                .enumerate()
+                .map(|(k, w)| {
+                    w * horizontal[clamp_index(y as i64 + k as i64 - radius, height) * width + x]
+                })
+                .sum();
+        }
+    }
+
+    blurred
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stage_timer_records_slow_stage() {
+        let mut timer = StageTimer::new();
+        let value = timer.time("sleep", || {
+            std::thread::sleep(Duration::from_millis(20));
+            42
+        });
+
+        assert_eq!(value, 42);
+        assert_eq!(timer.stages().len(), 1);
+        assert_eq!(timer.stages()[0].0, "sleep");
+        assert!(timer.stages()[0].1 >= Duration::from_millis(20));
+        assert_eq!(timer.total(), timer.stages()[0].1);
+        assert!(timer.summary().starts_with("sleep: "));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0 B");
@@ -62,4 +654,192 @@ mod tests {
         assert_eq!(clamp(0, 1, 10), 1);
         assert_eq!(clamp(15, 1, 10), 10);
     }
+
+    #[test]
+    fn test_srgb_linear_round_trip() {
+        assert_eq!(srgb_to_linear(0), 0.0);
+        assert_eq!(srgb_to_linear(255), 1.0);
+        assert!((srgb_to_linear(128) - 0.2158).abs() < 0.001);
+        for value in 0..=255u8 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio([255, 255, 255], [0, 0, 0]) - 21.0).abs() < 0.01);
+        assert_eq!(contrast_ratio([90, 90, 90], [90, 90, 90]), 1.0);
+        // #777 on white is just below the WCAG AA threshold
+        assert!((contrast_ratio([119, 119, 119], [255, 255, 255]) - 4.48).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_psnr_of_noised_copy() {
+        let data: Vec<u8> = (0..32 * 32 * 4).map(|i| (i % 200) as u8 + 20).collect();
+        let original = Frame::from_rgba(data.clone(), 32, 32);
+
+        // Perturb every color channel by a small amount
+        let noised_data: Vec<u8> = data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                if i % 4 == 3 {
+                    *v
+                } else {
+                    v.wrapping_add((i % 5) as u8)
+                }
+            })
+            .collect();
+        let noised = Frame::from_rgba(noised_data, 32, 32);
+
+        assert_eq!(frame_mean_squared_error(&original, &original), 0.0);
+        assert!(psnr(0.0).is_infinite());
+
+        let value = psnr(frame_mean_squared_error(&original, &noised));
+        assert!(value.is_finite());
+        assert!(value > 35.0, "PSNR too low: {}", value);
+
+        let mut gif_a = Gif::new();
+        gif_a.add_frame(original);
+        let mut gif_b = Gif::new();
+        gif_b.add_frame(noised);
+        assert_eq!(psnr(mean_squared_error(&gif_a, &gif_b)), value);
+    }
+
+    #[test]
+    fn test_per_frame_psnr_finds_noised_frame() {
+        let mut gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        gif.normalize().unwrap();
+
+        let mut noised = gif.clone();
+        for (i, value) in noised.frames[6].data.iter_mut().enumerate() {
+            if i % 4 != 3 {
+                *value = value.wrapping_add((i % 7) as u8);
+            }
+        }
+
+        let scores = per_frame_psnr(&gif, &noised);
+        assert_eq!(scores.len(), 10);
+        assert!(scores
+            .iter()
+            .filter(|s| s.index != 6)
+            .all(|s| s.psnr.is_infinite()));
+
+        let worst = worst_frames(&scores, 3);
+        assert_eq!(worst.len(), 1);
+        assert_eq!(worst[0].index, 6);
+    }
+
+    #[test]
+    fn test_align_frames_by_time() {
+        let mut a = Gif::new();
+        for delay in [10, 10, 20] {
+            let mut frame = Frame::new(1, 1);
+            frame.delay = delay;
+            a.add_frame(frame);
+        }
+
+        // Output with the first two frames merged into one
+        let mut b = Gif::new();
+        for delay in [20, 20] {
+            let mut frame = Frame::new(1, 1);
+            frame.delay = delay;
+            b.add_frame(frame);
+        }
+
+        assert_eq!(align_frames_by_time(&a, &b), vec![(0, 0), (2, 1)]);
+        assert_eq!(align_frames_by_time(&a, &a), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let gif = Gif::from_file("tests/fixtures/colorful.gif").unwrap();
+        let same = diff_report(&gif, &gif);
+        assert!(same.is_within(0));
+        assert_eq!(same.frames.len(), 10);
+        assert!(same.frames.iter().all(|d| d.max == 0 && d.mean == 0.0));
+
+        // Brighten every color channel of one frame
+        let mut brightened = gif.clone();
+        for (i, value) in brightened.frames[3].data.iter_mut().enumerate() {
+            if i % 4 != 3 {
+                *value = value.saturating_add(40);
+            }
+        }
+        let report = diff_report(&gif, &brightened);
+        let changed: Vec<usize> = report.frames_exceeding(0).map(|d| d.index).collect();
+        assert_eq!(changed, vec![3]);
+        assert!(report.frames[3].mean > 0.0);
+        assert!(!report.is_within(0));
+        assert!(report.is_within(40));
+
+        let mut shorter = gif.clone();
+        shorter.frames.pop();
+        assert!(!diff_report(&gif, &shorter).frame_counts_match());
+    }
+
+    #[test]
+    fn test_ssim_identical_and_inverted() {
+        let image = GrayImage::from_fn(48, 32, |x, y| image::Luma([((x * 5 + y * 3) % 256) as u8]));
+        let mut inverted = image.clone();
+        image::imageops::invert(&mut inverted);
+
+        let same = ssim(&image, &image);
+        assert!((same - 1.0).abs() < 1e-9, "SSIM with itself: {}", same);
+
+        let opposite = ssim(&image, &inverted);
+        assert!(opposite < 0.2, "SSIM with inverted copy: {}", opposite);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#ff8000").unwrap(), [255, 128, 0]);
+        assert_eq!(parse_hex_color("00FF7f").unwrap(), [0, 255, 127]);
+        assert!(parse_hex_color("#fff").is_err());
+        assert!(parse_hex_color("#gg0000").is_err());
+
+        assert_eq!(parse_hex_rgba("#ff8000").unwrap(), [255, 128, 0, 255]);
+        assert_eq!(parse_hex_rgba("#ff800000").unwrap(), [255, 128, 0, 0]);
+        assert!(parse_hex_rgba("#ff80000").is_err());
+    }
+
+    #[test]
+    fn test_frame_selector_single_indices() {
+        let selector = FrameSelector::parse("1,3").unwrap();
+        let selected: Vec<usize> = (0..6).filter(|&i| selector.contains(i)).collect();
+        assert_eq!(selected, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_frame_selector_ranges_and_exclusions() {
+        let selector: FrameSelector = "1,3,5-9,^2,^7".parse().unwrap();
+        let selected: Vec<usize> = (0..12).filter(|&i| selector.contains(i)).collect();
+        assert_eq!(selected, vec![1, 3, 5, 6, 8, 9]);
+        assert!(selector.exceeds(9));
+        assert!(!selector.exceeds(10));
+
+        // Only exclusions: everything else is selected
+        let selector = FrameSelector::parse("^0-1").unwrap();
+        let selected: Vec<usize> = (0..4).filter(|&i| selector.contains(i)).collect();
+        assert_eq!(selected, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_frame_selector_invalid() {
+        assert!(FrameSelector::parse("").is_err());
+        assert!(FrameSelector::parse("1,,2").is_err());
+        assert!(FrameSelector::parse("a").is_err());
+        assert!(FrameSelector::parse("5-2").is_err());
+    }
 }
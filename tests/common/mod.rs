@@ -0,0 +1,27 @@
+// Shared helpers for integration tests
+
+use gif_toolkit::testgen;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// Directory holding the test fixtures
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// Generate any missing fixture GIFs so tests don't depend on running
+/// `test_gen` beforehand
+pub fn ensure_fixtures() {
+    static GENERATE: Once = Once::new();
+
+    GENERATE.call_once(|| {
+        let dir = fixtures_dir();
+        let missing = testgen::FIXTURE_NAMES
+            .iter()
+            .any(|name| !dir.join(name).exists());
+
+        if missing {
+            testgen::generate_all(dir.to_str().unwrap()).expect("Failed to generate fixtures");
+        }
+    });
+}
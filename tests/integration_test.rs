@@ -7,7 +7,10 @@
 // 4. Loading back
 // 5. Verifying data integrity
 
-use gif_toolkit::core::{Frame, Gif};
+mod common;
+
+use gif_toolkit::core::{Frame, Gif, Loop};
+use gif_toolkit::testgen;
 use std::path::Path;
 
 #[test]
@@ -99,7 +102,7 @@ fn test_gif_default() {
     assert_eq!(gif.width, 0);
     assert_eq!(gif.height, 0);
     assert_eq!(gif.frames.len(), 0);
-    assert_eq!(gif.loop_count, 0);
+    assert_eq!(gif.loop_count, Loop::Infinite);
     assert!(gif.global_palette.is_none());
 }
 
@@ -119,7 +122,7 @@ fn test_add_multiple_frames() {
     let mut gif = Gif::new();
 
     // Add multiple frames
-    for i in 0..5 {
+    for _ in 0..5 {
         let frame = Frame::new(50, 50);
         gif.add_frame(frame);
     }
@@ -157,3 +160,13 @@ fn test_frame_from_rgba_invalid_length() {
     let invalid_data = vec![0u8; 100]; // Wrong length for a 10x10 image (should be 400)
     Frame::from_rgba(invalid_data, 10, 10);
 }
+
+#[test]
+fn test_ensure_fixtures() {
+    common::ensure_fixtures();
+
+    for name in testgen::FIXTURE_NAMES {
+        let path = common::fixtures_dir().join(name);
+        assert!(path.exists(), "missing fixture: {}", path.display());
+    }
+}